@@ -19,7 +19,6 @@
 //! - Dependency resolution order demonstration
 
 use std::collections::HashMap;
-use std::fs;
 use zzstat::{StatContext, StatId};
 use zzstat_json::StatTemplateManager;
 
@@ -31,8 +30,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         "{}/examples/complex_dependencies.json",
         env!("CARGO_MANIFEST_DIR")
     );
-    let json = fs::read_to_string(&json_path)?;
-    let manager = StatTemplateManager::from_json(&json)?;
+    let manager = StatTemplateManager::from_json_file(&json_path)?;
     let mut resolver = zzstat::StatResolver::new();
     let context = StatContext::new();
 
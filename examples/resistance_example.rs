@@ -16,7 +16,6 @@
 //! - Clamp bounds (0-90% or 0-95%)
 
 use std::collections::HashMap;
-use std::fs;
 use zzstat::{StatContext, StatId};
 use zzstat_json::StatTemplateManager;
 
@@ -25,8 +24,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Load template configuration
     let json_path = format!("{}/examples/resistance.json", env!("CARGO_MANIFEST_DIR"));
-    let json = fs::read_to_string(&json_path)?;
-    let manager = StatTemplateManager::from_json(&json)?;
+    let manager = StatTemplateManager::from_json_file(&json_path)?;
     let mut resolver = zzstat::StatResolver::new();
     let context = StatContext::new();
 
@@ -65,8 +63,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Load Vitality template from warrior.json (or create a simple one)
     // For this example, we'll use warrior.json's Vitality template
     let warrior_json_path = format!("{}/examples/warrior.json", env!("CARGO_MANIFEST_DIR"));
-    let warrior_json = fs::read_to_string(&warrior_json_path)?;
-    let warrior_manager = StatTemplateManager::from_json(&warrior_json)?;
+    let warrior_manager = StatTemplateManager::from_json_file(&warrior_json_path)?;
 
     // Apply Vitality first (needed for resistance calculations)
     let mut vitality_params = HashMap::new();
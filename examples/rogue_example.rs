@@ -17,7 +17,6 @@
 //! - Class penalties and bonuses
 
 use std::collections::HashMap;
-use std::fs;
 use zzstat::{StatContext, StatId};
 use zzstat_json::StatTemplateManager;
 
@@ -26,8 +25,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Load template configuration
     let json_path = format!("{}/examples/rogue.json", env!("CARGO_MANIFEST_DIR"));
-    let json = fs::read_to_string(&json_path)?;
-    let manager = StatTemplateManager::from_json(&json)?;
+    let manager = StatTemplateManager::from_json_file(&json_path)?;
     let mut resolver = zzstat::StatResolver::new();
     let context = StatContext::new();
 
@@ -16,7 +16,6 @@
 //! - Multiple sources (constant, scaling, map)
 
 use std::collections::HashMap;
-use std::fs;
 use zzstat::{StatContext, StatId};
 use zzstat_json::StatTemplateManager;
 
@@ -25,8 +24,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Load template configuration
     let json_path = format!("{}/examples/warrior.json", env!("CARGO_MANIFEST_DIR"));
-    let json = fs::read_to_string(&json_path)?;
-    let manager = StatTemplateManager::from_json(&json)?;
+    let manager = StatTemplateManager::from_json_file(&json_path)?;
     let mut resolver = zzstat::StatResolver::new();
     let context = StatContext::new();
 
@@ -1,5 +1,8 @@
+use crate::error::YamlStatError;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 
 /// JSON configuration structure for stat definitions and templates.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -11,15 +14,1446 @@ pub struct StatConfig {
     /// Direct stat definitions (for immediate use)
     #[serde(default)]
     pub stats: HashMap<String, StatDefinition>,
+
+    /// Global constants available to every `{{param}}` (per-call params take precedence)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub constants: HashMap<String, f64>,
+
+    /// Shared transforms referenced from `transforms` arrays via `{"$ref": "<name>"}`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub snippets: HashMap<String, TransformConfig>,
+
+    /// Shared sources referenced from `sources` arrays via `{"$ref": "<name>"}`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub source_snippets: HashMap<String, SourceConfig>,
+
+    /// Transforms appended to every stat and template (after their own transforms)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub default_transforms: Vec<TransformConfig>,
+
+    /// Old stat names mapped to their canonical names (for renamed stats)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub aliases: HashMap<String, String>,
+
+    /// Combine each stat's multiplicative transforms into one additive percentage
+    /// (two ×1.1 give ×1.2 instead of ×1.21)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub multiplicative_as_additive: bool,
+
+    /// Scaling sources without a `level` read the `level` param (or constant) and fail
+    /// without one, instead of defaulting to 1.0
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub require_explicit_level: bool,
+}
+
+impl StatConfig {
+    /// Parses a config from JSON, expanding `{"$ref": "<name>"}` snippet references.
+    ///
+    /// References are looked up in `snippets` (inside `transforms`, `default_transforms`, and
+    /// conditional branches) or `source_snippets` (inside `sources`).
+    ///
+    /// # Arguments
+    ///
+    /// * `json_content` - JSON string containing the config
+    ///
+    /// # Returns
+    ///
+    /// The parsed `StatConfig`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if a reference names a missing snippet, or
+    /// `YamlStatError::JsonParseError` if the JSON is invalid.
+    pub fn from_json(json_content: &str) -> Result<Self, YamlStatError> {
+        Self::from_value(serde_json::from_str(json_content)?)
+    }
+
+    /// Parses an already-decoded JSON document (see `from_json`).
+    pub(crate) fn from_value(mut value: Value) -> Result<Self, YamlStatError> {
+        expand_snippet_refs(&mut value)?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Produces a readable multi-line outline of the config for debugging.
+    ///
+    /// Lists every stat and template (sorted by name) with its sources and transforms.
+    /// Parameter references are shown symbolically (e.g. `{{level}}`).
+    pub fn describe(&self) -> String {
+        let mut out = String::new();
+
+        if !self.stats.is_empty() {
+            out.push_str("Stats:\n");
+            let mut names: Vec<&String> = self.stats.keys().collect();
+            names.sort();
+            for name in names {
+                let definition = &self.stats[name];
+                out.push_str(&format!("  {}\n", name));
+                describe_pipeline(
+                    &mut out,
+                    &definition.base,
+                    &definition.sources,
+                    &definition.transforms,
+                );
+            }
+        }
+
+        if !self.templates.is_empty() {
+            out.push_str("Templates:\n");
+            let mut names: Vec<&String> = self.templates.keys().collect();
+            names.sort();
+            for name in names {
+                let template = &self.templates[name];
+                match &template.description {
+                    Some(description) => out.push_str(&format!("  {} - {}\n", name, description)),
+                    None => out.push_str(&format!("  {}\n", name)),
+                }
+                describe_pipeline(
+                    &mut out,
+                    &template.base,
+                    &template.sources,
+                    &template.transforms,
+                );
+            }
+        }
+
+        if !self.default_transforms.is_empty() {
+            out.push_str("Default transforms:\n");
+            for transform in &self.default_transforms {
+                out.push_str(&format!(
+                    "  - {}\n",
+                    describe_item(transform.kind_label(), transform)
+                ));
+            }
+        }
+
+        out
+    }
+
+    /// Lists the steps that compute a stat, in the order they run.
+    ///
+    /// A templated stat starts with `("template", <name>)`. Each source follows as
+    /// `("source", ...)` (sources are summed), then each transform as `(kind, ...)`,
+    /// including the config-wide defaults. A templated stat lists its template's sources and
+    /// transforms before its own, as the loader registers them. Steps are described by their
+    /// `name`, or by a short formula in terms of `x` (e.g. `x × 1.1`) when unnamed.
+    ///
+    /// # Arguments
+    ///
+    /// * `stat_name` - Stat to describe
+    ///
+    /// # Returns
+    ///
+    /// Ordered `(phase_or_kind, description)` pairs; empty if the stat does not exist.
+    pub fn pipeline(&self, stat_name: &str) -> Vec<(String, String)> {
+        use crate::template::StatTemplateManager;
+
+        let Some(definition) = self.stats.get(stat_name) else {
+            return Vec::new();
+        };
+        let mut steps = Vec::new();
+
+        let mut merged = None;
+        if let Some(template) = &definition.template {
+            steps.push(("template".to_string(), template.clone()));
+            let templates = StatTemplateManager::resolve_extends(self.templates.clone())
+                .unwrap_or_else(|_| self.templates.clone());
+            merged = StatTemplateManager::find_template(&templates, template)
+                .map(|template| definition.merged_with(template));
+        }
+        let (base, sources, transforms, ignore_default_transforms) = match &merged {
+            Some(merged) => (
+                &merged.base,
+                &merged.sources,
+                &merged.transforms,
+                merged.ignore_default_transforms,
+            ),
+            None => (
+                &definition.base,
+                &definition.sources,
+                &definition.transforms,
+                definition.ignore_default_transforms,
+            ),
+        };
+
+        let base_source = base
+            .clone()
+            .map(|value| SourceConfig::Constant { value, name: None });
+        for source in base_source.iter().chain(sources) {
+            let description = source
+                .name()
+                .map(str::to_string)
+                .unwrap_or_else(|| render_formula(None, &None, std::slice::from_ref(source), &[]));
+            steps.push(("source".to_string(), description));
+        }
+
+        let default_transforms: &[TransformConfig] = if ignore_default_transforms {
+            &[]
+        } else {
+            &self.default_transforms
+        };
+        let transforms = transforms.iter().chain(default_transforms);
+        let transforms = if self.multiplicative_as_additive {
+            TransformConfig::combine_multiplicatives(transforms.clone(), &self.constants)
+                .unwrap_or_else(|_| transforms.map(Cow::Borrowed).collect())
+        } else {
+            transforms.map(Cow::Borrowed).collect()
+        };
+        for transform in &transforms {
+            let description = transform.name().map(str::to_string).unwrap_or_else(|| {
+                render_formula(Some("x"), &None, &[], std::slice::from_ref(transform))
+            });
+            steps.push((transform.kind_label().to_string(), description));
+        }
+
+        steps
+    }
+
+    /// Renders the config as Markdown documentation.
+    ///
+    /// Every stat and template (sorted by name) gets a section with its template or
+    /// description, the `{{param}}` names it uses, and a formula built from its sources and
+    /// transforms (e.g. `clamp(({{base}} + Vitality × 3) × 1.2, 100, ∞)`). Transforms without
+    /// a simple algebraic form are shown as `kind(...)`.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+
+        if !self.stats.is_empty() {
+            out.push_str("# Stats\n");
+            for (name, definition) in sorted(&self.stats) {
+                out.push_str(&format!("\n## {}\n\n", name));
+                if let Some(template) = &definition.template {
+                    out.push_str(&format!("Template: `{}`\n\n", template));
+                }
+                markdown_pipeline(
+                    &mut out,
+                    definition.template.as_deref(),
+                    &definition.base,
+                    &definition.sources,
+                    &definition.transforms,
+                );
+            }
+        }
+
+        if !self.templates.is_empty() {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str("# Templates\n");
+            for (name, template) in sorted(&self.templates) {
+                out.push_str(&format!("\n## {}\n\n", name));
+                if let Some(description) = &template.description {
+                    out.push_str(&format!("{}\n\n", description));
+                }
+                markdown_pipeline(
+                    &mut out,
+                    template.extends.as_deref(),
+                    &template.base,
+                    &template.sources,
+                    &template.transforms,
+                );
+            }
+        }
+
+        out
+    }
+
+    /// Returns the canonical name for `name`, following `aliases` (unaliased names map to themselves).
+    pub fn canonical_stat_name<'a>(&'a self, name: &'a str) -> &'a str {
+        canonical_name(&self.aliases, name)
+    }
+
+    /// Rewrites every stat reference (including `@stat` values) in stats, templates,
+    /// snippets, and default transforms to its canonical name.
+    pub(crate) fn apply_aliases(&mut self) {
+        if self.aliases.is_empty() {
+            return;
+        }
+        let aliases = &self.aliases;
+
+        let pipelines = self
+            .stats
+            .values_mut()
+            .map(|d| (&mut d.base, &mut d.sources, &mut d.transforms))
+            .chain(
+                self.templates
+                    .values_mut()
+                    .map(|t| (&mut t.base, &mut t.sources, &mut t.transforms)),
+            );
+        for (base, sources, transforms) in pipelines {
+            let refs = base
+                .iter_mut()
+                .filter_map(SourceValue::stat_ref_mut)
+                .chain(sources.iter_mut().flat_map(SourceConfig::stat_refs_mut))
+                .chain(
+                    transforms
+                        .iter_mut()
+                        .flat_map(TransformConfig::stat_refs_mut),
+                );
+            for stat in refs {
+                rename_stat_ref(aliases, stat);
+            }
+        }
+
+        let shared = self
+            .default_transforms
+            .iter_mut()
+            .chain(self.snippets.values_mut())
+            .flat_map(TransformConfig::stat_refs_mut)
+            .chain(
+                self.source_snippets
+                    .values_mut()
+                    .flat_map(SourceConfig::stat_refs_mut),
+            );
+        for stat in shared {
+            rename_stat_ref(aliases, stat);
+        }
+    }
+
+    /// Resolves every stat with an `expect` value and compares it against the expectation.
+    ///
+    /// Values within 0.01 of the expectation pass, matching the tolerance used in the
+    /// examples. Stats are checked in name order, each in a resolver holding only it and its
+    /// dependencies (see `subset`), so one broken stat does not fail the others.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - StatContext for resolution
+    ///
+    /// # Errors
+    ///
+    /// Returns every failure: a `YamlStatError::InvalidConfig` per mismatch (with the actual
+    /// and expected values) or per stat that failed to resolve. If the config itself cannot be
+    /// built, that error is the only one returned.
+    pub fn self_test(&self, context: &zzstat::StatContext) -> Result<(), Vec<YamlStatError>> {
+        const TOLERANCE: f64 = 0.01;
+
+        crate::loader::StatLoader::build_resolver(self.clone()).map_err(|e| vec![e])?;
+
+        let mut failures = Vec::new();
+        for (name, definition) in sorted(&self.stats) {
+            let Some(expected) = definition.expect else {
+                continue;
+            };
+            match self.resolve_isolated(name, context) {
+                Ok(resolved) if (resolved.value - expected).abs() <= TOLERANCE => {}
+                Ok(resolved) => failures.push(YamlStatError::InvalidConfig(format!(
+                    "stat '{}': expected {}, got {}",
+                    name, expected, resolved.value
+                ))),
+                Err(e) => failures.push(YamlStatError::InvalidConfig(format!(
+                    "stat '{}': {}",
+                    name, e
+                ))),
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+
+    /// Resolves one stat in a resolver built from `subset(&[stat_name])`.
+    ///
+    /// zzstat resolves the whole graph on every `resolve`, so in a resolver holding the full
+    /// config any failing stat fails them all.
+    fn resolve_isolated(
+        &self,
+        stat_name: &str,
+        context: &zzstat::StatContext,
+    ) -> Result<zzstat::ResolvedStat, YamlStatError> {
+        let mut resolver = crate::loader::StatLoader::build_resolver(self.subset(&[stat_name])?)?;
+        Ok(resolver.resolve(&zzstat::StatId::from_str(stat_name), context)?)
+    }
+
+    /// Resolves every stat under each param set and reports values that are NaN or infinite.
+    ///
+    /// Each param set is layered over the config constants. A param set whose config fails
+    /// to build is reported once. Otherwise each stat is resolved with only its dependencies
+    /// (see `subset`), so a stat that fails to resolve (e.g. a zero `ratio_scale`
+    /// denominator) is reported on its own rather than failing every stat.
+    ///
+    /// # Arguments
+    ///
+    /// * `params_sets` - Representative parameter sets to try
+    /// * `context` - StatContext for resolution
+    ///
+    /// # Errors
+    ///
+    /// Returns a `YamlStatError::InvalidConfig` per non-finite or failing stat and per param
+    /// set that fails to build, each naming the params it happened under.
+    pub fn check_no_nan(
+        &self,
+        params_sets: &[HashMap<String, f64>],
+        context: &zzstat::StatContext,
+    ) -> Result<(), Vec<YamlStatError>> {
+        let mut failures = Vec::new();
+        for params in params_sets {
+            let label = sorted(params)
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let mut config = self.clone();
+            config
+                .constants
+                .extend(params.iter().map(|(k, v)| (k.clone(), *v)));
+            if let Err(e) = crate::loader::StatLoader::build_resolver(config.clone()) {
+                failures.push(YamlStatError::InvalidConfig(format!(
+                    "params {{{}}}: {}",
+                    label, e
+                )));
+                continue;
+            }
+
+            for (name, _) in sorted(&self.stats) {
+                match config.resolve_isolated(name, context) {
+                    Ok(stat) if stat.value.is_finite() => {}
+                    Ok(stat) => failures.push(YamlStatError::InvalidConfig(format!(
+                        "stat '{}' is {} with params {{{}}}",
+                        name, stat.value, label
+                    ))),
+                    Err(e) => failures.push(YamlStatError::InvalidConfig(format!(
+                        "stat '{}' with params {{{}}}: {}",
+                        name, label, e
+                    ))),
+                }
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+
+    /// Runs every static check on the config and returns all problems found.
+    ///
+    /// Checks conditional operators, `template` and `extends` references, that numbers
+    /// (including numeric strings) are finite, and that clamp bounds are ordered. Unknown
+    /// `type` tags never get this far: the tagged enums reject them while parsing.
+    ///
+    /// # Returns
+    ///
+    /// Every problem as a `YamlStatError::InvalidConfig` prefixed with where it was found;
+    /// empty if the config is valid.
+    pub fn validate_strict(&self) -> Vec<YamlStatError> {
+        let mut problems = Vec::new();
+        self.check_template_refs(&mut problems);
+
+        for pipeline in self.pipelines() {
+            let transforms = flatten_transforms(&pipeline.transforms);
+            check_operators(&pipeline.location, &transforms, &mut problems);
+            check_finite(&pipeline, &transforms, &mut problems);
+            check_clamp_bounds(&pipeline.location, &transforms, &mut problems);
+        }
+
+        problems
+    }
+
+    /// Checks that no stat depends on itself through stat references.
+    ///
+    /// The dependency graph uses every stat a stat's sources and transforms reference (`map`,
+    /// `conditional`, stat-based clamps, ...). Stats that instantiate a template get the
+    /// template's references, and their own, prefixed with their entity (`hero:ATK`
+    /// depending on `STR` means `hero:STR`), exactly as `apply_template` registers them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` with the cycle path (e.g. `A -> B -> A`), or
+    /// if a template's `extends`/`include` chain is invalid.
+    pub fn validate(&self) -> Result<(), YamlStatError> {
+        use crate::template::StatTemplateManager;
+
+        let templates = StatTemplateManager::resolve_extends(self.templates.clone())?;
+        let refs = |transforms: &'_ [TransformConfig]| -> Vec<String> {
+            transforms
+                .iter()
+                .flat_map(TransformConfig::stat_refs)
+                .map(|name| self.canonical_stat_name(name).to_string())
+                .collect()
+        };
+
+        let mut graph: HashMap<String, Vec<String>> = HashMap::new();
+        for (name, definition) in &self.stats {
+            let template = definition
+                .template
+                .as_ref()
+                .and_then(|name| StatTemplateManager::find_template(&templates, name));
+            // Templated stats register their own sources and transforms with the template
+            let dependencies = match template {
+                Some(template) => {
+                    let merged = definition.merged_with(template);
+                    let entity_id = name.rfind(':').map_or("", |colon| &name[..colon]);
+                    let mut template_refs: Vec<String> = merged
+                        .stat_refs()
+                        .into_iter()
+                        .map(|name| self.canonical_stat_name(name).to_string())
+                        .collect();
+                    if !merged.ignore_default_transforms {
+                        template_refs.extend(refs(&self.default_transforms));
+                    }
+                    template_refs
+                        .iter()
+                        .map(|dependency| {
+                            StatTemplateManager::dependency_stat_id(entity_id, dependency)
+                                .to_string()
+                        })
+                        .collect()
+                }
+                None => {
+                    let mut dependencies: Vec<String> = definition
+                        .base
+                        .iter()
+                        .filter_map(SourceValue::stat_ref)
+                        .chain(definition.sources.iter().flat_map(SourceConfig::stat_refs))
+                        .map(|name| self.canonical_stat_name(name).to_string())
+                        .chain(refs(&definition.transforms))
+                        .collect();
+                    if !definition.ignore_default_transforms {
+                        dependencies.extend(refs(&self.default_transforms));
+                    }
+                    dependencies
+                }
+            };
+            graph.insert(self.canonical_stat_name(name).to_string(), dependencies);
+        }
+
+        let mut done = HashSet::new();
+        for (name, _) in sorted(&graph) {
+            let mut chain = Vec::new();
+            if let Some(cycle) = find_cycle(&graph, name, &mut chain, &mut done) {
+                return Err(YamlStatError::InvalidConfig(format!(
+                    "Stat dependency cycle: {}",
+                    cycle.join(" -> ")
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Rewrites purely numeric strings (e.g. `"100"`) as numbers, so equal configs serialize
+    /// the same way.
+    ///
+    /// Covers stats, templates, and `default_transforms`, including nested conditional
+    /// branches. Params, expressions, percentages (`"20%"`), and `@stat` references are kept.
+    pub fn normalize(&mut self) {
+        let pipelines = self
+            .stats
+            .values_mut()
+            .map(|d| (&mut d.base, &mut d.sources, &mut d.transforms))
+            .chain(
+                self.templates
+                    .values_mut()
+                    .map(|t| (&mut t.base, &mut t.sources, &mut t.transforms)),
+            );
+        for (base, sources, transforms) in pipelines {
+            let values = base
+                .iter_mut()
+                .chain(sources.iter_mut().flat_map(SourceConfig::values_mut))
+                .chain(transforms.iter_mut().flat_map(TransformConfig::values_mut));
+            values.for_each(SourceValue::normalize);
+        }
+        self.default_transforms
+            .iter_mut()
+            .flat_map(TransformConfig::values_mut)
+            .for_each(SourceValue::normalize);
+    }
+
+    /// Runs balance checks that need representative inputs and returns a warning per finding.
+    ///
+    /// For every `clamp` among a stat's own transforms, the stat is resolved up to that
+    /// clamp with `params` over the config constants. A pre-clamp value outside the `min`/`max`
+    /// bounds means the clamp is always active for realistic inputs, which is usually a
+    /// mistake. Stat-based bounds (`min_stat`/`max_stat`) are not checked.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Representative parameters (take precedence over constants)
+    ///
+    /// # Returns
+    ///
+    /// Warnings in stat name order, prefixed with the stat; empty if nothing was found.
+    pub fn lint(&self, params: &HashMap<String, f64>) -> Vec<String> {
+        let mut constants = self.constants.clone();
+        constants.extend(params.iter().map(|(k, v)| (k.clone(), *v)));
+        let context = zzstat::StatContext::new();
+
+        let mut warnings = Vec::new();
+        for (name, definition) in sorted(&self.stats) {
+            for (index, transform) in definition.transforms.iter().enumerate() {
+                let TransformConfig::Clamp { min, max, .. } = transform else {
+                    continue;
+                };
+                let bound = |value: &Option<SourceValue>| {
+                    value.as_ref().and_then(|v| v.resolve(&constants).ok())
+                };
+                let (min, max) = (bound(min), bound(max));
+                if min.is_none() && max.is_none() {
+                    continue;
+                }
+
+                // Resolve the stat with only the transforms that run before this clamp
+                let mut before_clamp = self.clone();
+                before_clamp.constants = constants.clone();
+                if let Some(stat) = before_clamp.stats.get_mut(name.as_str()) {
+                    stat.transforms.truncate(index);
+                    stat.ignore_default_transforms = true;
+                }
+                let value = crate::loader::StatLoader::build_resolver(before_clamp).and_then(
+                    |mut resolver| {
+                        Ok(resolver
+                            .resolve(&zzstat::StatId::from_str(name), &context)?
+                            .value)
+                    },
+                );
+
+                let value = match value {
+                    Ok(value) => value,
+                    Err(e) => {
+                        warnings.push(format!(
+                            "stat '{}': could not resolve the pre-clamp value: {}",
+                            name, e
+                        ));
+                        continue;
+                    }
+                };
+                if let Some(min) = min
+                    && value < min
+                {
+                    warnings.push(format!(
+                        "stat '{}': pre-clamp value {} is below the clamp min {}, so the clamp is always active",
+                        name, value, min
+                    ));
+                } else if let Some(max) = max
+                    && value > max
+                {
+                    warnings.push(format!(
+                        "stat '{}': pre-clamp value {} is above the clamp max {}, so the clamp is always active",
+                        name, value, max
+                    ));
+                }
+            }
+        }
+
+        warnings
+    }
+
+    /// Resolves the values a stat's own base and sources contribute before any transforms.
+    ///
+    /// Constant and scaling sources are evaluated with `params` over the config constants.
+    /// Scaling sources whose level is a stat need a resolver and are skipped. A templated
+    /// stat lists its template's base and sources first (with the template's default params
+    /// and the stat's `params` under `params`), then its own, as the loader registers them.
+    ///
+    /// # Arguments
+    ///
+    /// * `stat_name` - Stat to inspect (a key of `stats`)
+    /// * `params` - Parameters for `{{param}}` values (take precedence over constants)
+    ///
+    /// # Returns
+    ///
+    /// Each source's optional name and value, in declaration order (`base` first, unnamed).
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if the stat or its template does not exist or a
+    /// value cannot be resolved.
+    pub fn resolved_sources(
+        &self,
+        stat_name: &str,
+        params: &HashMap<String, f64>,
+    ) -> Result<Vec<(Option<String>, f64)>, YamlStatError> {
+        use crate::template::StatTemplateManager;
+
+        let definition = self.stats.get(stat_name).ok_or_else(|| {
+            YamlStatError::InvalidConfig(format!("Stat not found: {}", stat_name))
+        })?;
+
+        let template = match &definition.template {
+            Some(name) => {
+                let templates = StatTemplateManager::resolve_extends(self.templates.clone())?;
+                let template =
+                    StatTemplateManager::find_template(&templates, name).ok_or_else(|| {
+                        YamlStatError::InvalidConfig(format!("Template not found: {}", name))
+                    })?;
+                Some(definition.merged_with(template))
+            }
+            None => None,
+        };
+
+        let mut merged = self.constants.clone();
+        if let Some(template) = &template {
+            merged.extend(template.params.iter().map(|(k, v)| (k.clone(), *v)));
+            merged.extend(definition.params.iter().map(|(k, v)| (k.clone(), *v)));
+        }
+        merged.extend(params.iter().map(|(k, v)| (k.clone(), *v)));
+
+        let (base, sources) = match &template {
+            Some(template) => (&template.base, &template.sources),
+            None => (&definition.base, &definition.sources),
+        };
+        let base_source = base
+            .clone()
+            .map(|value| SourceConfig::Constant { value, name: None });
+
+        base_source
+            .iter()
+            .chain(sources)
+            .filter(|source| source.stat_refs().is_empty())
+            .map(|source| {
+                let value = StatTemplateManager::resolve_source_value(source, &merged, false)?;
+                Ok((source.name().map(str::to_string), value))
+            })
+            .collect()
+    }
+
+    /// Returns a self-contained config with only the named stats and everything they need.
+    ///
+    /// Stats referenced by the named stats (through their bases, sources, transforms,
+    /// templates, or the default transforms) are pulled in transitively, along with the
+    /// templates they instantiate and those templates' `extends` parents and `include`
+    /// mixins. References through aliases are followed to the aliased stat, and a templated
+    /// stat's references are prefixed with its entity (`hero:HP` needing `STR` pulls in
+    /// `hero:STR`). Constants, snippets, aliases, and default transforms are kept as-is.
+    ///
+    /// # Arguments
+    ///
+    /// * `stat_names` - Stats to keep (keys of `stats`)
+    ///
+    /// # Returns
+    ///
+    /// The trimmed `StatConfig`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if a named stat or a referenced template does
+    /// not exist.
+    pub fn subset(&self, stat_names: &[&str]) -> Result<StatConfig, YamlStatError> {
+        use crate::template::StatTemplateManager;
+
+        let mut stats = HashMap::new();
+        let mut templates = HashMap::new();
+        let mut pending: Vec<String> = stat_names.iter().map(|name| name.to_string()).collect();
+
+        for name in stat_names {
+            if !self.stats.contains_key(*name) {
+                return Err(YamlStatError::InvalidConfig(format!(
+                    "Stat not found: {}",
+                    name
+                )));
+            }
+        }
+
+        while let Some(name) = pending.pop() {
+            let name = self.canonical_stat_name(&name).to_string();
+            // References to stats outside `stats` (e.g. entity stats) have nothing to copy
+            let Some(definition) = self.stats.get(&name) else {
+                continue;
+            };
+            if stats.insert(name.clone(), definition.clone()).is_some() {
+                continue;
+            }
+
+            // Templated stats resolve every reference within their entity, like validate
+            let entity_id = match definition.template {
+                Some(_) => name.rfind(':').map_or("", |colon| &name[..colon]),
+                None => "",
+            };
+            let prefixed = |dependency: &str| {
+                StatTemplateManager::dependency_stat_id(entity_id, dependency).to_string()
+            };
+            pending.extend(
+                definition
+                    .base
+                    .iter()
+                    .filter_map(SourceValue::stat_ref)
+                    .chain(definition.sources.iter().flat_map(SourceConfig::stat_refs))
+                    .chain(
+                        definition
+                            .transforms
+                            .iter()
+                            .flat_map(TransformConfig::stat_refs),
+                    )
+                    .chain(
+                        self.default_transforms
+                            .iter()
+                            .flat_map(TransformConfig::stat_refs),
+                    )
+                    .map(prefixed),
+            );
+
+            let mut pending_templates: Vec<&str> =
+                definition.template.iter().map(String::as_str).collect();
+            while let Some(current) = pending_templates.pop() {
+                let (key, template) = self
+                    .templates
+                    .iter()
+                    .find(|(key, t)| *key == current || t.id.as_deref() == Some(current))
+                    .ok_or_else(|| {
+                        YamlStatError::InvalidConfig(format!("Template not found: {}", current))
+                    })?;
+                if templates.insert(key.clone(), template.clone()).is_some() {
+                    continue;
+                }
+                pending.extend(template.stat_refs().into_iter().map(prefixed));
+                pending_templates.extend(template.extends.as_deref());
+                pending_templates.extend(template.include.iter().map(String::as_str));
+            }
+        }
+
+        Ok(StatConfig {
+            templates,
+            stats,
+            ..self.clone()
+        })
+    }
+
+    /// Reports stats and templates that name a template that does not exist.
+    fn check_template_refs(&self, problems: &mut Vec<YamlStatError>) {
+        use crate::template::StatTemplateManager;
+
+        let references = sorted(&self.stats)
+            .into_iter()
+            .filter_map(|(name, d)| Some((format!("stat '{}'", name), d.template.as_ref()?)))
+            .chain(sorted(&self.templates).into_iter().flat_map(|(name, t)| {
+                let extends = t
+                    .extends
+                    .iter()
+                    .map(move |parent| (format!("template '{}' extends", name), parent));
+                let includes = t
+                    .include
+                    .iter()
+                    .map(move |mixin| (format!("template '{}' includes", name), mixin));
+                extends.chain(includes)
+            }));
+
+        for (location, template_name) in references {
+            if StatTemplateManager::find_template(&self.templates, template_name).is_none() {
+                problems.push(YamlStatError::InvalidConfig(format!(
+                    "{}: unknown template '{}'",
+                    location, template_name
+                )));
+            }
+        }
+    }
+
+    /// Lists every source/transform pipeline in the config, sorted within each section.
+    fn pipelines(&self) -> Vec<Pipeline<'_>> {
+        let mut pipelines = Vec::new();
+
+        for (name, definition) in sorted(&self.stats) {
+            pipelines.push(Pipeline {
+                location: format!("stat '{}'", name),
+                values: definition
+                    .base
+                    .iter()
+                    .chain(source_values(&definition.sources))
+                    .collect(),
+                transforms: definition.transforms.iter().collect(),
+            });
+        }
+        for (name, template) in sorted(&self.templates) {
+            pipelines.push(Pipeline {
+                location: format!("template '{}'", name),
+                values: template
+                    .base
+                    .iter()
+                    .chain(source_values(&template.sources))
+                    .collect(),
+                transforms: template.transforms.iter().collect(),
+            });
+        }
+        for (name, snippet) in sorted(&self.snippets) {
+            pipelines.push(Pipeline {
+                location: format!("snippet '{}'", name),
+                values: Vec::new(),
+                transforms: vec![snippet],
+            });
+        }
+        for (name, snippet) in sorted(&self.source_snippets) {
+            pipelines.push(Pipeline {
+                location: format!("source snippet '{}'", name),
+                values: snippet.values(),
+                transforms: Vec::new(),
+            });
+        }
+        pipelines.push(Pipeline {
+            location: "default_transforms".to_string(),
+            values: Vec::new(),
+            transforms: self.default_transforms.iter().collect(),
+        });
+
+        pipelines
+    }
+}
+
+/// The values and transforms of one stat, template, or snippet, for `validate_strict`.
+struct Pipeline<'a> {
+    location: String,
+    values: Vec<&'a SourceValue>,
+    transforms: Vec<&'a TransformConfig>,
+}
+
+/// Looks up the canonical name of a possibly aliased stat.
+pub(crate) fn canonical_name<'a>(aliases: &'a HashMap<String, String>, name: &'a str) -> &'a str {
+    aliases.get(name).map_or(name, String::as_str)
+}
+
+/// Points a stat name, or an `@stat` value, at the canonical stat.
+fn rename_stat_ref(aliases: &HashMap<String, String>, stat: &mut String) {
+    let renamed = match stat.trim().strip_prefix('@') {
+        Some(name) => format!("@{}", canonical_name(aliases, name.trim())),
+        None => canonical_name(aliases, stat).to_string(),
+    };
+    *stat = renamed;
+}
+
+/// Returns a map's entries sorted by key.
+pub(crate) fn sorted<V>(map: &HashMap<String, V>) -> Vec<(&String, &V)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by_key(|(name, _)| *name);
+    entries
+}
+
+/// Returns every value of a list of sources.
+fn source_values(sources: &[SourceConfig]) -> impl Iterator<Item = &SourceValue> {
+    sources.iter().flat_map(SourceConfig::values)
+}
+
+/// Returns the transforms plus every transform nested in a conditional/range branch or best-of option.
+fn flatten_transforms<'a>(transforms: &[&'a TransformConfig]) -> Vec<&'a TransformConfig> {
+    let mut flat = Vec::new();
+    let mut pending = transforms.to_vec();
+    while let Some(transform) = pending.pop() {
+        if let TransformConfig::Conditional {
+            then, else_then, ..
+        }
+        | TransformConfig::Range {
+            then, else_then, ..
+        } = transform
+        {
+            pending.push(then);
+            pending.extend(else_then.as_deref());
+        }
+        if let TransformConfig::BestOf { options, .. } = transform {
+            pending.extend(options);
+        }
+        flat.push(transform);
+    }
+    flat.reverse();
+    flat
+}
+
+/// Depth-first search for a dependency cycle through `name`.
+///
+/// `chain` holds the stats on the current path; `done` holds stats already known to be
+/// cycle-free. Returns the cycle path, starting and ending with the same stat.
+fn find_cycle<'a>(
+    graph: &'a HashMap<String, Vec<String>>,
+    name: &'a str,
+    chain: &mut Vec<&'a str>,
+    done: &mut HashSet<&'a str>,
+) -> Option<Vec<String>> {
+    if done.contains(name) {
+        return None;
+    }
+    if let Some(start) = chain.iter().position(|&stat| stat == name) {
+        let mut cycle: Vec<String> = chain[start..].iter().map(|s| s.to_string()).collect();
+        cycle.push(name.to_string());
+        return Some(cycle);
+    }
+
+    chain.push(name);
+    for dependency in graph.get(name).into_iter().flatten() {
+        if let Some(cycle) = find_cycle(graph, dependency, chain, done) {
+            return Some(cycle);
+        }
+    }
+    chain.pop();
+    done.insert(name);
+    None
+}
+
+/// Reports conditionals with an unknown operator, or a comparison without a value.
+fn check_operators(
+    location: &str,
+    transforms: &[&TransformConfig],
+    problems: &mut Vec<YamlStatError>,
+) {
+    use crate::transform_conditional::condition_threshold;
+
+    for transform in transforms {
+        let operators: Vec<(&str, Option<f64>)> = match transform {
+            TransformConfig::Conditional { all, any, .. } if !all.is_empty() || !any.is_empty() => {
+                all.iter()
+                    .chain(any)
+                    .map(|c| (c.operator.as_str(), c.condition_value))
+                    .collect()
+            }
+            TransformConfig::Conditional {
+                operator,
+                condition_value,
+                ..
+            }
+            | TransformConfig::ConditionalStat {
+                operator,
+                condition_value,
+                ..
+            } => vec![(operator, *condition_value)],
+            _ => continue,
+        };
+        for (operator, condition_value) in operators {
+            if let Err(e) = condition_threshold(operator, condition_value) {
+                problems.push(YamlStatError::InvalidConfig(format!("{}: {}", location, e)));
+            }
+        }
+    }
+}
+
+/// Reports NaN or infinite numbers, and strings that are neither numbers nor `{{param}}`.
+fn check_finite(
+    pipeline: &Pipeline,
+    transforms: &[&TransformConfig],
+    problems: &mut Vec<YamlStatError>,
+) {
+    let values = pipeline
+        .values
+        .iter()
+        .copied()
+        .chain(pipeline.transforms.iter().flat_map(|t| t.values()));
+
+    for value in values {
+        if !value.param_names().is_empty() || value.stat_ref().is_some() {
+            continue;
+        }
+        match value.resolve(&HashMap::new()) {
+            Ok(n) if n.is_finite() => {}
+            Ok(n) => problems.push(YamlStatError::InvalidConfig(format!(
+                "{}: non-finite value {}",
+                pipeline.location, n
+            ))),
+            Err(e) => problems.push(YamlStatError::InvalidConfig(format!(
+                "{}: {}",
+                pipeline.location, e
+            ))),
+        }
+    }
+
+    for transform in transforms {
+        if let TransformConfig::Conditional {
+            condition_value: Some(condition_value),
+            ..
+        }
+        | TransformConfig::ConditionalStat {
+            condition_value: Some(condition_value),
+            ..
+        } = transform
+            && !condition_value.is_finite()
+        {
+            problems.push(YamlStatError::InvalidConfig(format!(
+                "{}: non-finite condition_value {}",
+                pipeline.location, condition_value
+            )));
+        }
+        if let TransformConfig::Conditional { all, any, .. } = transform {
+            for condition_value in all.iter().chain(any).filter_map(|c| c.condition_value) {
+                if !condition_value.is_finite() {
+                    problems.push(YamlStatError::InvalidConfig(format!(
+                        "{}: non-finite condition_value {}",
+                        pipeline.location, condition_value
+                    )));
+                }
+            }
+        }
+        if let TransformConfig::Range { min, max, .. } = transform
+            && !(min.is_finite() && max.is_finite())
+        {
+            problems.push(YamlStatError::InvalidConfig(format!(
+                "{}: non-finite range bounds {}..{}",
+                pipeline.location, min, max
+            )));
+        }
+    }
+}
+
+/// Reports clamps, fraction clamps, and ranges whose numeric `min` is greater than their `max`.
+fn check_clamp_bounds(
+    location: &str,
+    transforms: &[&TransformConfig],
+    problems: &mut Vec<YamlStatError>,
+) {
+    for transform in transforms {
+        if let TransformConfig::Clamp {
+            min: Some(min),
+            max: Some(max),
+            ..
+        } = transform
+            && let (Ok(min), Ok(max)) = (min.resolve(&HashMap::new()), max.resolve(&HashMap::new()))
+            && min > max
+        {
+            problems.push(YamlStatError::InvalidConfig(format!(
+                "{}: clamp min {} is greater than max {}",
+                location, min, max
+            )));
+        }
+        if let TransformConfig::ClampToStatFraction {
+            min_fraction: Some(min),
+            max_fraction: Some(max),
+            ..
+        } = transform
+            && let (Ok(min), Ok(max)) = (min.resolve(&HashMap::new()), max.resolve(&HashMap::new()))
+            && min > max
+        {
+            problems.push(YamlStatError::InvalidConfig(format!(
+                "{}: clamp_to_stat_fraction min_fraction {} is greater than max_fraction {}",
+                location, min, max
+            )));
+        }
+        if let TransformConfig::Range { min, max, .. } = transform
+            && min > max
+        {
+            problems.push(YamlStatError::InvalidConfig(format!(
+                "{}: range min {} is greater than max {}",
+                location, min, max
+            )));
+        }
+    }
+}
+
+/// Appends the base, sources, and transforms of a stat or template to a `describe` outline.
+fn describe_pipeline(
+    out: &mut String,
+    base: &Option<SourceValue>,
+    sources: &[SourceConfig],
+    transforms: &[TransformConfig],
+) {
+    if let Some(base) = base {
+        out.push_str(&format!("    base: {}\n", base));
+    }
+    if !sources.is_empty() {
+        out.push_str("    sources:\n");
+        for source in sources {
+            out.push_str(&format!(
+                "      - {}\n",
+                describe_item(source.kind_label(), source)
+            ));
+        }
+    }
+    if !transforms.is_empty() {
+        out.push_str("    transforms:\n");
+        for transform in transforms {
+            out.push_str(&format!(
+                "      - {}\n",
+                describe_item(transform.kind_label(), transform)
+            ));
+        }
+    }
+}
+
+/// Writes the parameter and formula lines of a Markdown section.
+///
+/// `inherited` names a template whose value the pipeline starts from.
+fn markdown_pipeline(
+    out: &mut String,
+    inherited: Option<&str>,
+    base: &Option<SourceValue>,
+    sources: &[SourceConfig],
+    transforms: &[TransformConfig],
+) {
+    let params: std::collections::BTreeSet<&str> =
+        pipeline_params(base, sources, transforms).collect();
+    if !params.is_empty() {
+        let names: Vec<String> = params.iter().map(|p| format!("`{}`", p)).collect();
+        out.push_str(&format!("Parameters: {}\n\n", names.join(", ")));
+    }
+    out.push_str(&format!(
+        "Formula: `{}`\n",
+        render_formula(inherited, base, sources, transforms)
+    ));
+}
+
+/// Renders a pipeline as a readable expression: the sum of the sources, then each transform.
+fn render_formula(
+    inherited: Option<&str>,
+    base: &Option<SourceValue>,
+    sources: &[SourceConfig],
+    transforms: &[TransformConfig],
+) -> String {
+    let mut terms: Vec<String> = inherited
+        .map(str::to_string)
+        .into_iter()
+        .chain(base.iter().map(SourceValue::to_string))
+        .collect();
+    for source in sources {
+        terms.push(match source {
+            SourceConfig::Constant { value, .. } => value.to_string(),
+            SourceConfig::Percentage { of, percent, .. } => format!("{}% × {}", percent, of),
+            SourceConfig::Curve { level, .. } => format!("curve({})", level),
+            SourceConfig::Scaling {
+                base, scale, level, ..
+            } => {
+                let level = match level {
+                    Some(LevelValue::Value(value)) => value.to_string(),
+                    Some(LevelValue::Stat(StatLevel::Stat { stat })) => stat.clone(),
+                    None => "1".to_string(),
+                };
+                match base {
+                    SourceValue::Number(n) if *n == 0.0 => format!("{} × {}", scale, level),
+                    _ => format!("{} + {} × {}", base, scale, level),
+                }
+            }
+        });
+    }
+    let mut expr = if terms.is_empty() {
+        "0".to_string()
+    } else {
+        terms.join(" + ")
+    };
+
+    // Parenthesizes a sum so it can be multiplied
+    let grouped = |expr: &str| {
+        let mut depth = 0;
+        let top_level_sum = expr.chars().any(|c| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+            c == '+' && depth == 0
+        });
+        if top_level_sum {
+            format!("({})", expr)
+        } else {
+            expr.to_string()
+        }
+    };
+    let bound =
+        |value: &Option<SourceValue>, stat: &Option<String>, unbounded: &str| match (value, stat) {
+            (Some(value), _) => value.to_string(),
+            (None, Some(stat)) => stat.clone(),
+            (None, None) => unbounded.to_string(),
+        };
+    for transform in transforms {
+        expr = match transform {
+            TransformConfig::Multiplicative { value, .. } => {
+                format!("{} × {}", grouped(&expr), value)
+            }
+            TransformConfig::Additive { value, .. } => format!("{} + {}", expr, value),
+            TransformConfig::Subtractive { value, .. } => format!("{} - {}", expr, value),
+            TransformConfig::Divide { value, .. } => format!("{} / {}", grouped(&expr), value),
+            TransformConfig::Power { exponent, .. } => format!("{}^{}", grouped(&expr), exponent),
+            TransformConfig::PercentAdd { percent, .. } => {
+                format!("{} × (1 + {}%)", grouped(&expr), percent)
+            }
+            TransformConfig::Min { value, .. } => format!("max({}, {})", expr, value),
+            TransformConfig::Max { value, .. } => format!("min({}, {})", expr, value),
+            TransformConfig::Map {
+                dependencies,
+                multiplier,
+                ..
+            } => {
+                let names: Vec<&str> = dependencies.iter().map(String::as_str).collect();
+                let sum = names.join(" + ");
+                match multiplier {
+                    Some(multiplier) => format!("{} + {} × {}", expr, grouped(&sum), multiplier),
+                    None => format!("{} + {}", expr, sum),
+                }
+            }
+            TransformConfig::Clamp {
+                min,
+                max,
+                min_stat,
+                max_stat,
+                ..
+            } => format!(
+                "clamp({}, {}, {})",
+                expr,
+                bound(min, min_stat, "-∞"),
+                bound(max, max_stat, "∞")
+            ),
+            TransformConfig::ClampToStatFraction {
+                stat,
+                min_fraction,
+                max_fraction,
+                ..
+            } => {
+                let side = |fraction: &Option<SourceValue>, unbounded: &str| match fraction {
+                    Some(fraction) => format!("{} × {}", fraction, stat),
+                    None => unbounded.to_string(),
+                };
+                format!(
+                    "clamp({}, {}, {})",
+                    expr,
+                    side(min_fraction, "-∞"),
+                    side(max_fraction, "∞")
+                )
+            }
+            other => format!("{}({})", other.kind_label(), expr),
+        };
+    }
+    expr
+}
+
+/// Renders a source/transform as `kind field=value ... (name)` from its serialized fields.
+fn describe_item(kind: &str, item: &impl Serialize) -> String {
+    let Ok(Value::Object(fields)) = serde_json::to_value(item) else {
+        return kind.to_string();
+    };
+
+    let mut line = kind.to_string();
+    for (key, value) in &fields {
+        if key == "type" || key == "name" || value.is_null() {
+            continue;
+        }
+        match value {
+            Value::String(s) => line.push_str(&format!(" {}={}", key, s)),
+            other => line.push_str(&format!(" {}={}", key, other)),
+        }
+    }
+    if let Some(Value::String(name)) = fields.get("name") {
+        line.push_str(&format!(" ({})", name));
+    }
+    line
+}
+
+/// Replaces `$ref` objects in stat and template definitions with the referenced snippets.
+fn expand_snippet_refs(config: &mut Value) -> Result<(), YamlStatError> {
+    let transform_snippets = config.get("snippets").cloned().unwrap_or(Value::Null);
+    let source_snippets = config
+        .get("source_snippets")
+        .cloned()
+        .unwrap_or(Value::Null);
+
+    // Snippets may use other snippets (e.g. in a conditional branch)
+    for (key, snippets, nested_branches) in [
+        ("snippets", &transform_snippets, true),
+        ("source_snippets", &source_snippets, false),
+    ] {
+        if let Some(Value::Object(entries)) = config.get_mut(key) {
+            for (name, snippet) in entries.iter_mut() {
+                let mut chain = vec![name.clone()];
+                expand_item(snippet, snippets, key, nested_branches, &mut chain)?;
+            }
+        }
+    }
+
+    if let Some(transforms) = config.get_mut("default_transforms") {
+        expand_array(transforms, &transform_snippets, "snippets", true)?;
+    }
+
+    for section in ["stats", "templates"] {
+        let Some(Value::Object(definitions)) = config.get_mut(section) else {
+            continue;
+        };
+        for definition in definitions.values_mut() {
+            if let Some(sources) = definition.get_mut("sources") {
+                expand_array(sources, &source_snippets, "source_snippets", false)?;
+            }
+            if let Some(transforms) = definition.get_mut("transforms") {
+                expand_array(transforms, &transform_snippets, "snippets", true)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Expands `$ref` entries of a JSON array (and, for transforms, nested conditional branches
+/// and `best_of` options).
+fn expand_array(
+    items: &mut Value,
+    snippets: &Value,
+    snippet_key: &str,
+    nested_branches: bool,
+) -> Result<(), YamlStatError> {
+    let Value::Array(items) = items else {
+        return Ok(());
+    };
+    for item in items.iter_mut() {
+        expand_item(
+            item,
+            snippets,
+            snippet_key,
+            nested_branches,
+            &mut Vec::new(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Expands a single `$ref` object in place.
+///
+/// `chain` holds the snippets being expanded around this item; a snippet that refers back to
+/// one of them (directly or through a branch) is reported as a cycle.
+fn expand_item(
+    item: &mut Value,
+    snippets: &Value,
+    snippet_key: &str,
+    nested_branches: bool,
+    chain: &mut Vec<String>,
+) -> Result<(), YamlStatError> {
+    let outer = chain.len();
+    while let Some(reference) = item.get("$ref") {
+        let name = reference.as_str().ok_or_else(|| {
+            YamlStatError::InvalidConfig(format!("$ref must be a string: {}", reference))
+        })?;
+        if chain.iter().any(|seen| seen == name) {
+            return Err(YamlStatError::InvalidConfig(format!(
+                "snippet cycle: {} -> {}",
+                chain.join(" -> "),
+                name
+            )));
+        }
+        let snippet = snippets.get(name).cloned().ok_or_else(|| {
+            YamlStatError::InvalidConfig(format!("Snippet not found in {}: {}", snippet_key, name))
+        })?;
+        chain.push(name.to_string());
+        *item = snippet;
+    }
+
+    if nested_branches {
+        for branch in ["then", "else_then"] {
+            if let Some(nested) = item.get_mut(branch).filter(|n| n.is_object()) {
+                expand_item(nested, snippets, snippet_key, nested_branches, chain)?;
+            }
+        }
+        if let Some(Value::Array(options)) = item.get_mut("options") {
+            for option in options {
+                expand_item(option, snippets, snippet_key, nested_branches, chain)?;
+            }
+        }
+    }
+    chain.truncate(outer);
+    Ok(())
 }
 
 /// Stat template - parameterizable stat definition
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct StatTemplate {
+    /// Stable identifier for cross-references (optional, the map key is used otherwise)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Parent template (map key or `id`) whose sources and transforms come first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+
+    /// Mixin templates whose sources and transforms are added, in order, after the parent's
+    /// and before this template's own (a mixin's `base` becomes a constant source)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+
     /// Template description
     #[serde(default)]
     pub description: Option<String>,
 
+    /// Base value shorthand (treated as a constant source before `sources`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<SourceValue>,
+
     /// Stat sources (additive)
     #[serde(default)]
     pub sources: Vec<SourceConfig>,
@@ -27,11 +1461,35 @@ pub struct StatTemplate {
     /// Stat transformations
     #[serde(default)]
     pub transforms: Vec<TransformConfig>,
+
+    /// Default parameter values, used for params the caller (or entity) does not supply
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub params: HashMap<String, f64>,
+
+    /// Free-form labels (e.g. `"percent"`), used to exempt stats from difficulty scaling
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Skip the config-wide `default_transforms` for this stat
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ignore_default_transforms: bool,
 }
 
 /// Single stat definition
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct StatDefinition {
+    /// Template to instantiate for this stat (its sources/transforms come first)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+
+    /// Parameters for `template`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub params: HashMap<String, f64>,
+
+    /// Base value shorthand (treated as a constant source before `sources`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<SourceValue>,
+
     /// Stat sources (additive)
     #[serde(default)]
     pub sources: Vec<SourceConfig>,
@@ -39,6 +1497,86 @@ pub struct StatDefinition {
     /// Stat transformations
     #[serde(default)]
     pub transforms: Vec<TransformConfig>,
+
+    /// Skip the config-wide `default_transforms` for this stat
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ignore_default_transforms: bool,
+
+    /// Value the stat is expected to resolve to (checked by `StatConfig::self_test`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect: Option<f64>,
+}
+
+impl StatDefinition {
+    /// Merges the entry into the (flattened) template it instantiates.
+    ///
+    /// The template's base, sources, and transforms come first, then the entry's own base,
+    /// sources, and transforms, so the config-wide defaults still run last. The defaults are
+    /// skipped if either the template or the entry opts out.
+    pub(crate) fn merged_with(&self, template: &StatTemplate) -> StatTemplate {
+        let mut merged = template.clone();
+        let base_source = self
+            .base
+            .clone()
+            .map(|value| SourceConfig::Constant { value, name: None });
+        merged
+            .sources
+            .extend(base_source.into_iter().chain(self.sources.iter().cloned()));
+        merged.transforms.extend(self.transforms.iter().cloned());
+        merged.ignore_default_transforms |= self.ignore_default_transforms;
+        merged
+    }
+}
+
+impl StatTemplate {
+    /// Returns a copy with string params substituted into every `{{param}}` placeholder,
+    /// including stat names, `@stat` values, and context keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::JsonParseError` if the substituted template no longer parses.
+    pub(crate) fn with_string_params(
+        &self,
+        strings: &HashMap<String, String>,
+    ) -> Result<StatTemplate, YamlStatError> {
+        let mut value = serde_json::to_value(self)?;
+        substitute_json_strings(&mut value, strings);
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Returns the (unprefixed) names of every stat the template depends on, including an
+    /// `@stat` base.
+    pub(crate) fn stat_refs(&self) -> Vec<&str> {
+        self.base
+            .iter()
+            .filter_map(SourceValue::stat_ref)
+            .chain(self.sources.iter().flat_map(SourceConfig::stat_refs))
+            .chain(self.transforms.iter().flat_map(TransformConfig::stat_refs))
+            .collect()
+    }
+
+    /// Returns every `{{param}}` name referenced by the template's base, sources, and
+    /// transforms, including those nested in conditional branches.
+    pub fn required_params(&self) -> HashSet<String> {
+        pipeline_params(&self.base, &self.sources, &self.transforms)
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// Returns every `{{param}}` name referenced by a base, sources, and transforms.
+fn pipeline_params<'a>(
+    base: &'a Option<SourceValue>,
+    sources: &'a [SourceConfig],
+    transforms: &'a [TransformConfig],
+) -> impl Iterator<Item = &'a str> {
+    let sources = sources.iter().flat_map(SourceConfig::values);
+    let transforms = transforms.iter().flat_map(TransformConfig::values);
+
+    base.iter()
+        .chain(sources)
+        .chain(transforms)
+        .flat_map(SourceValue::param_names)
 }
 
 /// Source configuration
@@ -62,14 +1600,290 @@ pub enum SourceConfig {
         base: SourceValue,
         /// Scale factor
         scale: SourceValue,
-        /// Level (optional, can be taken from context or parameter)
-        level: Option<SourceValue>,
+        /// Level (optional, a value, `{{param}}`, or `{"type": "stat", "stat": "..."}`)
+        level: Option<LevelValue>,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Curve source - linear interpolation between `[level, value]` control points
+    ///
+    /// Levels outside the points use the nearest endpoint's value.
+    #[serde(rename = "curve")]
+    Curve {
+        /// Level to evaluate at (f64 or "{{param}}" string)
+        level: SourceValue,
+        /// Control points as `[level, value]`, sorted by level (at least two)
+        points: Vec<[f64; 2]>,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Percentage source - `percent`% of another stat's resolved value
+    #[serde(rename = "percentage")]
+    Percentage {
+        /// Stat to take the percentage of (entity-prefixed like map dependencies)
+        #[serde(deserialize_with = "stat_name::of")]
+        of: String,
+        /// Percentage points (f64 or "{{param}}" string; 20 = 20%)
+        percent: SourceValue,
         /// Description (optional, for readability)
         #[serde(default, skip_serializing_if = "Option::is_none")]
         name: Option<String>,
     },
 }
 
+impl SourceConfig {
+    /// Returns the variant's `type` tag as used in JSON (e.g. `"constant"`).
+    pub fn kind_label(&self) -> &'static str {
+        match self {
+            SourceConfig::Constant { .. } => "constant",
+            SourceConfig::Scaling { .. } => "scaling",
+            SourceConfig::Curve { .. } => "curve",
+            SourceConfig::Percentage { .. } => "percentage",
+        }
+    }
+
+    /// Returns the optional `name` label.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            SourceConfig::Constant { name, .. }
+            | SourceConfig::Scaling { name, .. }
+            | SourceConfig::Curve { name, .. }
+            | SourceConfig::Percentage { name, .. } => name.as_deref(),
+        }
+    }
+
+    /// Returns every value in the source (for parameter scanning).
+    pub(crate) fn values(&self) -> Vec<&SourceValue> {
+        match self {
+            SourceConfig::Constant { value, .. } => vec![value],
+            SourceConfig::Curve { level, .. } => vec![level],
+            SourceConfig::Percentage { percent, .. } => vec![percent],
+            SourceConfig::Scaling {
+                base, scale, level, ..
+            } => [
+                Some(base),
+                Some(scale),
+                level.as_ref().and_then(LevelValue::as_value),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+        }
+    }
+
+    /// Mutable counterpart of `values`.
+    pub(crate) fn values_mut(&mut self) -> Vec<&mut SourceValue> {
+        match self {
+            SourceConfig::Constant { value, .. } => vec![value],
+            SourceConfig::Curve { level, .. } => vec![level],
+            SourceConfig::Percentage { percent, .. } => vec![percent],
+            SourceConfig::Scaling {
+                base, scale, level, ..
+            } => {
+                let mut values = vec![base, scale];
+                if let Some(LevelValue::Value(level)) = level {
+                    values.push(level);
+                }
+                values
+            }
+        }
+    }
+
+    /// Returns the names of every stat the source depends on.
+    pub(crate) fn stat_refs(&self) -> Vec<&str> {
+        match self {
+            SourceConfig::Constant { value, .. } => value.stat_ref().into_iter().collect(),
+            SourceConfig::Curve { .. } => Vec::new(),
+            SourceConfig::Scaling { level, .. } => level
+                .as_ref()
+                .and_then(LevelValue::stat)
+                .into_iter()
+                .collect(),
+            SourceConfig::Percentage { of, .. } => vec![of.as_str()],
+        }
+    }
+
+    /// Mutable counterpart of `stat_refs`, for rewriting stat names (e.g. aliases).
+    ///
+    /// An `@stat` constant yields its whole string, `@` included.
+    pub(crate) fn stat_refs_mut(&mut self) -> Vec<&mut String> {
+        match self {
+            SourceConfig::Constant { value, .. } => value.stat_ref_mut().into_iter().collect(),
+            SourceConfig::Scaling {
+                level: Some(LevelValue::Stat(StatLevel::Stat { stat })),
+                ..
+            } => vec![stat],
+            SourceConfig::Percentage { of, .. } => vec![of],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Evaluates a curve source: linear interpolation between `[level, value]` points.
+///
+/// Levels before the first or after the last point use that endpoint's value.
+///
+/// # Errors
+///
+/// Returns `YamlStatError::InvalidConfig` if fewer than two points are given, they are
+/// not sorted by level, or the level is NaN or infinite.
+pub(crate) fn curve_value(points: &[[f64; 2]], level: f64) -> Result<f64, YamlStatError> {
+    if !level.is_finite() {
+        return Err(YamlStatError::InvalidConfig(format!(
+            "Curve level must be finite, got {}",
+            level
+        )));
+    }
+    if points.len() < 2 {
+        return Err(YamlStatError::InvalidConfig(format!(
+            "Curve needs at least two points, got {}",
+            points.len()
+        )));
+    }
+    if points.windows(2).any(|pair| pair[0][0] > pair[1][0]) {
+        return Err(YamlStatError::InvalidConfig(
+            "Curve points must be sorted by level".to_string(),
+        ));
+    }
+
+    let (first, last) = (points[0], points[points.len() - 1]);
+    if level <= first[0] {
+        return Ok(first[1]);
+    }
+    if level >= last[0] {
+        return Ok(last[1]);
+    }
+    let upper = points.partition_point(|point| point[0] <= level);
+    let ([x0, y0], [x1, y1]) = (points[upper - 1], points[upper]);
+    Ok(y0 + (y1 - y0) * (level - x0) / (x1 - x0))
+}
+
+/// Scaling level - a value or another stat whose resolved value is used as the level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LevelValue {
+    /// Numeric value or "{{param}}" string
+    Value(SourceValue),
+    /// Stat reference (`{"type": "stat", "stat": "Intelligence"}`)
+    Stat(StatLevel),
+}
+
+/// Stat reference used as a scaling level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum StatLevel {
+    /// Level taken from a resolved stat
+    #[serde(rename = "stat")]
+    Stat {
+        /// Stat name
+        #[serde(deserialize_with = "stat_name::stat")]
+        stat: String,
+    },
+}
+
+impl LevelValue {
+    /// Returns the value, unless the level references a stat.
+    pub(crate) fn as_value(&self) -> Option<&SourceValue> {
+        match self {
+            LevelValue::Value(value) => Some(value),
+            LevelValue::Stat(_) => None,
+        }
+    }
+
+    /// Returns the referenced stat name, if any.
+    pub(crate) fn stat(&self) -> Option<&str> {
+        match self {
+            LevelValue::Value(_) => None,
+            LevelValue::Stat(StatLevel::Stat { stat }) => Some(stat),
+        }
+    }
+}
+
+/// Template parameter value - a number, or a string/bool for data-driven selection
+/// (e.g. an element name used in `"@{{element}}Bonus"`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ParamValue {
+    /// Numeric value
+    Number(f64),
+    /// Boolean value (`1.0`/`0.0` where a number is needed)
+    Bool(bool),
+    /// Text substituted into `{{param}}` placeholders
+    String(String),
+}
+
+impl From<f64> for ParamValue {
+    fn from(value: f64) -> Self {
+        ParamValue::Number(value)
+    }
+}
+
+impl From<bool> for ParamValue {
+    fn from(value: bool) -> Self {
+        ParamValue::Bool(value)
+    }
+}
+
+impl From<&str> for ParamValue {
+    fn from(value: &str) -> Self {
+        ParamValue::String(value.to_string())
+    }
+}
+
+impl From<String> for ParamValue {
+    fn from(value: String) -> Self {
+        ParamValue::String(value)
+    }
+}
+
+impl ParamValue {
+    /// Splits params into numbers (booleans as `1.0`/`0.0`) and strings.
+    pub(crate) fn split(
+        params: &HashMap<String, ParamValue>,
+    ) -> (HashMap<String, f64>, HashMap<String, String>) {
+        let mut numbers = HashMap::new();
+        let mut strings = HashMap::new();
+        for (name, value) in params {
+            match value {
+                ParamValue::Number(n) => {
+                    numbers.insert(name.clone(), *n);
+                }
+                ParamValue::Bool(b) => {
+                    numbers.insert(name.clone(), if *b { 1.0 } else { 0.0 });
+                }
+                ParamValue::String(s) => {
+                    strings.insert(name.clone(), s.clone());
+                }
+            }
+        }
+        (numbers, strings)
+    }
+}
+
+/// Replaces string-param placeholders in every string of a JSON document (not in keys).
+fn substitute_json_strings(value: &mut Value, strings: &HashMap<String, String>) {
+    match value {
+        Value::String(s) if s.contains("{{") => {
+            *s = crate::expression::substitute(s, strings);
+        }
+        Value::Array(items) => {
+            for item in items {
+                substitute_json_strings(item, strings);
+            }
+        }
+        Value::Object(fields) => {
+            for field in fields.values_mut() {
+                substitute_json_strings(field, strings);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Source value - f64 or string (for parameters)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -80,6 +1894,15 @@ pub enum SourceValue {
     String(String),
 }
 
+impl std::fmt::Display for SourceValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SourceValue::Number(n) => write!(f, "{}", n),
+            SourceValue::String(s) => write!(f, "{}", s),
+        }
+    }
+}
+
 impl SourceValue {
     /// Resolves the value to f64, replacing parameters.
     ///
@@ -93,26 +1916,245 @@ impl SourceValue {
     ///
     /// # Errors
     ///
-    /// Returns error string if parameter is not found or string cannot be parsed as f64.
+    /// Returns error string if parameter is not found, the `{{}}` placeholder is empty, or
+    /// string cannot be parsed as f64. A percentage (a plain number followed by one `%`)
+    /// resolves to a fraction, so `"20%"` is `0.2`; a malformed one (`"x%"`, `"20%%"`) is an
+    /// error. Other strings with `{{param}}` placeholders or `+ - * / ( )` are evaluated as
+    /// arithmetic expressions (`"{{base}} + {{per_level}} * {{level}}"`). `@stat` references
+    /// (see `stat_ref`) cannot be resolved from params and are an error here.
     pub fn resolve(&self, params: &HashMap<String, f64>) -> Result<f64, String> {
         match self {
             SourceValue::Number(n) => Ok(*n),
             SourceValue::String(s) => {
+                #[cfg(test)]
+                STRING_RESOLVES.with(|count| count.set(count.get() + 1));
                 // Resolve {{param}} syntax
-                if s.starts_with("{{") && s.ends_with("}}") {
-                    let param_name = s[2..s.len() - 2].trim();
+                if let Some(stat) = self.stat_ref() {
+                    Err(format!(
+                        "Stat reference @{} is only supported in constant sources and clamp bounds",
+                        stat
+                    ))
+                } else if let Some(param_name) = placeholder(s) {
+                    if param_name.is_empty() {
+                        return Err(format!("Empty parameter placeholder: {}", s));
+                    }
                     params
                         .get(param_name)
                         .copied()
                         .ok_or_else(|| format!("Parameter not found: {}", param_name))
+                } else if let Ok(number) = s.parse::<f64>() {
+                    Ok(number)
+                } else if let Some(percent) = percentage(s) {
+                    Ok(percent / 100.0)
+                } else if s.trim().ends_with('%') {
+                    Err(format!("Invalid percentage: {}", s))
+                } else if s.contains("{{") || s.contains(['+', '-', '*', '/', '(', ')']) {
+                    crate::expression::evaluate(s, params)
                 } else {
-                    // Parse string as f64
-                    s.parse::<f64>()
-                        .map_err(|_| format!("Invalid number: {}", s))
+                    Err(format!("Invalid number: {}", s))
                 }
             }
         }
     }
+
+    /// Turns a purely numeric, finite string into a `Number`; anything else is unchanged.
+    pub(crate) fn normalize(&mut self) {
+        if let SourceValue::String(s) = self
+            && let Ok(number) = s.parse::<f64>()
+            && number.is_finite()
+        {
+            *self = SourceValue::Number(number);
+        }
+    }
+
+    /// Returns the parameter names referenced by this value.
+    pub(crate) fn param_names(&self) -> Vec<&str> {
+        match self {
+            SourceValue::String(s) => crate::expression::param_names(s),
+            SourceValue::Number(_) => Vec::new(),
+        }
+    }
+
+    /// Resolves the value with typed params: string params are substituted as text first
+    /// (so `"@{{element}}Bonus"` can select a stat), then numbers and booleans resolve as in
+    /// `resolve`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as `resolve`, after substitution.
+    pub fn resolve_with_values(&self, params: &HashMap<String, ParamValue>) -> Result<f64, String> {
+        let (numbers, strings) = ParamValue::split(params);
+        match self {
+            SourceValue::String(s) if !strings.is_empty() => {
+                SourceValue::String(crate::expression::substitute(s, &strings)).resolve(&numbers)
+            }
+            _ => self.resolve(&numbers),
+        }
+    }
+
+    /// Returns the stat name of an `@stat` reference (e.g. `"@Strength"`), or `None`.
+    ///
+    /// Stat references become dependencies, so the value follows the referenced stat and is
+    /// entity-prefixed like any other dependency (`@Strength` reads `hero:Strength`).
+    pub fn stat_ref(&self) -> Option<&str> {
+        match self {
+            SourceValue::String(s) => s
+                .trim()
+                .strip_prefix('@')
+                .map(str::trim)
+                .filter(|stat| !stat.is_empty()),
+            SourceValue::Number(_) => None,
+        }
+    }
+
+    /// Mutable access to the whole string (`@` included) of a stat reference.
+    pub(crate) fn stat_ref_mut(&mut self) -> Option<&mut String> {
+        match self {
+            SourceValue::String(s) if s.trim().starts_with('@') => Some(s),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Number of string values `SourceValue::resolve` has parsed on this thread.
+    pub(crate) static STRING_RESOLVES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Returns the trimmed name inside a `{{param}}` placeholder, or `None` if `s` is not one.
+fn placeholder(s: &str) -> Option<&str> {
+    s.strip_prefix("{{")
+        .and_then(|rest| rest.strip_suffix("}}"))
+        .filter(|inner| !inner.contains("{{") && !inner.contains("}}"))
+        .map(str::trim)
+}
+
+/// Returns the number in a percentage literal (`"20%"` is `20.0`), or `None` if `s` is not
+/// a plain number followed by a single `%`.
+fn percentage(s: &str) -> Option<f64> {
+    s.trim()
+        .strip_suffix('%')
+        .and_then(|number| number.trim().parse().ok())
+}
+
+/// `deserialize_with` helpers for stat name fields.
+///
+/// They reject empty or blank names, so a typo such as `""` in a dependency list fails while
+/// parsing instead of resolving against a nonexistent stat. The error names the field.
+mod stat_name {
+    use super::SourceValue;
+    use serde::{Deserialize, Deserializer, de::Error};
+
+    fn check<E: Error>(field: &str, name: String) -> Result<String, E> {
+        if name.trim().is_empty() {
+            return Err(E::custom(format!(
+                "stat name must not be empty (check `{}`)",
+                field
+            )));
+        }
+        Ok(name)
+    }
+
+    fn one<'de, D: Deserializer<'de>>(field: &str, deserializer: D) -> Result<String, D::Error> {
+        check(field, String::deserialize(deserializer)?)
+    }
+
+    fn optional<'de, D: Deserializer<'de>>(
+        field: &str,
+        deserializer: D,
+    ) -> Result<Option<String>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|name| check(field, name))
+            .transpose()
+    }
+
+    fn list<'de, D: Deserializer<'de>>(
+        field: &str,
+        deserializer: D,
+    ) -> Result<Vec<String>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|name| check(field, name))
+            .collect()
+    }
+
+    pub(super) fn stat<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        one("stat", deserializer)
+    }
+
+    pub(super) fn of<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        one("of", deserializer)
+    }
+
+    pub(super) fn condition_stat<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<String, D::Error> {
+        one("condition_stat", deserializer)
+    }
+
+    pub(super) fn optional_condition_stat<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<String>, D::Error> {
+        optional("condition_stat", deserializer)
+    }
+
+    pub(super) fn then_stat<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<String, D::Error> {
+        one("then_stat", deserializer)
+    }
+
+    pub(super) fn else_stat<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<String, D::Error> {
+        one("else_stat", deserializer)
+    }
+
+    pub(super) fn numerator_stat<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<String, D::Error> {
+        one("numerator_stat", deserializer)
+    }
+
+    pub(super) fn denominator_stat<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<String, D::Error> {
+        one("denominator_stat", deserializer)
+    }
+
+    pub(super) fn min_stat<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<String>, D::Error> {
+        optional("min_stat", deserializer)
+    }
+
+    pub(super) fn max_stat<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<String>, D::Error> {
+        optional("max_stat", deserializer)
+    }
+
+    pub(super) fn stats<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<String>, D::Error> {
+        list("stats", deserializer)
+    }
+
+    pub(super) fn dependencies<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<String>, D::Error> {
+        list("dependencies", deserializer)
+    }
+
+    pub(super) fn terms<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(String, SourceValue)>, D::Error> {
+        Vec::<(String, SourceValue)>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(name, weight)| Ok((check("terms", name)?, weight)))
+            .collect()
+    }
 }
 
 /// Transform configuration
@@ -122,7 +2164,8 @@ pub enum TransformConfig {
     /// Multiplicative transformation
     #[serde(rename = "multiplicative")]
     Multiplicative {
-        /// Multiplier value
+        /// Multiplier value (a factor: `1.2` is +20%, while `"20%"` is `×0.2`; use
+        /// `percent_increase` for +20%)
         value: SourceValue,
         /// Description (optional, for readability)
         #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -139,6 +2182,69 @@ pub enum TransformConfig {
         name: Option<String>,
     },
 
+    /// Subtractive transformation
+    #[serde(rename = "subtractive")]
+    Subtractive {
+        /// Value to subtract
+        value: SourceValue,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Divide transformation - divides by a non-zero value
+    #[serde(rename = "divide")]
+    Divide {
+        /// Divisor (must not be zero)
+        value: SourceValue,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Power transformation - raises the value to `exponent`
+    ///
+    /// A negative value with a fractional exponent fails resolution of every stat in the
+    /// resolver, not only this one.
+    #[serde(rename = "power")]
+    Power {
+        /// Exponent (f64 or "{{param}}" string)
+        exponent: SourceValue,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Percent-add transformation - adds `percent`% of the current value
+    #[serde(rename = "percent_add")]
+    PercentAdd {
+        /// Percentage points to add (f64 or "{{param}}" string; 15 = +15%)
+        percent: SourceValue,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Min transformation - raises the value to at least `value` (a one-sided clamp)
+    #[serde(rename = "min")]
+    Min {
+        /// Lower bound (f64 or "{{param}}" string)
+        value: SourceValue,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Max transformation - lowers the value to at most `value` (a one-sided clamp)
+    #[serde(rename = "max")]
+    Max {
+        /// Upper bound (f64 or "{{param}}" string)
+        value: SourceValue,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
     /// Clamp transformation
     #[serde(rename = "clamp")]
     Clamp {
@@ -146,6 +2252,20 @@ pub enum TransformConfig {
         min: Option<SourceValue>,
         /// Maximum value
         max: Option<SourceValue>,
+        /// Stat whose value is also a minimum (the larger of the two mins applies)
+        #[serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            deserialize_with = "stat_name::min_stat"
+        )]
+        min_stat: Option<String>,
+        /// Stat whose value is also a maximum (the smaller of the two maxes applies)
+        #[serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            deserialize_with = "stat_name::max_stat"
+        )]
+        max_stat: Option<String>,
         /// Description (optional, for readability)
         #[serde(default, skip_serializing_if = "Option::is_none")]
         name: Option<String>,
@@ -154,11 +2274,27 @@ pub enum TransformConfig {
     /// Conditional transformation
     #[serde(rename = "conditional")]
     Conditional {
-        /// Condition stat name
-        condition_stat: String,
-        /// Condition value
-        condition_value: f64,
-        /// Condition operator (>, <, >=, <=, ==)
+        /// Condition stat name (exactly one of `condition_stat` and `condition_context`)
+        #[serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            deserialize_with = "stat_name::optional_condition_stat"
+        )]
+        condition_stat: Option<String>,
+        /// `StatContext` key to read instead of a stat (booleans read as 1/0, missing as 0)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        condition_context: Option<String>,
+        /// Sub-conditions that must all hold (instead of a single condition)
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        all: Vec<SubCondition>,
+        /// Sub-conditions of which at least one must hold (instead of a single condition)
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        any: Vec<SubCondition>,
+        /// Condition value; required by every operator except `is_true`/`is_false`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        condition_value: Option<f64>,
+        /// Condition operator (>, <, >=, <=, ==, !=, is_true, is_false; unused with `all`/`any`)
+        #[serde(default, skip_serializing_if = "String::is_empty")]
         operator: String,
         /// Transform to apply when condition is met
         then: Box<TransformConfig>,
@@ -166,10 +2302,296 @@ pub enum TransformConfig {
         else_then: Option<Box<TransformConfig>>,
     },
 
+    /// Range transformation - applies `then` when `min <= condition_stat <= max`
+    #[serde(rename = "range")]
+    Range {
+        /// Condition stat name
+        #[serde(deserialize_with = "stat_name::condition_stat")]
+        condition_stat: String,
+        /// Lower bound of the band
+        min: f64,
+        /// Upper bound of the band
+        max: f64,
+        /// Whether the bounds are part of the band (default true; false means `min < stat < max`)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        inclusive: Option<bool>,
+        /// Transform to apply when the stat is in range
+        then: Box<TransformConfig>,
+        /// Transform to apply when the stat is out of range (optional)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        else_then: Option<Box<TransformConfig>>,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Best-of transformation - applies each option to the incoming value, keeps the max (or min)
+    #[serde(rename = "best_of")]
+    BestOf {
+        /// Alternative transforms, each applied to the same incoming value
+        options: Vec<TransformConfig>,
+        /// Which result to keep: "max" or "min"
+        mode: String,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Conditional stat transformation - adds `then_stat` when the condition holds, else `else_stat`
+    #[serde(rename = "conditional_stat")]
+    ConditionalStat {
+        /// Condition stat name
+        #[serde(deserialize_with = "stat_name::condition_stat")]
+        condition_stat: String,
+        /// Condition value; required by every operator except `is_true`/`is_false`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        condition_value: Option<f64>,
+        /// Condition operator (>, <, >=, <=, ==, !=, is_true, is_false)
+        operator: String,
+        /// Stat added when the condition is met
+        #[serde(deserialize_with = "stat_name::then_stat")]
+        then_stat: String,
+        /// Stat added when the condition is not met
+        #[serde(deserialize_with = "stat_name::else_stat")]
+        else_stat: String,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Remap transformation - maps `[in_min, in_max]` linearly onto `[out_min, out_max]`
+    #[serde(rename = "remap")]
+    Remap {
+        /// Start of the input range (f64 or "{{param}}" string)
+        in_min: SourceValue,
+        /// End of the input range (must differ from `in_min`)
+        in_max: SourceValue,
+        /// Output for `in_min`
+        out_min: SourceValue,
+        /// Output for `in_max`
+        out_max: SourceValue,
+        /// Keep the output inside the output range for inputs outside the input range
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        clamp: bool,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Round transformation - rounds to a number of decimal places
+    #[serde(rename = "round")]
+    Round {
+        /// Decimal places to keep (defaults to 0, i.e. whole numbers)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        decimals: Option<u32>,
+        /// Rounding direction: "nearest" (default), "floor", or "ceil"
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        mode: Option<String>,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Quantize transformation - snaps the value to the nearest multiple of `step`
+    #[serde(rename = "quantize")]
+    Quantize {
+        /// Step size (f64 or "{{param}}" string; must not be zero)
+        step: SourceValue,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Ramp transformation - percentage bonus that grows per stack up to a cap
+    ///
+    /// Computes `value * (1 + min(per_stack * stacks, cap) / 100)`.
+    #[serde(rename = "ramp")]
+    Ramp {
+        /// Percent bonus per stack
+        per_stack: SourceValue,
+        /// Number of stacks (usually a "{{param}}")
+        stacks: SourceValue,
+        /// Maximum total percent bonus
+        cap: SourceValue,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Floor-to-stat transformation - raises the value to at least another stat's value
+    #[serde(rename = "floor_to_stat")]
+    FloorToStat {
+        /// Stat whose value forms the floor
+        #[serde(deserialize_with = "stat_name::stat")]
+        stat: String,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Ceil-to-stat transformation - lowers the value to at most another stat's value
+    #[serde(rename = "ceil_to_stat")]
+    CeilToStat {
+        /// Stat whose value forms the ceiling
+        #[serde(deserialize_with = "stat_name::stat")]
+        stat: String,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Min-of-stats transformation - raises the value to at least the smallest listed stat
+    #[serde(rename = "min_of_stats")]
+    MinOfStats {
+        /// Stats whose minimum forms the floor
+        #[serde(deserialize_with = "stat_name::stats")]
+        stats: Vec<String>,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Max-of-stats transformation - lowers the value to at most the largest listed stat
+    #[serde(rename = "max_of_stats")]
+    MaxOfStats {
+        /// Stats whose maximum forms the ceiling
+        #[serde(deserialize_with = "stat_name::stats")]
+        stats: Vec<String>,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Weighted average transformation - adds `Σ(stat * weight) / Σ(weight)` of dependent stats
+    #[serde(rename = "weighted_average")]
+    WeightedAverage {
+        /// (stat name, weight) pairs; weights can be f64 or "{{param}}" strings
+        #[serde(deserialize_with = "stat_name::terms")]
+        terms: Vec<(String, SourceValue)>,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Timed additive transformation - adds a value only while a context tick is in a window
+    ///
+    /// Applies when `start_tick <= tick < end_tick`, where `tick` is read from the context
+    /// under `tick_key`; a missing tick means the transform does nothing.
+    #[serde(rename = "timed_additive")]
+    TimedAdditive {
+        /// Value to add (f64 or "{{param}}" string)
+        value: SourceValue,
+        /// First tick the value applies on
+        start_tick: f64,
+        /// Tick the value stops applying on (exclusive)
+        end_tick: f64,
+        /// Context key holding the current tick
+        tick_key: String,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Rate limit transformation - keeps the value within `max_delta` of a previous value
+    ///
+    /// The previous value is read from the context under `prev_key`; without it the value
+    /// passes through unchanged.
+    #[serde(rename = "rate_limit")]
+    RateLimit {
+        /// Largest allowed change from the previous value (f64 or "{{param}}" string)
+        max_delta: SourceValue,
+        /// Context key holding the previous resolved value
+        prev_key: String,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Percent increase transformation - multiplies by `1 + value`
+    ///
+    /// `value` is a fraction, so `"20%"` (or `0.2`) means +20%.
+    #[serde(rename = "percent_increase")]
+    PercentIncrease {
+        /// Increase as a fraction (f64, "20%" or "{{param}}" string)
+        value: SourceValue,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Table lookup transformation - multiplies by the table entry selected by a context key
+    ///
+    /// The key is read as a string from the context under `key_param`. A missing or unknown
+    /// key uses `default`, or leaves the value unchanged if there is none.
+    #[serde(rename = "table_lookup")]
+    TableLookup {
+        /// Context key holding the lookup key (e.g. `"element"`)
+        key_param: String,
+        /// Lookup key to multiplier (f64 or "{{param}}" string)
+        table: HashMap<String, SourceValue>,
+        /// Multiplier for missing or unknown keys
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        default: Option<SourceValue>,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Ratio scale transformation - multiplies by `1 + (numerator / denominator) * factor`
+    ///
+    /// A zero denominator fails resolution of every stat in the resolver, not only this one.
+    #[serde(rename = "ratio_scale")]
+    RatioScale {
+        /// Stat in the numerator of the ratio
+        #[serde(deserialize_with = "stat_name::numerator_stat")]
+        numerator_stat: String,
+        /// Stat in the denominator of the ratio (must not resolve to zero)
+        #[serde(deserialize_with = "stat_name::denominator_stat")]
+        denominator_stat: String,
+        /// Scale applied to the ratio (f64 or "{{param}}" string)
+        factor: SourceValue,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Excess-of transformation - adds `max(0, stat - threshold) * factor`
+    #[serde(rename = "excess_of")]
+    ExcessOf {
+        /// Stat whose excess over the threshold contributes
+        #[serde(deserialize_with = "stat_name::stat")]
+        stat: String,
+        /// Breakpoint below which the stat contributes nothing (f64 or "{{param}}" string)
+        threshold: SourceValue,
+        /// Amount added per point above the threshold (f64 or "{{param}}" string)
+        factor: SourceValue,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
+    /// Clamp-to-stat-fraction transformation - clamps to `[min_fraction * stat, max_fraction * stat]`
+    #[serde(rename = "clamp_to_stat_fraction")]
+    ClampToStatFraction {
+        /// Stat the bounds are fractions of
+        #[serde(deserialize_with = "stat_name::stat")]
+        stat: String,
+        /// Lower bound as a fraction of the stat (f64 or "{{param}}" string, optional)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_fraction: Option<SourceValue>,
+        /// Upper bound as a fraction of the stat (f64 or "{{param}}" string, optional)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_fraction: Option<SourceValue>,
+        /// Description (optional, for readability)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+
     /// Map transformation - adds values from dependent stats multiplied by a multiplier
     #[serde(rename = "map")]
     Map {
         /// Dependent stat names
+        #[serde(deserialize_with = "stat_name::dependencies")]
         dependencies: Vec<String>,
         /// Multiplier to apply to the sum of dependent stat values
         /// Can be f64 or "{{param}}" string
@@ -179,3 +2601,534 @@ pub enum TransformConfig {
         name: Option<String>,
     },
 }
+
+/// One comparison in the `all`/`any` list of a `conditional` transform
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubCondition {
+    /// Condition stat name
+    #[serde(deserialize_with = "stat_name::condition_stat")]
+    pub condition_stat: String,
+    /// Condition value; required by every operator except `is_true`/`is_false`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition_value: Option<f64>,
+    /// Condition operator (>, <, >=, <=, ==, !=, is_true, is_false)
+    pub operator: String,
+}
+
+/// Read-only view of a `conditional` transform (see `TransformConfig::as_conditional`)
+#[derive(Debug, Clone, Copy)]
+pub struct ConditionalView<'a> {
+    /// Condition stat name
+    pub condition_stat: Option<&'a str>,
+    /// Context key read instead of a stat
+    pub condition_context: Option<&'a str>,
+    /// Sub-conditions that must all hold
+    pub all: &'a [SubCondition],
+    /// Sub-conditions of which at least one must hold
+    pub any: &'a [SubCondition],
+    /// Condition operator (>, <, >=, <=, ==, !=, is_true, is_false)
+    pub operator: &'a str,
+    /// Condition value (`None` when omitted for a flag operator)
+    pub condition_value: Option<f64>,
+    /// Transform applied when the condition is met
+    pub then: &'a TransformConfig,
+    /// Transform applied when the condition is not met
+    pub else_then: Option<&'a TransformConfig>,
+}
+
+impl ConditionalView<'_> {
+    /// Returns the `type` tag of the `then` branch.
+    pub fn then_kind(&self) -> &'static str {
+        self.then.kind_label()
+    }
+
+    /// Returns the `type` tag of the `else_then` branch, if there is one.
+    pub fn else_kind(&self) -> Option<&'static str> {
+        self.else_then.map(TransformConfig::kind_label)
+    }
+}
+
+impl TransformConfig {
+    /// Returns a read-only view of a `conditional` transform, or `None` for other variants.
+    pub fn as_conditional(&self) -> Option<ConditionalView<'_>> {
+        match self {
+            TransformConfig::Conditional {
+                condition_stat,
+                condition_context,
+                all,
+                any,
+                condition_value,
+                operator,
+                then,
+                else_then,
+            } => Some(ConditionalView {
+                condition_stat: condition_stat.as_deref(),
+                condition_context: condition_context.as_deref(),
+                all,
+                any,
+                operator,
+                condition_value: *condition_value,
+                then,
+                else_then: else_then.as_deref(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the variant's `type` tag as used in JSON (e.g. `"multiplicative"`).
+    pub fn kind_label(&self) -> &'static str {
+        match self {
+            TransformConfig::Multiplicative { .. } => "multiplicative",
+            TransformConfig::Additive { .. } => "additive",
+            TransformConfig::Subtractive { .. } => "subtractive",
+            TransformConfig::Divide { .. } => "divide",
+            TransformConfig::Power { .. } => "power",
+            TransformConfig::PercentAdd { .. } => "percent_add",
+            TransformConfig::Min { .. } => "min",
+            TransformConfig::Max { .. } => "max",
+            TransformConfig::Clamp { .. } => "clamp",
+            TransformConfig::Round { .. } => "round",
+            TransformConfig::Quantize { .. } => "quantize",
+            TransformConfig::Remap { .. } => "remap",
+            TransformConfig::ExcessOf { .. } => "excess_of",
+            TransformConfig::ClampToStatFraction { .. } => "clamp_to_stat_fraction",
+            TransformConfig::Ramp { .. } => "ramp",
+            TransformConfig::FloorToStat { .. } => "floor_to_stat",
+            TransformConfig::CeilToStat { .. } => "ceil_to_stat",
+            TransformConfig::MinOfStats { .. } => "min_of_stats",
+            TransformConfig::MaxOfStats { .. } => "max_of_stats",
+            TransformConfig::Conditional { .. } => "conditional",
+            TransformConfig::Range { .. } => "range",
+            TransformConfig::BestOf { .. } => "best_of",
+            TransformConfig::ConditionalStat { .. } => "conditional_stat",
+            TransformConfig::WeightedAverage { .. } => "weighted_average",
+            TransformConfig::TimedAdditive { .. } => "timed_additive",
+            TransformConfig::RateLimit { .. } => "rate_limit",
+            TransformConfig::PercentIncrease { .. } => "percent_increase",
+            TransformConfig::TableLookup { .. } => "table_lookup",
+            TransformConfig::RatioScale { .. } => "ratio_scale",
+            TransformConfig::Map { .. } => "map",
+        }
+    }
+
+    /// Merges multiplicative transforms into a single multiplier of `1 + Σ(value - 1)`.
+    ///
+    /// The merged transform takes the place of the first multiplicative; all other
+    /// transforms keep their order. Used when `multiplicative_as_additive` is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `transforms` - A stat's transforms, in application order
+    /// * `params` - Parameter values for `{{param}}` multipliers
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if a multiplier fails to resolve.
+    pub(crate) fn combine_multiplicatives<'a>(
+        transforms: impl IntoIterator<Item = &'a TransformConfig>,
+        params: &HashMap<String, f64>,
+    ) -> Result<Vec<Cow<'a, TransformConfig>>, YamlStatError> {
+        let mut combined = Vec::new();
+        let mut first = None;
+        let mut count = 0;
+        let mut percent = 0.0;
+
+        for transform in transforms {
+            if let TransformConfig::Multiplicative { value, .. } = transform {
+                percent += value.resolve(params).map_err(|e| {
+                    YamlStatError::InvalidConfig(format!("Transform resolution error: {}", e))
+                })? - 1.0;
+                count += 1;
+                if first.is_some() {
+                    continue;
+                }
+                first = Some(combined.len());
+            }
+            combined.push(Cow::Borrowed(transform));
+        }
+
+        if let Some(index) = first.filter(|_| count > 1) {
+            combined[index] = Cow::Owned(TransformConfig::Multiplicative {
+                value: SourceValue::Number(1.0 + percent),
+                name: Some(format!("{} multipliers combined", count)),
+            });
+        }
+        Ok(combined)
+    }
+
+    /// Returns the optional `name` label (conditionals have none).
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            TransformConfig::Multiplicative { name, .. }
+            | TransformConfig::Additive { name, .. }
+            | TransformConfig::Subtractive { name, .. }
+            | TransformConfig::Divide { name, .. }
+            | TransformConfig::Power { name, .. }
+            | TransformConfig::PercentAdd { name, .. }
+            | TransformConfig::Min { name, .. }
+            | TransformConfig::Max { name, .. }
+            | TransformConfig::Clamp { name, .. }
+            | TransformConfig::Round { name, .. }
+            | TransformConfig::Quantize { name, .. }
+            | TransformConfig::Remap { name, .. }
+            | TransformConfig::ExcessOf { name, .. }
+            | TransformConfig::ClampToStatFraction { name, .. }
+            | TransformConfig::Ramp { name, .. }
+            | TransformConfig::FloorToStat { name, .. }
+            | TransformConfig::CeilToStat { name, .. }
+            | TransformConfig::MinOfStats { name, .. }
+            | TransformConfig::MaxOfStats { name, .. }
+            | TransformConfig::WeightedAverage { name, .. }
+            | TransformConfig::TimedAdditive { name, .. }
+            | TransformConfig::RateLimit { name, .. }
+            | TransformConfig::PercentIncrease { name, .. }
+            | TransformConfig::TableLookup { name, .. }
+            | TransformConfig::RatioScale { name, .. }
+            | TransformConfig::ConditionalStat { name, .. }
+            | TransformConfig::Range { name, .. }
+            | TransformConfig::BestOf { name, .. }
+            | TransformConfig::Map { name, .. } => name.as_deref(),
+            TransformConfig::Conditional { .. } => None,
+        }
+    }
+
+    /// Returns every value in the transform, including nested transforms (for parameter scanning).
+    pub(crate) fn values(&self) -> Vec<&SourceValue> {
+        match self {
+            TransformConfig::Multiplicative { value, .. }
+            | TransformConfig::Additive { value, .. }
+            | TransformConfig::Subtractive { value, .. }
+            | TransformConfig::Divide { value, .. }
+            | TransformConfig::PercentAdd { percent: value, .. }
+            | TransformConfig::Quantize { step: value, .. }
+            | TransformConfig::Min { value, .. }
+            | TransformConfig::Max { value, .. }
+            | TransformConfig::Power {
+                exponent: value, ..
+            }
+            | TransformConfig::TimedAdditive { value, .. }
+            | TransformConfig::PercentIncrease { value, .. } => vec![value],
+            TransformConfig::RateLimit { max_delta, .. } => vec![max_delta],
+            TransformConfig::RatioScale { factor, .. } => vec![factor],
+            TransformConfig::ExcessOf {
+                threshold, factor, ..
+            } => vec![threshold, factor],
+            TransformConfig::TableLookup { table, default, .. } => {
+                table.values().chain(default).collect()
+            }
+            TransformConfig::Clamp { min, max, .. } => min.iter().chain(max).collect(),
+            TransformConfig::ClampToStatFraction {
+                min_fraction,
+                max_fraction,
+                ..
+            } => min_fraction.iter().chain(max_fraction).collect(),
+            TransformConfig::Round { .. }
+            | TransformConfig::FloorToStat { .. }
+            | TransformConfig::CeilToStat { .. }
+            | TransformConfig::MinOfStats { .. }
+            | TransformConfig::MaxOfStats { .. }
+            | TransformConfig::ConditionalStat { .. } => Vec::new(),
+            TransformConfig::Ramp {
+                per_stack,
+                stacks,
+                cap,
+                ..
+            } => vec![per_stack, stacks, cap],
+            TransformConfig::Remap {
+                in_min,
+                in_max,
+                out_min,
+                out_max,
+                ..
+            } => vec![in_min, in_max, out_min, out_max],
+            TransformConfig::Conditional {
+                then, else_then, ..
+            }
+            | TransformConfig::Range {
+                then, else_then, ..
+            } => {
+                let mut values = then.values();
+                if let Some(else_then) = else_then {
+                    values.extend(else_then.values());
+                }
+                values
+            }
+            TransformConfig::BestOf { options, .. } => {
+                options.iter().flat_map(TransformConfig::values).collect()
+            }
+            TransformConfig::WeightedAverage { terms, .. } => {
+                terms.iter().map(|(_, weight)| weight).collect()
+            }
+            TransformConfig::Map { multiplier, .. } => multiplier.iter().collect(),
+        }
+    }
+
+    /// Mutable counterpart of `values`, including nested transforms.
+    pub(crate) fn values_mut(&mut self) -> Vec<&mut SourceValue> {
+        match self {
+            TransformConfig::Multiplicative { value, .. }
+            | TransformConfig::Additive { value, .. }
+            | TransformConfig::Subtractive { value, .. }
+            | TransformConfig::Divide { value, .. }
+            | TransformConfig::PercentAdd { percent: value, .. }
+            | TransformConfig::Quantize { step: value, .. }
+            | TransformConfig::Min { value, .. }
+            | TransformConfig::Max { value, .. }
+            | TransformConfig::Power {
+                exponent: value, ..
+            }
+            | TransformConfig::TimedAdditive { value, .. }
+            | TransformConfig::PercentIncrease { value, .. } => vec![value],
+            TransformConfig::RateLimit { max_delta, .. } => vec![max_delta],
+            TransformConfig::RatioScale { factor, .. } => vec![factor],
+            TransformConfig::ExcessOf {
+                threshold, factor, ..
+            } => vec![threshold, factor],
+            TransformConfig::TableLookup { table, default, .. } => {
+                table.values_mut().chain(default).collect()
+            }
+            TransformConfig::Clamp { min, max, .. } => min.iter_mut().chain(max).collect(),
+            TransformConfig::ClampToStatFraction {
+                min_fraction,
+                max_fraction,
+                ..
+            } => min_fraction.iter_mut().chain(max_fraction).collect(),
+            TransformConfig::Round { .. }
+            | TransformConfig::FloorToStat { .. }
+            | TransformConfig::CeilToStat { .. }
+            | TransformConfig::MinOfStats { .. }
+            | TransformConfig::MaxOfStats { .. }
+            | TransformConfig::ConditionalStat { .. } => Vec::new(),
+            TransformConfig::Ramp {
+                per_stack,
+                stacks,
+                cap,
+                ..
+            } => vec![per_stack, stacks, cap],
+            TransformConfig::Remap {
+                in_min,
+                in_max,
+                out_min,
+                out_max,
+                ..
+            } => vec![in_min, in_max, out_min, out_max],
+            TransformConfig::Conditional {
+                then, else_then, ..
+            }
+            | TransformConfig::Range {
+                then, else_then, ..
+            } => {
+                let mut values = then.values_mut();
+                if let Some(else_then) = else_then {
+                    values.extend(else_then.values_mut());
+                }
+                values
+            }
+            TransformConfig::BestOf { options, .. } => options
+                .iter_mut()
+                .flat_map(TransformConfig::values_mut)
+                .collect(),
+            TransformConfig::WeightedAverage { terms, .. } => {
+                terms.iter_mut().map(|(_, weight)| weight).collect()
+            }
+            TransformConfig::Map { multiplier, .. } => multiplier.iter_mut().collect(),
+        }
+    }
+
+    /// Returns the names of every stat the transform depends on, including nested transforms.
+    pub(crate) fn stat_refs(&self) -> Vec<&str> {
+        match self {
+            TransformConfig::Multiplicative { .. }
+            | TransformConfig::Additive { .. }
+            | TransformConfig::Subtractive { .. }
+            | TransformConfig::Divide { .. }
+            | TransformConfig::Power { .. }
+            | TransformConfig::PercentAdd { .. }
+            | TransformConfig::Min { .. }
+            | TransformConfig::Max { .. }
+            | TransformConfig::Round { .. }
+            | TransformConfig::Quantize { .. }
+            | TransformConfig::Ramp { .. }
+            | TransformConfig::TimedAdditive { .. }
+            | TransformConfig::RateLimit { .. }
+            | TransformConfig::PercentIncrease { .. }
+            | TransformConfig::TableLookup { .. }
+            | TransformConfig::Remap { .. } => Vec::new(),
+            TransformConfig::Clamp {
+                min,
+                max,
+                min_stat,
+                max_stat,
+                ..
+            } => min_stat
+                .iter()
+                .chain(max_stat)
+                .map(String::as_str)
+                .chain(min.iter().chain(max).filter_map(SourceValue::stat_ref))
+                .collect(),
+            TransformConfig::FloorToStat { stat, .. }
+            | TransformConfig::CeilToStat { stat, .. }
+            | TransformConfig::ExcessOf { stat, .. }
+            | TransformConfig::ClampToStatFraction { stat, .. } => {
+                vec![stat.as_str()]
+            }
+            TransformConfig::Conditional {
+                condition_stat,
+                all,
+                any,
+                then,
+                else_then,
+                ..
+            } => {
+                let mut refs: Vec<&str> = condition_stat
+                    .iter()
+                    .chain(all.iter().chain(any).map(|c| &c.condition_stat))
+                    .map(String::as_str)
+                    .collect();
+                refs.extend(then.stat_refs());
+                if let Some(else_then) = else_then {
+                    refs.extend(else_then.stat_refs());
+                }
+                refs
+            }
+            TransformConfig::Range {
+                condition_stat,
+                then,
+                else_then,
+                ..
+            } => {
+                let mut refs = vec![condition_stat.as_str()];
+                refs.extend(then.stat_refs());
+                if let Some(else_then) = else_then {
+                    refs.extend(else_then.stat_refs());
+                }
+                refs
+            }
+            TransformConfig::BestOf { options, .. } => options
+                .iter()
+                .flat_map(TransformConfig::stat_refs)
+                .collect(),
+            TransformConfig::WeightedAverage { terms, .. } => {
+                terms.iter().map(|(stat, _)| stat.as_str()).collect()
+            }
+            TransformConfig::RatioScale {
+                numerator_stat,
+                denominator_stat,
+                ..
+            } => vec![numerator_stat.as_str(), denominator_stat.as_str()],
+            TransformConfig::ConditionalStat {
+                condition_stat,
+                then_stat,
+                else_stat,
+                ..
+            } => vec![
+                condition_stat.as_str(),
+                then_stat.as_str(),
+                else_stat.as_str(),
+            ],
+            TransformConfig::MinOfStats { stats, .. }
+            | TransformConfig::MaxOfStats { stats, .. } => {
+                stats.iter().map(String::as_str).collect()
+            }
+            TransformConfig::Map { dependencies, .. } => {
+                dependencies.iter().map(String::as_str).collect()
+            }
+        }
+    }
+
+    /// Mutable counterpart of `stat_refs`, for rewriting stat names (e.g. aliases).
+    pub(crate) fn stat_refs_mut(&mut self) -> Vec<&mut String> {
+        match self {
+            TransformConfig::Multiplicative { .. }
+            | TransformConfig::Additive { .. }
+            | TransformConfig::Subtractive { .. }
+            | TransformConfig::Divide { .. }
+            | TransformConfig::Power { .. }
+            | TransformConfig::PercentAdd { .. }
+            | TransformConfig::Min { .. }
+            | TransformConfig::Max { .. }
+            | TransformConfig::Round { .. }
+            | TransformConfig::Quantize { .. }
+            | TransformConfig::Ramp { .. }
+            | TransformConfig::TimedAdditive { .. }
+            | TransformConfig::RateLimit { .. }
+            | TransformConfig::PercentIncrease { .. }
+            | TransformConfig::TableLookup { .. }
+            | TransformConfig::Remap { .. } => Vec::new(),
+            TransformConfig::Clamp {
+                min,
+                max,
+                min_stat,
+                max_stat,
+                ..
+            } => min_stat
+                .iter_mut()
+                .chain(max_stat)
+                .chain(
+                    min.iter_mut()
+                        .chain(max)
+                        .filter_map(SourceValue::stat_ref_mut),
+                )
+                .collect(),
+            TransformConfig::FloorToStat { stat, .. }
+            | TransformConfig::CeilToStat { stat, .. }
+            | TransformConfig::ExcessOf { stat, .. }
+            | TransformConfig::ClampToStatFraction { stat, .. } => vec![stat],
+            TransformConfig::Conditional {
+                condition_stat,
+                all,
+                any,
+                then,
+                else_then,
+                ..
+            } => {
+                let mut refs: Vec<&mut String> = condition_stat
+                    .iter_mut()
+                    .chain(all.iter_mut().chain(any).map(|c| &mut c.condition_stat))
+                    .collect();
+                refs.extend(then.stat_refs_mut());
+                if let Some(else_then) = else_then {
+                    refs.extend(else_then.stat_refs_mut());
+                }
+                refs
+            }
+            TransformConfig::Range {
+                condition_stat,
+                then,
+                else_then,
+                ..
+            } => {
+                let mut refs = vec![condition_stat];
+                refs.extend(then.stat_refs_mut());
+                if let Some(else_then) = else_then {
+                    refs.extend(else_then.stat_refs_mut());
+                }
+                refs
+            }
+            TransformConfig::BestOf { options, .. } => options
+                .iter_mut()
+                .flat_map(TransformConfig::stat_refs_mut)
+                .collect(),
+            TransformConfig::WeightedAverage { terms, .. } => {
+                terms.iter_mut().map(|(stat, _)| stat).collect()
+            }
+            TransformConfig::RatioScale {
+                numerator_stat,
+                denominator_stat,
+                ..
+            } => vec![numerator_stat, denominator_stat],
+            TransformConfig::ConditionalStat {
+                condition_stat,
+                then_stat,
+                else_stat,
+                ..
+            } => vec![condition_stat, then_stat, else_stat],
+            TransformConfig::MinOfStats { stats, .. }
+            | TransformConfig::MaxOfStats { stats, .. }
+            | TransformConfig::Map {
+                dependencies: stats,
+                ..
+            } => stats.iter_mut().collect(),
+        }
+    }
+}
@@ -22,4 +22,27 @@ pub enum YamlStatError {
     /// Invalid transform type
     #[error("Invalid transform type: {0}")]
     InvalidTransformType(String),
+
+    /// I/O error (e.g. reading a config file)
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+impl YamlStatError {
+    /// Wraps an I/O error with the path that was being read.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the file being read
+    /// * `err` - Underlying I/O error
+    ///
+    /// # Returns
+    ///
+    /// An `IoError` whose message reads `reading <path>: <err>`.
+    pub fn io_with_path(path: &std::path::Path, err: std::io::Error) -> Self {
+        YamlStatError::IoError(std::io::Error::new(
+            err.kind(),
+            format!("reading {}: {}", path.display(), err),
+        ))
+    }
 }
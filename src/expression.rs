@@ -0,0 +1,213 @@
+//! Arithmetic expressions in `SourceValue` strings (e.g. `"{{base}} + {{per_level}} * {{level}}"`).
+//!
+//! Supports numbers, `{{param}}` placeholders, `+ - * /` with the usual precedence, unary
+//! minus, and parentheses. Parentheses and unary minus may nest at most
+//! [`MAX_DEPTH`] levels deep.
+
+use std::collections::HashMap;
+
+/// Maximum nesting of parentheses and unary minus in one expression.
+pub(crate) const MAX_DEPTH: usize = 64;
+
+/// Evaluates an expression against parameter values.
+///
+/// # Errors
+///
+/// Returns an error string for unknown params, malformed expressions, division by zero,
+/// or nesting deeper than [`MAX_DEPTH`].
+pub(crate) fn evaluate(expression: &str, params: &HashMap<String, f64>) -> Result<f64, String> {
+    let tokens = tokenize(expression)?;
+    let mut parser = Parser {
+        tokens: &tokens,
+        pos: 0,
+        depth: 0,
+        params,
+        expression,
+    };
+    let value = parser.sum()?;
+    if parser.pos != tokens.len() {
+        return Err(format!("Invalid expression: {}", expression));
+    }
+    Ok(value)
+}
+
+/// Returns the trimmed names of every `{{param}}` placeholder in `s`, in order.
+pub(crate) fn param_names(s: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = after[..end].trim();
+        if !name.is_empty() {
+            names.push(name);
+        }
+        rest = &after[end + 2..];
+    }
+    names
+}
+
+/// Replaces each `{{name}}` placeholder that has an entry in `values` with that text,
+/// leaving other placeholders in place.
+pub(crate) fn substitute(s: &str, values: &HashMap<String, String>) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        match values.get(after[..end].trim()) {
+            Some(value) => out.push_str(value),
+            None => out.push_str(&rest[start..start + end + 4]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+enum Token<'a> {
+    Number(f64),
+    Param(&'a str),
+    /// An operator or parenthesis
+    Symbol(char),
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token<'_>>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = expression.trim_start();
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix("{{") {
+            let end = after
+                .find("}}")
+                .ok_or_else(|| format!("Unterminated parameter placeholder: {}", expression))?;
+            let name = after[..end].trim();
+            if name.is_empty() {
+                return Err(format!("Empty parameter placeholder: {}", expression));
+            }
+            tokens.push(Token::Param(name));
+            rest = &after[end + 2..];
+        } else if "+-*/()".contains(c) {
+            tokens.push(Token::Symbol(c));
+            rest = &rest[1..];
+        } else if c.is_ascii_digit() || c == '.' {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .unwrap_or(rest.len());
+            let number = rest[..end]
+                .parse()
+                .map_err(|_| format!("Invalid number in expression: {}", &rest[..end]))?;
+            tokens.push(Token::Number(number));
+            rest = &rest[end..];
+        } else {
+            return Err(format!("Unexpected '{}' in expression: {}", c, expression));
+        }
+        rest = rest.trim_start();
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive-descent parser: sum := product (('+' | '-') product)*,
+/// product := unary (('*' | '/') unary)*, unary := '-' unary | atom.
+struct Parser<'a> {
+    tokens: &'a [Token<'a>],
+    pos: usize,
+    /// Current nesting of parentheses and unary minus
+    depth: usize,
+    params: &'a HashMap<String, f64>,
+    expression: &'a str,
+}
+
+impl Parser<'_> {
+    fn sum(&mut self) -> Result<f64, String> {
+        let mut value = self.product()?;
+        while let Some(op @ ('+' | '-')) = self.peek_symbol() {
+            self.pos += 1;
+            let rhs = self.product()?;
+            if op == '+' {
+                value += rhs;
+            } else {
+                value -= rhs;
+            }
+        }
+        Ok(value)
+    }
+
+    fn product(&mut self) -> Result<f64, String> {
+        let mut value = self.unary()?;
+        while let Some(op @ ('*' | '/')) = self.peek_symbol() {
+            self.pos += 1;
+            let rhs = self.unary()?;
+            if op == '*' {
+                value *= rhs;
+            } else if rhs == 0.0 {
+                return Err(format!(
+                    "Division by zero in expression: {}",
+                    self.expression
+                ));
+            } else {
+                value /= rhs;
+            }
+        }
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> Result<f64, String> {
+        if self.peek_symbol() == Some('-') {
+            self.pos += 1;
+            self.enter()?;
+            let value = self.unary()?;
+            self.depth -= 1;
+            return Ok(-value);
+        }
+        self.atom()
+    }
+
+    fn atom(&mut self) -> Result<f64, String> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        match token {
+            Some(Token::Number(n)) => Ok(*n),
+            Some(Token::Param(name)) => self
+                .params
+                .get(*name)
+                .copied()
+                .ok_or_else(|| format!("Parameter not found: {}", name)),
+            Some(Token::Symbol('(')) => {
+                self.enter()?;
+                let value = self.sum()?;
+                self.depth -= 1;
+                if self.peek_symbol() != Some(')') {
+                    return Err(format!("Missing ')' in expression: {}", self.expression));
+                }
+                self.pos += 1;
+                Ok(value)
+            }
+            _ => Err(format!("Invalid expression: {}", self.expression)),
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(format!(
+                "Expression nested too deeply (max {} levels)",
+                MAX_DEPTH
+            ));
+        }
+        Ok(())
+    }
+
+    fn peek_symbol(&self) -> Option<char> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Symbol(c)) => Some(*c),
+            _ => None,
+        }
+    }
+}
@@ -8,7 +8,7 @@
 //! - JSON format for stat definitions
 //! - **Stat templates** - Parameterizable templates for reusable stat definitions
 //! - Multiple source types (constant, scaling)
-//! - Transform support (multiplicative, additive, clamp, conditional, map)
+//! - Transform support (multiplicative, additive, subtractive, divide, power, percent add, min, max, clamp, conditional, range, map, weighted average, round, quantize, ramp, floor/ceil to stat, min/max of stats, timed additive, rate limit, percent increase, table lookup, ratio scale, conditional stat, remap, excess of, clamp to stat fraction, best of)
 //! - Dependency resolution (stats can depend on other stats)
 //! - **Entity-based stat assignment** - Assign stats to entities using templates
 //! - **Entity-based stat management** - JSON serialization, entity-based stat management
@@ -48,17 +48,27 @@
 
 pub mod config;
 pub mod error;
+mod expression;
 pub mod loader;
 pub mod template;
 pub mod transform;
 pub mod transform_conditional;
 pub mod transform_map;
 
-pub use config::StatConfig;
+pub use config::{ParamValue, StatConfig};
 pub use error::YamlStatError;
 pub use loader::StatLoader;
-pub use template::{EntityParams, EntityStatConfig, StatTemplateManager};
-pub use transform::AdditiveTransform;
+pub use template::{
+    AuditRecord, BonusKind, EntityParams, EntityStatConfig, EquipBonus, MissingParamPolicy,
+    ParamCheck, StackRule, StatFlags, StatTemplateManager,
+};
+pub use transform::{
+    AdditiveTransform, CeilToStatTransform, DivideTransform, FloorToStatTransform, MaxTransform,
+    MinTransform, MutableAdditiveHandle, MutableAdditiveTransform, NamedTransform,
+    PercentIncreaseTransform, PowerTransform, QuantizeTransform, RampTransform, RateLimitTransform,
+    RemapTransform, RoundMode, RoundTransform, StatClampTransform, StatScalingTransform,
+    SubtractiveTransform, TableLookupTransform, TimedAdditiveTransform,
+};
 
 use zzstat::{StatContext, StatId, StatResolver};
 
@@ -101,6 +111,48 @@ pub fn load_from_json(json_content: &str) -> Result<StatResolver, YamlStatError>
     StatLoader::from_json(json_content)
 }
 
+/// Creates a stat resolver from a JSON file.
+///
+/// # Arguments
+///
+/// * `path` - Path to a JSON file containing stat definitions
+///
+/// # Returns
+///
+/// A configured `StatResolver` ready to resolve stats.
+///
+/// # Errors
+///
+/// Returns `YamlStatError::IoError` (mentioning the path) if the file cannot be read,
+/// or any error `load_from_json` can return.
+pub fn load_from_json_file(
+    path: impl AsRef<std::path::Path>,
+) -> Result<StatResolver, YamlStatError> {
+    StatLoader::from_json_file(path)
+}
+
+/// Creates a stat resolver from JSON content after checking for stat dependency cycles.
+///
+/// Like `load_from_json`, but runs `StatConfig::validate` first so a cycle such as
+/// `A -> B -> A` is reported by path instead of failing during resolution.
+///
+/// # Arguments
+///
+/// * `json_content` - JSON string containing stat definitions
+///
+/// # Returns
+///
+/// A configured `StatResolver` ready to resolve stats.
+///
+/// # Errors
+///
+/// Returns `YamlStatError::InvalidConfig` with the cycle path if stats depend on each other
+/// in a loop, or any error from `load_from_json`.
+pub fn load_from_json_validated(json_content: &str) -> Result<StatResolver, YamlStatError> {
+    StatConfig::from_json(json_content)?.validate()?;
+    load_from_json(json_content)
+}
+
 /// Creates a stat resolver from JSON content and resolves a specific stat.
 ///
 /// # Arguments
@@ -202,10 +254,339 @@ pub fn create_entity_stats(
     Ok(resolver)
 }
 
+/// Creates a stat resolver for an entity using a template, with typed params.
+///
+/// Like `create_entity_stats`, but params may also be strings (substituted as text, e.g. an
+/// element name in `"@{{element}}Bonus"`) or booleans (see
+/// `StatTemplateManager::apply_template_with_values`).
+///
+/// # Errors
+///
+/// Returns `YamlStatError` if JSON parsing fails, template is not found, or parameter resolution fails.
+pub fn create_entity_stats_with_values(
+    json_content: &str,
+    entity_name: &str,
+    template_name: &str,
+    params: &std::collections::HashMap<String, ParamValue>,
+) -> Result<StatResolver, YamlStatError> {
+    let manager = StatTemplateManager::from_json(json_content)?;
+    let mut resolver = StatResolver::new();
+    manager.apply_template_with_values(&mut resolver, template_name, entity_name, params)?;
+    Ok(resolver)
+}
+
+/// Runs `f`, converting a panic into an error.
+///
+/// Used by the `try_*` helpers; also handy around resolves that involve custom transforms.
+///
+/// # Errors
+///
+/// Returns `f`'s error, or `YamlStatError::InvalidConfig("internal panic: ...")` if it panicked.
+pub fn catch_panics<T>(f: impl FnOnce() -> Result<T, YamlStatError>) -> Result<T, YamlStatError> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(YamlStatError::InvalidConfig(format!(
+            "internal panic: {}",
+            message
+        )))
+    })
+}
+
+/// Like `load_from_json`, but a panic is returned as an error instead of unwinding.
+///
+/// # Errors
+///
+/// Returns `YamlStatError` as `load_from_json` does, or `InvalidConfig("internal panic: ...")`.
+pub fn try_load_from_json(json_content: &str) -> Result<StatResolver, YamlStatError> {
+    catch_panics(|| load_from_json(json_content))
+}
+
+/// Like `resolve_stat_from_json`, but a panic is returned as an error instead of unwinding.
+///
+/// # Errors
+///
+/// Returns `YamlStatError` as `resolve_stat_from_json` does, or
+/// `InvalidConfig("internal panic: ...")`.
+pub fn try_resolve_stat_from_json(
+    json_content: &str,
+    stat_name: &str,
+) -> Result<zzstat::ResolvedStat, YamlStatError> {
+    catch_panics(|| resolve_stat_from_json(json_content, stat_name))
+}
+
+/// Like `create_entity_stats`, but a panic is returned as an error instead of unwinding.
+///
+/// # Errors
+///
+/// Returns `YamlStatError` as `create_entity_stats` does, or
+/// `InvalidConfig("internal panic: ...")`.
+pub fn try_create_entity_stats(
+    json_content: &str,
+    entity_name: &str,
+    template_name: &str,
+    params: &std::collections::HashMap<String, f64>,
+) -> Result<StatResolver, YamlStatError> {
+    catch_panics(|| create_entity_stats(json_content, entity_name, template_name, params))
+}
+
+/// Parses a map of string parameters (e.g. loaded from a database) into f64 parameters.
+///
+/// # Arguments
+///
+/// * `params` - Parameter names to numeric strings (surrounding whitespace is ignored)
+///
+/// # Returns
+///
+/// The parameters ready for `apply_template`.
+///
+/// # Errors
+///
+/// Returns `YamlStatError::InvalidConfig` naming the first key (in sorted order) whose
+/// value is not a number.
+///
+/// # Example
+///
+/// ```
+/// use zzstat_json::params_from_str_map;
+/// use std::collections::HashMap;
+///
+/// let raw = HashMap::from([("level".to_string(), " 10 ".to_string())]);
+/// let params = params_from_str_map(&raw)?;
+/// assert_eq!(params["level"], 10.0);
+/// # Ok::<(), zzstat_json::YamlStatError>(())
+/// ```
+pub fn params_from_str_map(
+    params: &std::collections::HashMap<String, String>,
+) -> Result<std::collections::HashMap<String, f64>, YamlStatError> {
+    let mut keys: Vec<&String> = params.keys().collect();
+    keys.sort();
+
+    keys.into_iter()
+        .map(|key| {
+            let raw = &params[key];
+            raw.trim()
+                .parse::<f64>()
+                .map(|value| (key.clone(), value))
+                .map_err(|_| {
+                    YamlStatError::InvalidConfig(format!(
+                        "Param '{}' is not a number: {:?}",
+                        key, raw
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// Resolves a batch of stats and collects their values keyed by stat ID.
+///
+/// Works on raw `StatId`s, so stats registered by different managers (e.g. plugins
+/// sharing one resolver) can be gathered in one call.
+///
+/// # Arguments
+///
+/// * `resolver` - Resolver holding the stats
+/// * `ids` - Stats to resolve
+/// * `context` - StatContext for resolution
+///
+/// # Returns
+///
+/// Each stat's value keyed by its string form (e.g. `"player1:HP"`).
+///
+/// # Errors
+///
+/// Returns `YamlStatError::ResolutionError` for the first stat that fails to resolve.
+pub fn collect_resolved(
+    resolver: &mut StatResolver,
+    ids: &[StatId],
+    context: &StatContext,
+) -> Result<std::collections::HashMap<String, f64>, YamlStatError> {
+    ids.iter()
+        .map(|id| Ok((id.to_string(), resolver.resolve(id, context)?.value)))
+        .collect()
+}
+
+/// Resolves a stat under a context that may differ from the last one used.
+///
+/// zzstat's cache ignores the context, so a second `resolve` with a different context
+/// returns the value cached for the first. Transforms that read the context (e.g.
+/// `timed_additive`) need the cache cleared first, which this does before resolving.
+///
+/// # Arguments
+///
+/// * `resolver` - Resolver holding the stat
+/// * `stat_id` - Stat to resolve
+/// * `context` - StatContext for resolution
+///
+/// # Returns
+///
+/// The resolved stat, computed for `context`.
+///
+/// # Errors
+///
+/// Returns `YamlStatError::ResolutionError` if the stat fails to resolve.
+pub fn resolve_in_context(
+    resolver: &mut StatResolver,
+    stat_id: &StatId,
+    context: &StatContext,
+) -> Result<zzstat::ResolvedStat, YamlStatError> {
+    resolver.invalidate_all();
+    Ok(resolver.resolve(stat_id, context)?)
+}
+
+/// Evaluates a template's full source and transform pipeline without a `StatResolver`.
+///
+/// Dependency values are supplied directly (keyed by the unprefixed stat names the template
+/// references), so callers that manage their own dependency ordering can compute a single
+/// stat in isolation.
+///
+/// # Arguments
+///
+/// * `template` - Template to evaluate
+/// * `params` - Parameters to substitute in the template
+/// * `dependencies` - Already-resolved values of the stats the template depends on
+///
+/// # Returns
+///
+/// The final stat value.
+///
+/// # Errors
+///
+/// Returns `YamlStatError` if parameter resolution fails or a transform is missing a dependency.
+/// Returns `YamlStatError::InvalidConfig` if the template sets `extends` or `include`, since
+/// the templates they name are not available here.
+///
+/// # Example
+///
+/// ```no_run
+/// use zzstat_json::{eval_template, config::StatTemplate};
+/// use std::collections::HashMap;
+///
+/// let template: StatTemplate = serde_json::from_str(r#"
+/// {
+///   "sources": [{"type": "constant", "value": "{{base_hp}}"}],
+///   "transforms": [{"type": "map", "dependencies": ["Vitality"], "multiplier": 3.0}]
+/// }
+/// "#)?;
+///
+/// let params = HashMap::from([("base_hp".to_string(), 100.0)]);
+/// let dependencies = HashMap::from([("Vitality".to_string(), 10.0)]);
+/// let hp = eval_template(&template, &params, &dependencies)?;
+/// assert_eq!(hp, 130.0);
+/// # Ok::<(), zzstat_json::YamlStatError>(())
+/// ```
+pub fn eval_template(
+    template: &config::StatTemplate,
+    params: &std::collections::HashMap<String, f64>,
+    dependencies: &std::collections::HashMap<String, f64>,
+) -> Result<f64, YamlStatError> {
+    use config::SourceConfig;
+
+    if template.extends.is_some() || !template.include.is_empty() {
+        return Err(YamlStatError::InvalidConfig(
+            "eval_template cannot evaluate a template with extends or include".to_string(),
+        ));
+    }
+
+    let scratch = StatResolver::new();
+    let context = StatContext::new();
+    let stat_id = StatId::from_str("");
+
+    let base_source = template
+        .base
+        .clone()
+        .map(|value| SourceConfig::Constant { value, name: None });
+    let mut value = 0.0;
+    let mut transforms = Vec::new();
+    for source_config in base_source.iter().chain(&template.sources) {
+        if let Some(transform) =
+            StatTemplateManager::resolve_stat_scaling(source_config, params, "")?
+        {
+            transforms.push(transform);
+            continue;
+        }
+        let source = StatTemplateManager::resolve_source(
+            source_config,
+            params,
+            false,
+            &scratch,
+            "",
+            &context,
+        )?;
+        value += source.get_value(&stat_id, &context);
+    }
+
+    for transform_config in &template.transforms {
+        transforms.push(StatTemplateManager::resolve_transform(
+            transform_config,
+            params,
+        )?);
+    }
+
+    let dependency_values = dependencies
+        .iter()
+        .map(|(name, v)| (StatId::from_str(name), *v))
+        .collect();
+    for transform in transforms {
+        value = transform.apply(value, &dependency_values, &context)?;
+    }
+
+    Ok(value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Loads `examples/complex_dependencies.json`.
+    fn complex_json() -> String {
+        std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/complex_dependencies.json"
+        ))
+        .unwrap()
+    }
+
+    /// Entity configs for the complex example (Strength, Vitality, Defense, HP, ATK).
+    fn complex_entity_configs(entity_id: &str) -> Vec<EntityStatConfig> {
+        use std::collections::HashMap;
+
+        let params = HashMap::from([
+            ("level".to_string(), 10.0),
+            ("base_strength".to_string(), 30.0),
+            ("strength_per_level".to_string(), 2.0),
+            ("base_vitality".to_string(), 25.0),
+            ("vitality_per_level".to_string(), 2.0),
+            ("base_defense".to_string(), 10.0),
+            ("defense_per_level".to_string(), 1.0),
+            ("defense_bonus".to_string(), 5.0),
+            ("defense_multiplier".to_string(), 1.0),
+            ("base_hp".to_string(), 200.0),
+            ("hp_per_level".to_string(), 15.0),
+            ("hp_multiplier".to_string(), 1.0),
+            ("min_hp".to_string(), 100.0),
+            ("base_atk".to_string(), 40.0),
+            ("atk_per_level".to_string(), 3.0),
+            ("atk_bonus".to_string(), 10.0),
+            ("atk_multiplier".to_string(), 1.0),
+            ("max_atk".to_string(), 1000.0),
+        ]);
+        let mappings: Vec<_> = [
+            ("Strength", "BaseStrength"),
+            ("Vitality", "BaseVitality"),
+            ("Defense", "ComplexDefense"),
+            ("HP", "ComplexHP"),
+            ("ATK", "ComplexATK"),
+        ]
+        .iter()
+        .map(|(stat, template)| (stat.to_string(), template.to_string(), params.clone()))
+        .collect();
+        StatTemplateManager::entity_params_to_configs(entity_id, &mappings)
+    }
+
     #[test]
     fn test_basic_json_loading() {
         let json = r#"
@@ -293,4 +674,4505 @@ mod tests {
         // (100 + 10*5) * 1.5 = 150 * 1.5 = 225
         assert_eq!(resolved.value, 225.0);
     }
+
+    #[test]
+    fn test_load_missing_file_reports_path() {
+        let path = "does/not/exist/stats.json";
+        let result = StatLoader::from_json_file(path);
+        match result {
+            Err(YamlStatError::IoError(e)) => {
+                assert_eq!(e.kind(), std::io::ErrorKind::NotFound);
+                assert!(e.to_string().contains(path));
+            }
+            _ => panic!("Expected IoError"),
+        }
+    }
+
+    #[test]
+    fn test_default_transforms() {
+        let json = r#"
+{
+  "default_transforms": [
+    {"type": "clamp", "min": 0.0, "max": null}
+  ],
+  "stats": {
+    "HP": {
+      "sources": [{"type": "constant", "value": -20.0}]
+    },
+    "Debt": {
+      "sources": [{"type": "constant", "value": -20.0}],
+      "ignore_default_transforms": true
+    }
+  }
+}
+"#;
+        let mut resolver = load_from_json(json).unwrap();
+        let context = StatContext::new();
+        let hp = resolver.resolve(&StatId::from_str("HP"), &context).unwrap();
+        let debt = resolver
+            .resolve(&StatId::from_str("Debt"), &context)
+            .unwrap();
+        assert_eq!(hp.value, 0.0);
+        assert_eq!(debt.value, -20.0);
+
+        let manager = StatTemplateManager::from_json(
+            r#"{
+  "default_transforms": [{"type": "clamp", "min": 0.0, "max": null}],
+  "templates": {"Base": {"sources": [{"type": "constant", "value": -5.0}]}}
+}"#,
+        )
+        .unwrap();
+        let mut resolver = StatResolver::new();
+        manager
+            .apply_template(
+                &mut resolver,
+                "Base",
+                "e:Base",
+                &std::collections::HashMap::new(),
+            )
+            .unwrap();
+        let base = resolver
+            .resolve(&StatId::from_str("e:Base"), &context)
+            .unwrap();
+        assert_eq!(base.value, 0.0);
+    }
+
+    #[test]
+    fn test_resolve_sheet_timed() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "Base": {"sources": [{"type": "constant", "value": "{{base}}"}]}
+  }
+}
+"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        let mut resolver = StatResolver::new();
+        let configs = ["HP", "ATK", "DEF"]
+            .iter()
+            .map(|stat_type| EntityStatConfig {
+                entity_id: "hero".to_string(),
+                stat_type: stat_type.to_string(),
+                template_name: "Base".to_string(),
+                params: HashMap::from([("base".to_string(), 10.0)]),
+            })
+            .collect();
+        manager.load_entity_stats(&mut resolver, configs).unwrap();
+
+        let (values, timings) = manager
+            .resolve_sheet_timed(&mut resolver, "hero", &StatContext::new())
+            .unwrap();
+        assert_eq!(values.len(), 3);
+        for stat_type in values.keys() {
+            assert!(timings.contains_key(stat_type));
+        }
+        assert_eq!(values["ATK"], 10.0);
+    }
+
+    #[test]
+    fn test_apply_template_json_params() {
+        let json = r#"
+{
+  "templates": {
+    "HP": {
+      "sources": [{"type": "constant", "value": "{{base_hp}}"}],
+      "transforms": [
+        {
+          "type": "conditional",
+          "condition_stat": "Flag",
+          "condition_value": 0.0,
+          "operator": ">=",
+          "then": {"type": "additive", "value": "{{has_shield}}"}
+        }
+      ]
+    },
+    "Flag": {"sources": [{"type": "constant", "value": 0.0}]}
+  }
+}
+"#;
+        let manager = StatTemplateManager::from_json(json).unwrap();
+        let mut resolver = StatResolver::new();
+        let empty = serde_json::json!({});
+        manager
+            .apply_template_json_params(&mut resolver, "Flag", "p:Flag", &empty)
+            .unwrap();
+        let params = serde_json::json!({"base_hp": 100, "has_shield": true});
+        manager
+            .apply_template_json_params(&mut resolver, "HP", "p:HP", &params)
+            .unwrap();
+        let hp = resolver
+            .resolve(&StatId::from_str("p:HP"), &StatContext::new())
+            .unwrap();
+        assert_eq!(hp.value, 101.0);
+
+        let bad = serde_json::json!({"base_hp": [1, 2]});
+        let result = manager.apply_template_json_params(&mut resolver, "HP", "q:HP", &bad);
+        assert!(
+            matches!(result, Err(YamlStatError::InvalidConfig(msg)) if msg.contains("base_hp"))
+        );
+    }
+
+    #[test]
+    fn test_base_shorthand() {
+        let json = r#"
+{
+  "stats": {
+    "Short": {"base": 40.0, "transforms": [{"type": "multiplicative", "value": 2.0}]},
+    "Long": {
+      "sources": [{"type": "constant", "value": 40.0}],
+      "transforms": [{"type": "multiplicative", "value": 2.0}]
+    }
+  },
+  "templates": {
+    "Speed": {"base": "{{speed}}", "sources": [{"type": "constant", "value": 1.0}]}
+  }
+}
+"#;
+        let mut resolver = load_from_json(json).unwrap();
+        let context = StatContext::new();
+        let short = resolver
+            .resolve(&StatId::from_str("Short"), &context)
+            .unwrap();
+        let long = resolver
+            .resolve(&StatId::from_str("Long"), &context)
+            .unwrap();
+        assert_eq!(short.value, long.value);
+
+        let mut params = std::collections::HashMap::new();
+        params.insert("speed".to_string(), 5.0);
+        let mut resolver = create_entity_stats(json, "e:Speed", "Speed", &params).unwrap();
+        let speed = resolver
+            .resolve(&StatId::from_str("e:Speed"), &context)
+            .unwrap();
+        assert_eq!(speed.value, 6.0);
+    }
+
+    #[test]
+    fn test_reset_manager_and_resolver() {
+        use std::collections::HashMap;
+
+        let json = r#"{"templates": {"Base": {"sources": [{"type": "constant", "value": 7.0}]}}}"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        let mut resolver = StatResolver::new();
+        let context = StatContext::new();
+        let configs = StatTemplateManager::entity_params_to_configs(
+            "hero",
+            &[("HP".to_string(), "Base".to_string(), HashMap::new())],
+        );
+        manager
+            .load_entity_stats(&mut resolver, configs.clone())
+            .unwrap();
+        assert_eq!(
+            manager
+                .resolve_sheet_timed(&mut resolver, "hero", &context)
+                .unwrap()
+                .0
+                .len(),
+            1
+        );
+
+        manager.reset(&mut resolver);
+        assert!(
+            manager
+                .resolve_sheet_timed(&mut resolver, "hero", &context)
+                .unwrap()
+                .0
+                .is_empty()
+        );
+        assert!(
+            manager
+                .resolve_entity_stat(&mut resolver, "hero", "HP", &context)
+                .is_err()
+        );
+
+        manager.load_entity_stats(&mut resolver, configs).unwrap();
+        let hp = manager
+            .resolve_entity_stat(&mut resolver, "hero", "HP", &context)
+            .unwrap();
+        assert_eq!(hp.value, 7.0);
+    }
+
+    #[test]
+    fn test_round_transform_decimals() {
+        let json = r#"
+{
+  "stats": {
+    "Crit": {
+      "sources": [{"type": "constant", "value": 42.376}],
+      "transforms": [{"type": "round", "decimals": 1}]
+    },
+    "HP": {
+      "sources": [{"type": "constant", "value": 42.6}],
+      "transforms": [{"type": "round"}]
+    }
+  }
+}
+"#;
+        let mut resolver = load_from_json(json).unwrap();
+        let context = StatContext::new();
+        let crit = resolver
+            .resolve(&StatId::from_str("Crit"), &context)
+            .unwrap();
+        let hp = resolver.resolve(&StatId::from_str("HP"), &context).unwrap();
+        assert_eq!(crit.value, 42.4);
+        assert_eq!(hp.value, 43.0);
+    }
+
+    #[test]
+    fn test_shadowed_params() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "constants": {"level_cap": 60.0, "base_hp": 100.0},
+  "templates": {
+    "HP": {
+      "sources": [{"type": "constant", "value": "{{base_hp}}"}],
+      "transforms": [{"type": "clamp", "min": null, "max": "{{level_cap}}"}]
+    }
+  }
+}
+"#;
+        let manager = StatTemplateManager::from_json(json).unwrap();
+
+        let no_shadow = HashMap::from([("bonus".to_string(), 1.0)]);
+        assert!(
+            manager
+                .shadowed_params("HP", &no_shadow)
+                .unwrap()
+                .is_empty()
+        );
+
+        let shadow = HashMap::from([("base_hp".to_string(), 50.0)]);
+        assert_eq!(
+            manager.shadowed_params("HP", &shadow).unwrap(),
+            vec!["base_hp"]
+        );
+
+        // The per-call param wins over the constant
+        let mut resolver = StatResolver::new();
+        manager
+            .apply_template(&mut resolver, "HP", "e:HP", &shadow)
+            .unwrap();
+        let hp = resolver
+            .resolve(&StatId::from_str("e:HP"), &StatContext::new())
+            .unwrap();
+        assert_eq!(hp.value, 50.0);
+    }
+
+    #[test]
+    fn test_ramp_transform() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "Damage": {
+      "sources": [{"type": "constant", "value": 100.0}],
+      "transforms": [
+        {"type": "ramp", "per_stack": 2.0, "stacks": "{{stacks}}", "cap": 20.0}
+      ]
+    }
+  }
+}
+"#;
+        let context = StatContext::new();
+        let five = HashMap::from([("stacks".to_string(), 5.0)]);
+        let mut resolver = create_entity_stats(json, "e:Damage", "Damage", &five).unwrap();
+        let value = resolver
+            .resolve(&StatId::from_str("e:Damage"), &context)
+            .unwrap()
+            .value;
+        assert!((value - 110.0).abs() < 1e-9);
+
+        let fifteen = HashMap::from([("stacks".to_string(), 15.0)]);
+        let mut resolver = create_entity_stats(json, "e:Damage", "Damage", &fifteen).unwrap();
+        let value = resolver
+            .resolve(&StatId::from_str("e:Damage"), &context)
+            .unwrap()
+            .value;
+        assert!((value - 120.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_resolve_entity_stat_bounded() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "A": {"sources": [{"type": "constant", "value": 1.0}]},
+    "B": {"base": 1.0, "transforms": [{"type": "map", "dependencies": ["A"]}]},
+    "C": {"base": 1.0, "transforms": [{"type": "map", "dependencies": ["B"]}]},
+    "D": {"base": 1.0, "transforms": [{"type": "map", "dependencies": ["C"]}]}
+  }
+}
+"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        let mut resolver = StatResolver::new();
+        let mappings: Vec<_> = ["A", "B", "C", "D"]
+            .iter()
+            .map(|s| (s.to_string(), s.to_string(), HashMap::new()))
+            .collect();
+        let configs = StatTemplateManager::entity_params_to_configs("e", &mappings);
+        manager.load_entity_stats(&mut resolver, configs).unwrap();
+
+        let context = StatContext::new();
+        let result = manager.resolve_entity_stat_bounded(&mut resolver, "e", "D", &context, 2);
+        assert!(
+            matches!(result, Err(YamlStatError::InvalidConfig(msg)) if msg.contains("max resolution depth exceeded"))
+        );
+
+        let d = manager
+            .resolve_entity_stat_bounded(&mut resolver, "e", "D", &context, 3)
+            .unwrap();
+        assert_eq!(d.value, 4.0);
+    }
+
+    #[test]
+    fn test_extends_by_id_or_key() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "BaseHPTemplate": {
+      "id": "base_hp",
+      "sources": [{"type": "constant", "value": 100.0}],
+      "transforms": [{"type": "clamp", "min": 0.0, "max": 150.0}]
+    },
+    "ByKey": {
+      "extends": "BaseHPTemplate",
+      "transforms": [{"type": "multiplicative", "value": 2.0}]
+    },
+    "ById": {
+      "extends": "base_hp",
+      "transforms": [{"type": "multiplicative", "value": 2.0}]
+    }
+  }
+}
+"#;
+        let manager = StatTemplateManager::from_json(json).unwrap();
+        let mut resolver = StatResolver::new();
+        let params = HashMap::new();
+        manager
+            .apply_template(&mut resolver, "ByKey", "a:HP", &params)
+            .unwrap();
+        manager
+            .apply_template(&mut resolver, "ById", "b:HP", &params)
+            .unwrap();
+        manager
+            .apply_template(&mut resolver, "base_hp", "c:HP", &params)
+            .unwrap();
+
+        let context = StatContext::new();
+        let a = resolver
+            .resolve(&StatId::from_str("a:HP"), &context)
+            .unwrap();
+        let b = resolver
+            .resolve(&StatId::from_str("b:HP"), &context)
+            .unwrap();
+        let c = resolver
+            .resolve(&StatId::from_str("c:HP"), &context)
+            .unwrap();
+        assert_eq!(a.value, 200.0); // parent clamp runs before the child's multiplier
+        assert_eq!(a.value, b.value);
+        assert_eq!(c.value, 100.0);
+
+        let cyclic = r#"{"templates": {"A": {"extends": "B"}, "B": {"extends": "A"}}}"#;
+        assert!(StatTemplateManager::from_json(cyclic).is_err());
+
+        // Ids must be unique, or lookups by id would depend on map order
+        let duplicate = json.replace(r#""ByKey": {"#, r#""ByKey": {"id": "base_hp","#);
+        let err = StatTemplateManager::from_json(&duplicate).err().unwrap();
+        assert!(
+            err.to_string().contains(
+                "Template id 'base_hp' of 'ByKey' is already used by template 'BaseHPTemplate'"
+            ),
+            "{}",
+            err
+        );
+        let shadowing = json.replace(r#""id": "base_hp""#, r#""id": "ById""#);
+        assert!(StatTemplateManager::from_json(&shadowing).is_err());
+        let stats =
+            duplicate.replacen('{', r#"{"stats": {"hero:HP": {"template": "base_hp"}},"#, 1);
+        assert!(load_from_json(&stats).is_err());
+    }
+
+    #[test]
+    fn test_eval_template_warrior_hp() {
+        use std::collections::HashMap;
+
+        let json = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/warrior.json"
+        ))
+        .unwrap();
+        let config: StatConfig = serde_json::from_str(&json).unwrap();
+        let params = HashMap::from([
+            ("base_hp".to_string(), 150.0),
+            ("hp_per_level".to_string(), 12.0),
+            ("level".to_string(), 10.0),
+        ]);
+        let dependencies = HashMap::from([
+            ("Vitality".to_string(), 35.0),
+            ("Strength".to_string(), 45.0),
+        ]);
+
+        let hp = eval_template(&config.templates["WarriorHP"], &params, &dependencies).unwrap();
+        // (150 + 12*10 + 35*3 + 45*2) * 1.2 = 558
+        assert!((hp - 558.0).abs() < 1e-9);
+
+        let missing = eval_template(&config.templates["WarriorHP"], &params, &HashMap::new());
+        assert!(missing.is_err());
+
+        // An inheriting template is rejected instead of silently losing its parent
+        let config: StatConfig = serde_json::from_str(
+            r#"
+{
+  "templates": {
+    "Parent": {"base": 100.0},
+    "Child": {"extends": "Parent", "transforms": [{"type": "multiplicative", "value": 2.0}]},
+    "Mixed": {"include": ["Parent"]}
+  }
+}
+"#,
+        )
+        .unwrap();
+        for name in ["Child", "Mixed"] {
+            let result = eval_template(&config.templates[name], &params, &dependencies);
+            assert!(
+                matches!(result, Err(YamlStatError::InvalidConfig(_))),
+                "{name}: {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_flag_operators() {
+        let json = r#"
+{
+  "stats": {
+    "Enraged": {"sources": [{"type": "constant", "value": 1.0}]},
+    "Stunned": {"sources": [{"type": "constant", "value": 0.0}]},
+    "ATK": {
+      "sources": [{"type": "constant", "value": 100.0}],
+      "transforms": [
+        {
+          "type": "conditional",
+          "condition_stat": "Enraged",
+          "operator": "is_true",
+          "then": {"type": "additive", "value": 50.0}
+        },
+        {
+          "type": "conditional",
+          "condition_stat": "Stunned",
+          "operator": "is_true",
+          "then": {"type": "multiplicative", "value": 0.0}
+        },
+        {
+          "type": "conditional",
+          "condition_stat": "Stunned",
+          "operator": "is_false",
+          "then": {"type": "additive", "value": 1.0}
+        }
+      ]
+    }
+  }
+}
+"#;
+        let mut resolver = load_from_json(json).unwrap();
+        let atk = resolver
+            .resolve(&StatId::from_str("ATK"), &StatContext::new())
+            .unwrap();
+        assert_eq!(atk.value, 151.0);
+    }
+
+    #[test]
+    fn test_entity_stats_by_depth() {
+        let mut manager = StatTemplateManager::from_json(&complex_json()).unwrap();
+        let mut resolver = StatResolver::new();
+        manager
+            .load_entity_stats(&mut resolver, complex_entity_configs("tank"))
+            .unwrap();
+
+        let depths: std::collections::HashMap<_, _> =
+            manager.entity_stats_by_depth("tank").into_iter().collect();
+        assert_eq!(depths["Strength"], 0);
+        assert_eq!(depths["Vitality"], 0);
+        assert_eq!(depths["Defense"], 1);
+        assert_eq!(depths["HP"], 2);
+        assert_eq!(depths.values().max(), Some(&depths["HP"]));
+
+        let ordered = manager.entity_stats_by_depth("tank");
+        assert_eq!(ordered.first().map(|(_, d)| *d), Some(0));
+        assert!(manager.entity_stats_by_depth("nobody").is_empty());
+    }
+
+    #[test]
+    fn test_floor_and_ceil_to_stat() {
+        let json = r#"
+{
+  "templates": {
+    "Strength": {"sources": [{"type": "constant", "value": 60.0}]},
+    "ATK": {
+      "sources": [{"type": "constant", "value": 40.0}],
+      "transforms": [{"type": "floor_to_stat", "stat": "Strength"}]
+    },
+    "Shield": {
+      "sources": [{"type": "constant", "value": 90.0}],
+      "transforms": [{"type": "ceil_to_stat", "stat": "Strength"}]
+    }
+  }
+}
+"#;
+        let manager = StatTemplateManager::from_json(json).unwrap();
+        let mut resolver = StatResolver::new();
+        let params = std::collections::HashMap::new();
+        for stat in ["Strength", "ATK", "Shield"] {
+            manager
+                .apply_template(&mut resolver, stat, &format!("hero:{}", stat), &params)
+                .unwrap();
+        }
+
+        let context = StatContext::new();
+        let atk = manager
+            .resolve_entity_stat(&mut resolver, "hero", "ATK", &context)
+            .unwrap();
+        let shield = manager
+            .resolve_entity_stat(&mut resolver, "hero", "Shield", &context)
+            .unwrap();
+        assert_eq!(atk.value, 60.0);
+        assert_eq!(shield.value, 60.0);
+    }
+
+    #[test]
+    fn test_stats_instantiate_templates_inline() {
+        let json = r#"
+{
+  "templates": {
+    "BaseHP": {
+      "sources": [
+        {"type": "constant", "value": "{{base_hp}}"},
+        {"type": "scaling", "base": 0.0, "scale": 10.0, "level": "{{level}}"}
+      ]
+    }
+  },
+  "stats": {
+    "HP": {
+      "template": "BaseHP",
+      "params": {"base_hp": 100.0, "level": 5.0},
+      "transforms": [{"type": "multiplicative", "value": 2.0}]
+    },
+    "MP": {"sources": [{"type": "constant", "value": 30.0}]}
+  }
+}
+"#;
+        let mut resolver = load_from_json(json).unwrap();
+        let context = StatContext::new();
+        let hp = resolver.resolve(&StatId::from_str("HP"), &context).unwrap();
+        let mp = resolver.resolve(&StatId::from_str("MP"), &context).unwrap();
+        assert_eq!(hp.value, 300.0); // (100 + 10*5) * 2
+        assert_eq!(mp.value, 30.0);
+
+        let missing = r#"{"stats": {"HP": {"template": "Nope"}}}"#;
+        assert!(load_from_json(missing).is_err());
+    }
+
+    #[test]
+    fn test_inline_template_defaults_run_last() {
+        let json = r#"
+{
+  "default_transforms": [{"type": "clamp", "max": 100.0}],
+  "templates": {
+    "Doubled": {"base": "{{base}}", "transforms": [{"type": "multiplicative", "value": 2.0}]}
+  },
+  "stats": {
+    "ATK": {
+      "template": "Doubled",
+      "params": {"base": 40.0},
+      "transforms": [{"type": "additive", "value": 30.0}]
+    }
+  }
+}
+"#;
+        let mut resolver = load_from_json(json).unwrap();
+        let atk = resolver
+            .resolve(&StatId::from_str("ATK"), &StatContext::new())
+            .unwrap();
+        // Template ×2 (80), the entry's own +30 (110), then the default clamp (100)
+        let steps: Vec<f64> = atk.transforms.iter().map(|(_, value)| *value).collect();
+        assert_eq!(steps, [80.0, 110.0, 100.0]);
+        assert_eq!(atk.value, 100.0);
+    }
+
+    #[test]
+    fn test_kind_labels_match_serde_tags() {
+        use config::{SourceConfig, TransformConfig};
+
+        let transforms: Vec<TransformConfig> = serde_json::from_str(
+            r#"[
+  {"type": "multiplicative", "value": 1.0},
+  {"type": "additive", "value": 1.0},
+  {"type": "clamp", "min": 0.0, "max": 1.0},
+  {"type": "round"},
+  {"type": "ramp", "per_stack": 1.0, "stacks": 1.0, "cap": 1.0},
+  {"type": "floor_to_stat", "stat": "A"},
+  {"type": "ceil_to_stat", "stat": "A"},
+  {"type": "min_of_stats", "stats": ["A", "B"]},
+  {"type": "max_of_stats", "stats": ["A", "B"]},
+  {"type": "conditional", "condition_stat": "A", "condition_value": 1.0, "operator": ">",
+   "then": {"type": "additive", "value": 1.0}},
+  {"type": "range", "condition_stat": "A", "min": 1.0, "max": 2.0,
+   "then": {"type": "additive", "value": 1.0}},
+  {"type": "weighted_average", "terms": [["A", 1.0]]},
+  {"type": "timed_additive", "value": 1.0, "start_tick": 0.0, "end_tick": 1.0, "tick_key": "tick"},
+  {"type": "rate_limit", "max_delta": 1.0, "prev_key": "prev"},
+  {"type": "percent_increase", "value": "20%"},
+  {"type": "table_lookup", "key_param": "element", "table": {"fire": 1.2}},
+  {"type": "ratio_scale", "numerator_stat": "A", "denominator_stat": "B", "factor": 0.1},
+  {"type": "remap", "in_min": 0.0, "in_max": 1.0, "out_min": 0.0, "out_max": 10.0},
+  {"type": "excess_of", "stat": "A", "threshold": 50.0, "factor": 0.5},
+  {"type": "clamp_to_stat_fraction", "stat": "A", "max_fraction": 0.5},
+  {"type": "subtractive", "value": 1.0},
+  {"type": "divide", "value": 2.0},
+  {"type": "power", "exponent": 0.5},
+  {"type": "percent_add", "percent": 15.0},
+  {"type": "quantize", "step": 5.0},
+  {"type": "min", "value": 1.0},
+  {"type": "max", "value": 10.0},
+  {"type": "conditional_stat", "condition_stat": "A", "operator": "is_true",
+   "then_stat": "B", "else_stat": "C"},
+  {"type": "map", "dependencies": ["A"]},
+  {"type": "best_of", "mode": "max", "options": [{"type": "additive", "value": 1.0}]}
+]"#,
+        )
+        .unwrap();
+        let sources: Vec<SourceConfig> = serde_json::from_str(
+            r#"[
+  {"type": "constant", "value": 1.0},
+  {"type": "scaling", "base": 0.0, "scale": 1.0}
+]"#,
+        )
+        .unwrap();
+
+        for transform in &transforms {
+            let tag = serde_json::to_value(transform).unwrap()["type"].clone();
+            assert_eq!(tag, transform.kind_label());
+        }
+        for source in &sources {
+            let tag = serde_json::to_value(source).unwrap()["type"].clone();
+            assert_eq!(tag, source.kind_label());
+        }
+    }
+
+    #[test]
+    fn test_weighted_average_transform() {
+        let config = |w1: f64, w2: f64| {
+            format!(
+                r#"{{
+  "stats": {{
+    "Offense": {{"sources": [{{"type": "constant", "value": 80.0}}]}},
+    "Defense": {{"sources": [{{"type": "constant", "value": 40.0}}]}},
+    "Rating": {{
+      "transforms": [
+        {{"type": "weighted_average", "terms": [["Offense", {}], ["Defense", {}]]}}
+      ]
+    }}
+  }}
+}}"#,
+                w1, w2
+            )
+        };
+
+        let context = StatContext::new();
+        let rating_id = StatId::from_str("Rating");
+        let mut resolver = load_from_json(&config(3.0, 1.0)).unwrap();
+        let rating = resolver.resolve(&rating_id, &context).unwrap();
+        assert_eq!(rating.value, 70.0); // (80*3 + 40*1) / 4
+
+        let mut doubled = load_from_json(&config(6.0, 2.0)).unwrap();
+        assert_eq!(
+            doubled.resolve(&rating_id, &context).unwrap().value,
+            rating.value
+        );
+
+        assert!(load_from_json(&config(1.0, -1.0)).is_err());
+    }
+
+    #[test]
+    fn test_snippet_refs() {
+        let json = r#"
+{
+  "snippets": {
+    "clamp_1_200": {"type": "clamp", "min": 1.0, "max": 200.0}
+  },
+  "source_snippets": {
+    "base_10": {"type": "constant", "value": 10.0}
+  },
+  "stats": {
+    "Strength": {
+      "sources": [{"$ref": "base_10"}, {"type": "constant", "value": 500.0}],
+      "transforms": [{"$ref": "clamp_1_200"}]
+    },
+    "Agility": {
+      "sources": [{"$ref": "base_10"}],
+      "transforms": [{"$ref": "clamp_1_200"}]
+    }
+  }
+}
+"#;
+        let mut resolver = load_from_json(json).unwrap();
+        let context = StatContext::new();
+        let strength = resolver
+            .resolve(&StatId::from_str("Strength"), &context)
+            .unwrap();
+        let agility = resolver
+            .resolve(&StatId::from_str("Agility"), &context)
+            .unwrap();
+        assert_eq!(strength.value, 200.0);
+        assert_eq!(agility.value, 10.0);
+
+        let missing = r#"{"stats": {"HP": {"transforms": [{"$ref": "nope"}]}}}"#;
+        assert!(
+            matches!(load_from_json(missing), Err(YamlStatError::InvalidConfig(msg)) if msg.contains("nope"))
+        );
+
+        // best_of options may use snippets too
+        let best_of = r#"
+{
+  "snippets": {"double": {"type": "multiplicative", "value": 2.0}},
+  "stats": {"HP": {"base": 10.0, "transforms": [
+    {"type": "best_of", "mode": "max", "options": [{"$ref": "double"}, {"type": "additive", "value": 5.0}]}
+  ]}}
+}
+"#;
+        assert_eq!(resolve_stat_from_json(best_of, "HP").unwrap().value, 20.0);
+
+        // Snippets may nest other snippets, and one snippet may appear several times
+        let nested = r#"
+{
+  "snippets": {
+    "one": {"type": "additive", "value": 1.0},
+    "gated": {"type": "conditional", "condition_stat": "HP", "condition_value": 0.0, "operator": ">", "then": {"$ref": "one"}}
+  },
+  "stats": {"HP": {"base": 1.0}, "MP": {"base": 1.0, "transforms": [{"$ref": "gated"}, {"$ref": "one"}]}}
+}
+"#;
+        assert_eq!(resolve_stat_from_json(nested, "MP").unwrap().value, 3.0);
+
+        // A snippet that refers back to itself is an error, not endless recursion
+        let cyclic = r#"
+{
+  "snippets": {
+    "a": {"type": "conditional", "condition_stat": "HP", "condition_value": 0.0, "operator": ">", "then": {"$ref": "a"}}
+  },
+  "stats": {"HP": {"base": 1.0}, "MP": {"base": 1.0, "transforms": [{"$ref": "a"}]}}
+}
+"#;
+        let Err(err) = try_load_from_json(cyclic) else {
+            panic!("a self-referencing snippet should fail");
+        };
+        assert!(err.to_string().contains("snippet cycle: a -> a"), "{}", err);
+        let indirect = r#"
+{
+  "snippets": {
+    "b": {"$ref": "c"},
+    "c": {"type": "best_of", "mode": "max", "options": [{"$ref": "b"}]}
+  },
+  "stats": {"HP": {"base": 1.0}}
+}
+"#;
+        let Err(err) = try_load_from_json(indirect) else {
+            panic!("an indirect snippet cycle should fail");
+        };
+        assert!(
+            err.to_string().contains("snippet cycle: b -> c -> b"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_empty_map_dependencies_rejected() {
+        let json = r#"
+{
+  "stats": {
+    "HP": {
+      "sources": [{"type": "constant", "value": 100.0}],
+      "transforms": [{"type": "map", "dependencies": [], "multiplier": 2.0}]
+    }
+  }
+}
+"#;
+        assert!(matches!(
+            load_from_json(json),
+            Err(YamlStatError::InvalidConfig(msg)) if msg.contains("at least one dependency")
+        ));
+
+        let templates = json.replace("\"stats\"", "\"templates\"");
+        let params = std::collections::HashMap::new();
+        assert!(create_entity_stats(&templates, "e:HP", "HP", &params).is_err());
+    }
+
+    #[test]
+    fn test_resolve_entity_stat_multi() {
+        use std::collections::HashMap;
+        use zzstat::StatTransform;
+
+        /// Halves the value when the context has `pvp` set.
+        struct PvpReduction;
+
+        impl StatTransform for PvpReduction {
+            fn depends_on(&self) -> Vec<StatId> {
+                Vec::new()
+            }
+
+            fn apply(
+                &self,
+                value: f64,
+                _dependencies: &HashMap<StatId, f64>,
+                context: &StatContext,
+            ) -> Result<f64, zzstat::StatError> {
+                let pvp: bool = context.get("pvp").unwrap_or(false);
+                Ok(if pvp { value * 0.5 } else { value })
+            }
+
+            fn description(&self) -> String {
+                "PvpReduction".to_string()
+            }
+        }
+
+        let json = r#"{"templates": {"Damage": {"base": 100.0}}}"#;
+        let manager = StatTemplateManager::from_json(json).unwrap();
+        let mut resolver = StatResolver::new();
+        manager
+            .apply_template(&mut resolver, "Damage", "hero:Damage", &HashMap::new())
+            .unwrap();
+        manager.add_transform_to_entity(&mut resolver, "hero", "Damage", Box::new(PvpReduction));
+
+        let pve = StatContext::new();
+        let mut pvp = StatContext::new();
+        pvp.set("pvp", true);
+
+        let values = manager
+            .resolve_entity_stat_multi(
+                &mut resolver,
+                "hero",
+                "Damage",
+                &[("pve", &pve), ("pvp", &pvp)],
+            )
+            .unwrap();
+        assert_eq!(values["pve"], 100.0);
+        assert_eq!(values["pvp"], 50.0);
+    }
+
+    #[test]
+    fn test_describe_config() {
+        let json = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/warrior.json"
+        ))
+        .unwrap();
+        let config = config::StatConfig::from_json(&json).unwrap();
+        let description = config.describe();
+
+        for name in config.templates.keys() {
+            assert!(description.contains(name.as_str()));
+        }
+        for kind in [
+            "constant",
+            "scaling",
+            "map",
+            "multiplicative",
+            "clamp",
+            "conditional",
+        ] {
+            assert!(description.contains(kind), "missing {}", kind);
+        }
+        assert!(description.contains("{{base_hp}}"));
+    }
+
+    #[test]
+    fn test_entity_params_merged_under_call_params() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "HP": {
+      "sources": [
+        {"type": "constant", "value": "{{base_hp}}"},
+        {"type": "scaling", "base": 0.0, "scale": 10.0, "level": "{{level}}"}
+      ]
+    }
+  }
+}
+"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        manager.set_entity_params(EntityParams {
+            entity_id: "hero".to_string(),
+            params: HashMap::from([("level".to_string(), 5.0), ("base_hp".to_string(), 1.0)]),
+        });
+
+        let mut resolver = StatResolver::new();
+        let params = HashMap::from([("base_hp".to_string(), 100.0)]);
+        manager
+            .apply_template(&mut resolver, "HP", "hero:HP", &params)
+            .unwrap();
+        let context = StatContext::new();
+        let hp = manager
+            .resolve_entity_stat(&mut resolver, "hero", "HP", &context)
+            .unwrap();
+        assert_eq!(hp.value, 150.0); // per-call base_hp wins, level comes from the entity
+
+        assert!(
+            manager
+                .apply_template(&mut resolver, "HP", "other:HP", &params)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_resolve_entity_stat_flagged() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "Strength": {"base": "{{strength}}"},
+    "ATK": {
+      "base": "{{atk}}",
+      "transforms": [
+        {
+          "type": "conditional",
+          "condition_stat": "Strength",
+          "condition_value": 50.0,
+          "operator": ">=",
+          "then": {"type": "multiplicative", "value": 2.0}
+        },
+        {"type": "clamp", "min": 10.0, "max": 200.0}
+      ]
+    }
+  }
+}
+"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        let mut resolver = StatResolver::new();
+        let mappings = |entity: &str, strength: f64, atk: f64| {
+            let params =
+                HashMap::from([("strength".to_string(), strength), ("atk".to_string(), atk)]);
+            StatTemplateManager::entity_params_to_configs(
+                entity,
+                &[
+                    (
+                        "Strength".to_string(),
+                        "Strength".to_string(),
+                        params.clone(),
+                    ),
+                    ("ATK".to_string(), "ATK".to_string(), params),
+                ],
+            )
+        };
+        manager
+            .load_entity_stats(&mut resolver, mappings("strong", 60.0, 150.0))
+            .unwrap();
+        manager
+            .load_entity_stats(&mut resolver, mappings("weak", 10.0, 50.0))
+            .unwrap();
+
+        let context = StatContext::new();
+        let (value, flags) = manager
+            .resolve_entity_stat_flagged(&mut resolver, "strong", "ATK", &context)
+            .unwrap();
+        assert_eq!(value, 200.0);
+        assert!(flags.clamped_max && flags.conditional_fired && !flags.clamped_min);
+
+        let (value, flags) = manager
+            .resolve_entity_stat_flagged(&mut resolver, "weak", "ATK", &context)
+            .unwrap();
+        assert_eq!(value, 50.0);
+        assert_eq!(flags, StatFlags::default());
+    }
+
+    #[test]
+    fn test_names_label_breakdown_entries() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "HP": {
+      "base": 50.0,
+      "sources": [{"type": "constant", "value": 100.0, "name": "Base HP value"}],
+      "transforms": [{"type": "additive", "value": 10.0, "name": "Ring bonus"}]
+    }
+  },
+  "stats": {
+    "Mana": {
+      "sources": [{"type": "constant", "value": 30.0}],
+      "transforms": [{"type": "multiplicative", "value": 2.0, "name": "Staff"}]
+    }
+  }
+}
+"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        let mut resolver = StatResolver::new();
+        let configs = StatTemplateManager::entity_params_to_configs(
+            "hero",
+            &[("HP".to_string(), "HP".to_string(), HashMap::new())],
+        );
+        manager.load_entity_stats(&mut resolver, configs).unwrap();
+
+        let context = StatContext::new();
+        let resolved = manager
+            .resolve_entity_stat(&mut resolver, "hero", "HP", &context)
+            .unwrap();
+        assert_eq!(resolved.value, 160.0);
+        assert_eq!(resolved.sources[0].0, "Source #1");
+        assert_eq!(resolved.sources[1], ("Base HP value".to_string(), 100.0));
+        assert_eq!(resolved.transforms[0], ("Ring bonus".to_string(), 160.0));
+
+        let mut resolver = StatLoader::from_json(json).unwrap();
+        let mana = resolver
+            .resolve(&StatId::from_str("Mana"), &context)
+            .unwrap();
+        assert_eq!(mana.transforms[0], ("Staff".to_string(), 60.0));
+    }
+
+    #[test]
+    fn test_scaling_source_with_stat_level() {
+        let json = r#"
+{
+  "stats": {
+    "Intelligence": {"base": 20.0},
+    "SpellPower": {
+      "sources": [
+        {"type": "scaling", "base": 5.0, "scale": 2.0, "level": {"type": "stat", "stat": "Intelligence"}}
+      ],
+      "transforms": [{"type": "multiplicative", "value": 1.5}]
+    }
+  }
+}
+"#;
+        let context = StatContext::new();
+        let mut resolver = StatLoader::from_json(json).unwrap();
+        let spell_power = resolver
+            .resolve(&StatId::from_str("SpellPower"), &context)
+            .unwrap();
+        assert_eq!(spell_power.value, (5.0 + 2.0 * 20.0) * 1.5);
+
+        let smarter = json.replace(r#""base": 20.0"#, r#""base": 30.0"#);
+        let mut resolver = StatLoader::from_json(&smarter).unwrap();
+        let stronger = resolver
+            .resolve(&StatId::from_str("SpellPower"), &context)
+            .unwrap();
+        assert!(stronger.value > spell_power.value);
+    }
+
+    #[test]
+    fn test_validate_strict_reports_every_problem() {
+        let json = r#"
+{
+  "templates": {
+    "HP": {
+      "base": "fifty",
+      "transforms": [
+        {"type": "clamp", "min": 100.0, "max": 10.0},
+        {
+          "type": "conditional",
+          "condition_stat": "Level",
+          "condition_value": 5.0,
+          "operator": "=>",
+          "then": {"type": "clamp", "min": 3.0, "max": 1.0}
+        }
+      ]
+    },
+    "BossHP": {"extends": "Hp"}
+  },
+  "stats": {
+    "Mana": {"template": "MP"},
+    "Armor": {"base": "{{armor}}", "transforms": [{"type": "additive", "value": "inf"}]}
+  }
+}
+"#;
+        let problems: Vec<String> = StatConfig::from_json(json)
+            .unwrap()
+            .validate_strict()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        let expected = [
+            "stat 'Mana': unknown template 'MP'",
+            "template 'BossHP' extends: unknown template 'Hp'",
+            "stat 'Armor': non-finite value inf",
+            "template 'HP': Invalid number: fifty",
+            "template 'HP': clamp min 100 is greater than max 10",
+            "template 'HP': clamp min 3 is greater than max 1",
+            "Operator error: Invalid operator: =>",
+        ];
+        assert_eq!(problems.len(), expected.len(), "{:#?}", problems);
+        for fragment in expected {
+            assert!(
+                problems.iter().any(|p| p.contains(fragment)),
+                "missing {:?} in {:#?}",
+                fragment,
+                problems
+            );
+        }
+
+        let valid = StatConfig::from_json(&complex_json()).unwrap();
+        assert!(valid.validate_strict().is_empty());
+    }
+
+    #[test]
+    fn test_entities_to_csv() {
+        let mut manager = StatTemplateManager::from_json(&complex_json()).unwrap();
+        let mut resolver = StatResolver::new();
+        manager
+            .load_entity_stats(&mut resolver, complex_entity_configs("knight"))
+            .unwrap();
+        let mut goblin = complex_entity_configs("goblin");
+        goblin.retain(|c| c.stat_type != "ATK");
+        manager.load_entity_stats(&mut resolver, goblin).unwrap();
+
+        let context = StatContext::new();
+        let csv = manager
+            .entities_to_csv(
+                &mut resolver,
+                &["knight", "goblin"],
+                &["Strength", "HP", "ATK"],
+                &context,
+            )
+            .unwrap();
+
+        let rows: Vec<Vec<&str>> = csv.lines().map(|l| l.split(',').collect()).collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], ["entity", "Strength", "HP", "ATK"]);
+        assert_eq!(rows[1][0], "knight");
+        assert_eq!(rows[2][0], "goblin");
+        assert!(rows[1][1..].iter().all(|cell| cell.parse::<f64>().is_ok()));
+        assert_eq!(rows[2][1], rows[1][1]);
+        assert_eq!(rows[2][3], "");
+    }
+
+    #[test]
+    fn test_cloned_manager_is_independent() {
+        let original = StatTemplateManager::from_json(&complex_json()).unwrap();
+        let mut clone = original.clone();
+
+        clone.templates.remove("ComplexHP");
+        clone.templates.get_mut("BaseStrength").unwrap().description = Some("changed".to_string());
+
+        assert!(original.templates.contains_key("ComplexHP"));
+        assert_ne!(
+            original.templates["BaseStrength"].description.as_deref(),
+            Some("changed")
+        );
+        assert!(!clone.templates.contains_key("ComplexHP"));
+    }
+
+    #[test]
+    fn test_timed_additive_transform() {
+        let json = r#"
+{
+  "stats": {
+    "Poison": {
+      "base": 1.0,
+      "transforms": [
+        {"type": "timed_additive", "value": 5.0, "start_tick": 3.0, "end_tick": 8.0, "tick_key": "tick"}
+      ]
+    }
+  }
+}
+"#;
+        let mut resolver = StatLoader::from_json(json).unwrap();
+        let poison = StatId::from_str("Poison");
+
+        let mut at_tick = |tick: f64| {
+            let mut context = StatContext::new();
+            context.set("tick", tick);
+            resolve_in_context(&mut resolver, &poison, &context)
+                .unwrap()
+                .value
+        };
+        assert_eq!(at_tick(2.0), 1.0);
+        assert_eq!(at_tick(3.0), 6.0);
+        assert_eq!(at_tick(7.0), 6.0);
+        assert_eq!(at_tick(8.0), 1.0);
+
+        let no_tick = resolve_in_context(&mut resolver, &poison, &StatContext::new()).unwrap();
+        assert_eq!(no_tick.value, 1.0);
+    }
+
+    #[test]
+    fn test_malformed_placeholders_error_without_panicking() {
+        let garbage = [
+            "{{", "}}", "{{}}", "{{ }}", "{{}", "{}}", "{{{", "}}}}", "{{{{}}}}", "{{é", "é}}",
+            "{{hp", "hp}}", "{", "",
+        ];
+        for value in garbage {
+            let json = format!(
+                r#"{{"stats": {{"HP": {{"sources": [{{"type": "constant", "value": {:?}}}]}}}}}}"#,
+                value
+            );
+            assert!(
+                StatLoader::from_json(&json).is_err(),
+                "{:?} was accepted",
+                value
+            );
+        }
+
+        let truncated = r#"{"stats": {"HP": {"sources": [{"type": "constant", "value": "{{"#;
+        for end in 0..truncated.len() {
+            assert!(StatLoader::from_json(&truncated[..end]).is_err());
+        }
+
+        let err = config::SourceValue::String("{{ }}".to_string())
+            .resolve(&std::collections::HashMap::new())
+            .unwrap_err();
+        assert!(err.contains("Empty parameter placeholder"));
+    }
+
+    #[test]
+    fn test_min_and_max_of_stats_transforms() {
+        let json = r#"
+{
+  "stats": {
+    "Accuracy": {"base": 40.0},
+    "EvasionCap": {"base": 25.0},
+    "Hit": {"base": 10.0, "transforms": [{"type": "min_of_stats", "stats": ["Accuracy", "EvasionCap"]}]},
+    "Crit": {"base": 90.0, "transforms": [{"type": "max_of_stats", "stats": ["Accuracy", "EvasionCap"]}]}
+  }
+}
+"#;
+        let mut resolver = StatLoader::from_json(json).unwrap();
+        let context = StatContext::new();
+        let hit = resolver
+            .resolve(&StatId::from_str("Hit"), &context)
+            .unwrap();
+        assert_eq!(hit.value, 25.0);
+        let crit = resolver
+            .resolve(&StatId::from_str("Crit"), &context)
+            .unwrap();
+        assert_eq!(crit.value, 40.0);
+
+        let empty = json.replace(r#"["Accuracy", "EvasionCap"]}]},"#, r#"[]}]},"#);
+        assert!(StatLoader::from_json(&empty).is_err());
+    }
+
+    #[test]
+    fn test_params_from_str_map_names_bad_key() {
+        use std::collections::HashMap;
+
+        let raw = HashMap::from([
+            ("level".to_string(), "10".to_string()),
+            ("base_hp".to_string(), "100.5".to_string()),
+            ("armor".to_string(), "lots".to_string()),
+        ]);
+        let err = params_from_str_map(&raw).unwrap_err().to_string();
+        assert!(err.contains("'armor'"), "{}", err);
+        assert!(!err.contains("'level'"));
+
+        let mut raw = raw;
+        raw.remove("armor");
+        let params = params_from_str_map(&raw).unwrap();
+        assert_eq!(params["level"], 10.0);
+        assert_eq!(params["base_hp"], 100.5);
+    }
+
+    #[test]
+    fn test_clamp_with_static_min_and_stat_max() {
+        let json = r#"
+{
+  "templates": {
+    "Vitality": {"base": "{{vitality}}"},
+    "MaxHP": {"base": "{{vitality}}", "transforms": [{"type": "multiplicative", "value": 10.0}]},
+    "HP": {
+      "base": "{{hp}}",
+      "transforms": [{"type": "clamp", "min": 1.0, "max": 500.0, "max_stat": "MaxHP"}]
+    }
+  }
+}
+"#;
+        let manager = StatTemplateManager::from_json(json).unwrap();
+        let context = StatContext::new();
+        let hp_for = |hp: f64| {
+            let mut resolver = StatResolver::new();
+            let params = std::collections::HashMap::from([
+                ("vitality".to_string(), 20.0),
+                ("hp".to_string(), hp),
+            ]);
+            for stat in ["Vitality", "MaxHP", "HP"] {
+                manager
+                    .apply_template(&mut resolver, stat, &format!("hero:{}", stat), &params)
+                    .unwrap();
+            }
+            manager
+                .resolve_entity_stat(&mut resolver, "hero", "HP", &context)
+                .unwrap()
+                .value
+        };
+
+        assert_eq!(hp_for(350.0), 200.0); // MaxHP (200) is tighter than the static 500
+        assert_eq!(hp_for(-5.0), 1.0);
+        assert_eq!(hp_for(120.0), 120.0);
+    }
+
+    #[test]
+    fn test_subset_pulls_in_dependencies() {
+        let mut config = StatConfig::from_json(&complex_json()).unwrap();
+        let stats = serde_json::json!({
+            "Strength": {"template": "BaseStrength"},
+            "Vitality": {"template": "BaseVitality"},
+            "Defense": {"template": "ComplexDefense"},
+            "HP": {"template": "ComplexHP"},
+            "ATK": {"template": "ComplexATK"}
+        });
+        config.stats = serde_json::from_value(stats).unwrap();
+
+        let subset = config.subset(&["HP"]).unwrap();
+        let mut stats: Vec<&str> = subset.stats.keys().map(String::as_str).collect();
+        stats.sort();
+        assert_eq!(stats, ["Defense", "HP", "Strength", "Vitality"]);
+        let mut templates: Vec<&str> = subset.templates.keys().map(String::as_str).collect();
+        templates.sort();
+        assert_eq!(
+            templates,
+            [
+                "BaseStrength",
+                "BaseVitality",
+                "ComplexDefense",
+                "ComplexHP"
+            ]
+        );
+
+        assert!(config.subset(&["Mana"]).is_err());
+    }
+
+    #[test]
+    fn test_resolve_audited_warrior_hp() {
+        use std::collections::HashMap;
+
+        let json = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/warrior.json"
+        ))
+        .unwrap();
+        let mut manager = StatTemplateManager::from_json(&json).unwrap();
+        let params: HashMap<String, f64> = [
+            ("level", 10.0),
+            ("base_hp", 100.0),
+            ("hp_per_level", 10.0),
+            ("base_vitality", 10.0),
+            ("vitality_per_level", 2.0),
+            ("base_strength", 15.0),
+            ("strength_per_level", 3.0),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+        let mappings: Vec<_> = ["HP", "Vitality", "Strength"]
+            .into_iter()
+            .map(|stat| (stat.to_string(), format!("Warrior{}", stat), params.clone()))
+            .collect();
+        let configs = StatTemplateManager::entity_params_to_configs("warrior", &mappings);
+        let mut resolver = StatResolver::new();
+        manager.load_entity_stats(&mut resolver, configs).unwrap();
+
+        let (value, record) = manager
+            .resolve_audited(&mut resolver, "warrior", "HP", &StatContext::new())
+            .unwrap();
+
+        assert_eq!(record.value, value);
+        assert_eq!(record.template_name.as_deref(), Some("WarriorHP"));
+        assert!(record.config_version.is_some());
+        assert_eq!(
+            record.sources,
+            [
+                ("Base HP value".to_string(), 100.0),
+                ("Level-based HP scaling".to_string(), 100.0)
+            ]
+        );
+        assert_eq!(record.params["hp_per_level"], 10.0);
+        assert_eq!(record.dependencies["Vitality"], 30.0);
+        assert_eq!(record.dependencies["Strength"], 45.0);
+
+        let bonus = record
+            .transforms
+            .iter()
+            .position(|(label, _)| label.contains("+20% HP bonus"))
+            .expect("x1.2 step recorded");
+        let before = record.transforms[bonus - 1].1;
+        assert!((record.transforms[bonus].1 - before * 1.2).abs() < 1e-9);
+
+        let serialized = serde_json::to_value(&record).unwrap();
+        assert_eq!(serialized["stat_type"], "HP");
+    }
+
+    #[test]
+    fn test_require_explicit_level() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "HP": {"sources": [{"type": "scaling", "base": 100.0, "scale": 10.0}]}
+  }
+}
+"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        let context = StatContext::new();
+        let mut resolver = StatResolver::new();
+        manager
+            .apply_template(&mut resolver, "HP", "hero:HP", &HashMap::new())
+            .unwrap();
+        let hp = manager.resolve_entity_stat(&mut resolver, "hero", "HP", &context);
+        assert_eq!(hp.unwrap().value, 110.0);
+
+        manager.set_require_explicit_level(true);
+        let mut resolver = StatResolver::new();
+        let err = manager
+            .apply_template(&mut resolver, "HP", "hero:HP", &HashMap::new())
+            .unwrap_err();
+        assert!(matches!(err, YamlStatError::MissingDependency(ref p) if p == "level"));
+
+        let params = HashMap::from([("level".to_string(), 5.0)]);
+        manager
+            .apply_template(&mut resolver, "HP", "hero:HP", &params)
+            .unwrap();
+        let hp = manager.resolve_entity_stat(&mut resolver, "hero", "HP", &context);
+        assert_eq!(hp.unwrap().value, 150.0);
+
+        // The config flag reaches the loader, for plain and templated stats alike
+        let json = r#"
+{
+  "require_explicit_level": true,
+  "templates": {"HP": {"sources": [{"type": "scaling", "base": 100.0, "scale": 10.0}]}},
+  "stats": {
+    "MP": {"sources": [{"type": "scaling", "base": 50.0, "scale": 5.0}]},
+    "hero:HP": {"template": "HP"}
+  }
+}
+"#;
+        let Err(err) = load_from_json(json) else {
+            panic!("a missing level should fail the load");
+        };
+        assert!(matches!(err, YamlStatError::MissingDependency(ref p) if p == "level"));
+        let with_level = json.replace(r#""stats""#, r#""constants": {"level": 2.0}, "stats""#);
+        let mut resolver = load_from_json(&with_level).unwrap();
+        let mp = resolver.resolve(&StatId::from_str("MP"), &context).unwrap();
+        assert_eq!(mp.value, 60.0);
+        let hp = resolver
+            .resolve(&StatId::from_str("hero:HP"), &context)
+            .unwrap();
+        assert_eq!(hp.value, 120.0);
+        let manager = StatTemplateManager::from_json(json).unwrap();
+        assert!(
+            manager
+                .apply_template(&mut StatResolver::new(), "HP", "hero:HP", &HashMap::new())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_transform() {
+        let json = r#"
+{
+  "stats": {
+    "HP": {
+      "base": "{{hp}}",
+      "transforms": [{"type": "rate_limit", "max_delta": 10.0, "prev_key": "prev_hp"}]
+    }
+  },
+  "constants": {"hp": 250.0}
+}
+"#;
+        let mut resolver = StatLoader::from_json(json).unwrap();
+        let hp = StatId::from_str("HP");
+
+        let mut with_prev = |prev: f64| {
+            let mut context = StatContext::new();
+            context.set("prev_hp", prev);
+            resolve_in_context(&mut resolver, &hp, &context)
+                .unwrap()
+                .value
+        };
+        assert_eq!(with_prev(100.0), 110.0);
+        assert_eq!(with_prev(400.0), 390.0);
+        assert_eq!(with_prev(245.0), 250.0);
+
+        assert_eq!(
+            resolve_in_context(&mut resolver, &hp, &StatContext::new())
+                .unwrap()
+                .value,
+            250.0
+        );
+    }
+
+    #[test]
+    fn test_panics_become_errors() {
+        use std::collections::HashMap;
+        use zzstat::{StatError, StatTransform};
+
+        struct PanickingTransform;
+
+        impl StatTransform for PanickingTransform {
+            fn depends_on(&self) -> Vec<StatId> {
+                Vec::new()
+            }
+
+            fn apply(
+                &self,
+                _value: f64,
+                _dependencies: &HashMap<StatId, f64>,
+                _context: &StatContext,
+            ) -> Result<f64, StatError> {
+                panic!("transform exploded")
+            }
+
+            fn description(&self) -> String {
+                "PanickingTransform".to_string()
+            }
+        }
+
+        let json = r#"{"stats": {"HP": {"base": 100.0}}}"#;
+        let mut resolver = try_load_from_json(json).unwrap();
+        resolver.register_transform(StatId::from_str("HP"), Box::new(PanickingTransform));
+
+        let err = catch_panics(|| {
+            Ok(resolver
+                .resolve(&StatId::from_str("HP"), &StatContext::new())?
+                .value)
+        })
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: internal panic: transform exploded"
+        );
+
+        assert_eq!(try_resolve_stat_from_json(json, "HP").unwrap().value, 100.0);
+        assert!(try_resolve_stat_from_json("{", "HP").is_err());
+    }
+
+    #[test]
+    fn test_aliases_resolve_to_canonical_stat() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "aliases": {"CriticalChance": "CritChance"},
+  "stats": {
+    "CritChance": {"base": 0.25},
+    "CritDamage": {
+      "base": 1.5,
+      "transforms": [{"type": "map", "dependencies": ["CriticalChance"], "multiplier": 2.0}]
+    }
+  },
+  "templates": {
+    "CritChance": {"base": "{{crit}}"},
+    "Burst": {
+      "base": 10.0,
+      "transforms": [{"type": "map", "dependencies": ["CriticalChance"], "multiplier": 100.0}]
+    },
+    "Echo": {"sources": [{"type": "constant", "value": "@CriticalChance"}]},
+    "Capped": {
+      "base": 1.0,
+      "transforms": [{"type": "clamp", "max": "@CriticalChance"}]
+    }
+  }
+}
+"#;
+        let context = StatContext::new();
+        let mut resolver = StatLoader::from_json(json).unwrap();
+        let old = resolver
+            .resolve(&StatId::from_str("CriticalChance"), &context)
+            .unwrap();
+        assert_eq!(old.value, 0.25);
+        let crit_damage = resolver
+            .resolve(&StatId::from_str("CritDamage"), &context)
+            .unwrap();
+        assert_eq!(crit_damage.value, 2.0);
+
+        let manager = StatTemplateManager::from_json(json).unwrap();
+        let mut resolver = StatResolver::new();
+        let params = HashMap::from([("crit".to_string(), 0.1)]);
+        manager
+            .apply_template(&mut resolver, "CritChance", "hero:CritChance", &params)
+            .unwrap();
+        manager
+            .apply_template(&mut resolver, "Burst", "hero:Burst", &params)
+            .unwrap();
+        let burst = manager.resolve_entity_stat(&mut resolver, "hero", "Burst", &context);
+        assert_eq!(burst.unwrap().value, 20.0);
+        let old = manager.resolve_entity_stat(&mut resolver, "hero", "CriticalChance", &context);
+        assert_eq!(old.unwrap().value, 0.1);
+
+        // `@alias` values in sources and clamp bounds follow the alias too
+        for template in ["Echo", "Capped"] {
+            let mut resolver = StatResolver::new();
+            manager
+                .apply_template(&mut resolver, "CritChance", "hero:CritChance", &params)
+                .unwrap();
+            manager
+                .apply_template(&mut resolver, template, "hero:Crit", &params)
+                .unwrap();
+            let value = resolver
+                .resolve(&StatId::from_str("hero:Crit"), &context)
+                .unwrap()
+                .value;
+            assert_eq!(value, 0.1, "{template}");
+        }
+
+        // Every manager entry point takes the old name, not just resolve_entity_stat
+        let mut resolver = StatResolver::new();
+        manager
+            .apply_template(&mut resolver, "CritChance", "rogue:CriticalChance", &params)
+            .unwrap();
+        manager.add_transform_to_entity(
+            &mut resolver,
+            "rogue",
+            "CriticalChance",
+            Box::new(AdditiveTransform::new(0.05)),
+        );
+        let crit = resolver
+            .resolve(&StatId::from_str("rogue:CritChance"), &context)
+            .unwrap();
+        assert!((crit.value - 0.15).abs() < 1e-9);
+        let (base, value) = manager
+            .resolve_with_base(&mut resolver, "rogue", "CriticalChance", &context)
+            .unwrap();
+        assert_eq!(base, 0.1);
+        assert!((value - 0.15).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_power_budget() {
+        use std::collections::HashMap;
+
+        let mut manager = StatTemplateManager::from_json(&complex_json()).unwrap();
+        let mut resolver = StatResolver::new();
+        manager
+            .load_entity_stats(&mut resolver, complex_entity_configs("knight"))
+            .unwrap();
+
+        let context = StatContext::new();
+        let mut value = |stat: &str| {
+            manager
+                .resolve_entity_stat(&mut resolver, "knight", stat, &context)
+                .unwrap()
+                .value
+        };
+        let expected = value("HP") * 0.5 + value("ATK") * 2.0;
+
+        let weights = HashMap::from([("HP".to_string(), 0.5), ("ATK".to_string(), 2.0)]);
+        let budget = manager
+            .power_budget(&mut resolver, "knight", &weights, &context)
+            .unwrap();
+        assert!((budget - expected).abs() < 1e-9);
+
+        let ignored = HashMap::from([("Missing".to_string(), 0.0)]);
+        let budget = manager
+            .power_budget(&mut resolver, "knight", &ignored, &context)
+            .unwrap();
+        assert_eq!(budget, 0.0);
+    }
+
+    #[test]
+    fn test_template_include_mixins() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "BaseClamp": {"transforms": [{"type": "clamp", "min": 1.0, "max": 100.0}]},
+    "LevelScaling": {
+      "sources": [{"type": "scaling", "base": 0.0, "scale": 5.0, "level": "{{level}}"}]
+    },
+    "Strength": {
+      "base": "{{strength}}",
+      "include": ["LevelScaling", "BaseClamp"],
+      "transforms": [{"type": "additive", "value": 7.0}]
+    },
+    "Loop": {"include": ["Strength", "Loop"]}
+  }
+}
+"#;
+        let config = StatConfig::from_json(json).unwrap();
+        let mut templates = config.templates.clone();
+        templates.remove("Loop");
+        let manager = StatTemplateManager::from_config(StatConfig {
+            templates,
+            ..StatConfig::default()
+        })
+        .unwrap();
+
+        let context = StatContext::new();
+        let strength = |strength: f64, level: f64| {
+            let mut resolver = StatResolver::new();
+            let params = HashMap::from([
+                ("strength".to_string(), strength),
+                ("level".to_string(), level),
+            ]);
+            manager
+                .apply_template(&mut resolver, "Strength", "hero:Strength", &params)
+                .unwrap();
+            manager
+                .resolve_entity_stat(&mut resolver, "hero", "Strength", &context)
+                .unwrap()
+                .value
+        };
+
+        // Mixin transforms run before the template's own: clamp(20 + 5 * 2) + 7
+        assert_eq!(strength(20.0, 2.0), 37.0);
+        // The included clamp caps the pipeline before the +7
+        assert_eq!(strength(90.0, 10.0), 107.0);
+
+        let err = StatTemplateManager::from_config(config).err().unwrap();
+        assert!(err.to_string().contains("cycle"), "{}", err);
+
+        let missing = json.replace(r#"["LevelScaling", "BaseClamp"]"#, r#"["Nope"]"#);
+        let err = StatTemplateManager::from_json(&missing).err().unwrap();
+        assert!(
+            err.to_string().contains("includes unknown template: Nope"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_self_test_expectations() {
+        let json = r#"
+{
+  "stats": {
+    "Vitality": {"base": 10.0, "expect": 10.0},
+    "HP": {
+      "base": 100.0,
+      "transforms": [{"type": "map", "dependencies": ["Vitality"], "multiplier": 4.0}],
+      "expect": 140.0
+    },
+    "Mana": {"base": 50.0}
+  }
+}
+"#;
+        let context = StatContext::new();
+        let config = StatConfig::from_json(json).unwrap();
+        assert!(config.self_test(&context).is_ok());
+
+        let wrong = StatConfig::from_json(&json.replace("140.0", "150.0")).unwrap();
+        let failures = wrong.self_test(&context).unwrap_err();
+        assert_eq!(failures.len(), 1);
+        let message = failures[0].to_string();
+        assert!(
+            message.contains("'HP'")
+                && message.contains("expected 150")
+                && message.contains("got 140")
+        );
+
+        // A stat that fails to resolve only fails its own check
+        let broken = r#"
+{
+  "aliases": {"VIT": "Vitality"},
+  "stats": {
+    "Vitality": {"base": 10.0, "expect": 10.0},
+    "HP": {
+      "base": 100.0,
+      "transforms": [{"type": "map", "dependencies": ["VIT"], "multiplier": 4.0}],
+      "expect": 140.0
+    },
+    "Zero": {"base": 0.0},
+    "Mana": {"base": 50.0, "expect": 50.0},
+    "Broken": {
+      "base": 1.0,
+      "transforms": [{"type": "ratio_scale", "numerator_stat": "Vitality", "denominator_stat": "Zero", "factor": 1.0}],
+      "expect": 1.0
+    }
+  }
+}
+"#;
+        let failures = StatConfig::from_json(broken)
+            .unwrap()
+            .self_test(&context)
+            .unwrap_err();
+        assert_eq!(failures.len(), 1, "{:?}", failures);
+        assert!(
+            failures[0].to_string().contains("'Broken'"),
+            "{}",
+            failures[0]
+        );
+    }
+
+    #[test]
+    fn test_percent_strings() {
+        use config::SourceValue;
+        use std::collections::HashMap;
+
+        let params = HashMap::new();
+        let percent = |s: &str| SourceValue::String(s.to_string()).resolve(&params);
+        assert_eq!(percent("20%"), Ok(0.2));
+        assert_eq!(percent(" 150 % "), Ok(1.5));
+        assert_eq!(percent("0.5"), Ok(0.5));
+        assert!(percent("20%x").is_err());
+        assert!(percent("%").is_err());
+        assert!(percent("x%").is_err());
+        assert!(percent("20%%").is_err());
+        // Only a plain number before `%` is a percentage; expressions are parsed as such
+        assert!(
+            percent("{{base}} * 2%")
+                .unwrap_err()
+                .contains("Invalid percentage")
+        );
+        assert!(
+            percent("2 + 3%")
+                .unwrap_err()
+                .contains("Invalid percentage")
+        );
+
+        let json = r#"{"stats": {"ATK": {"base": 50.0, "transforms": [{"type": "percent_increase", "value": "20%"}]}}}"#;
+        let atk = resolve_stat_from_json(json, "ATK").unwrap();
+        assert!((atk.value - 60.0).abs() < 1e-9);
+        assert!(
+            StatConfig::from_json(json)
+                .unwrap()
+                .validate_strict()
+                .is_empty()
+        );
+
+        // Everywhere else "20%" is the fraction 0.2, so a multiplier of "20%" is ×0.2
+        let multiplier = json.replace("percent_increase", "multiplicative");
+        let atk = resolve_stat_from_json(&multiplier, "ATK").unwrap();
+        assert!((atk.value - 10.0).abs() < 1e-9);
+        assert!(
+            StatConfig::from_json(&multiplier)
+                .unwrap()
+                .validate_strict()
+                .is_empty()
+        );
+        let malformed = json.replace("20%", "20%x");
+        assert!(load_from_json(&malformed).is_err());
+        assert_eq!(
+            StatConfig::from_json(&malformed)
+                .unwrap()
+                .validate_strict()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_repeated_builds_resolve_identically() {
+        let json = r#"
+{
+  "stats": {
+    "Strength": {"base": 12.5, "transforms": [{"type": "multiplicative", "value": 1.1}]},
+    "Vitality": {"base": 9.0},
+    "Defense": {
+      "base": 3.0,
+      "transforms": [
+        {"type": "map", "dependencies": ["Strength", "Vitality"], "multiplier": 0.3},
+        {"type": "round", "decimals": 2}
+      ]
+    },
+    "HP": {
+      "base": 100.0,
+      "transforms": [
+        {"type": "map", "dependencies": ["Vitality", "Defense"], "multiplier": 4.0},
+        {"type": "clamp", "min": 1.0, "max": 250.0}
+      ]
+    }
+  }
+}
+"#;
+        let stat_ids: Vec<StatId> = ["Strength", "Vitality", "Defense", "HP"]
+            .into_iter()
+            .map(StatId::from_str)
+            .collect();
+        let build = || {
+            let mut resolver = StatLoader::from_json(json).unwrap();
+            stat_ids
+                .iter()
+                .map(|id| {
+                    let resolved = resolver.resolve(id, &StatContext::new()).unwrap();
+                    (resolved.value, resolved.sources, resolved.transforms)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let first = build();
+        for _ in 0..20 {
+            assert_eq!(build(), first);
+        }
+    }
+
+    #[test]
+    fn test_table_lookup_transform() {
+        let json = r#"
+{
+  "stats": {
+    "SpellDamage": {
+      "base": 100.0,
+      "transforms": [{
+        "type": "table_lookup",
+        "key_param": "element",
+        "table": {"fire": 1.2, "ice": "{{ice_factor}}"},
+        "default": 0.5
+      }]
+    }
+  },
+  "constants": {"ice_factor": 0.8}
+}
+"#;
+        let mut resolver = StatLoader::from_json(json).unwrap();
+        let spell = StatId::from_str("SpellDamage");
+        let mut with_element = |element: &str| {
+            let mut context = StatContext::new();
+            context.set("element", element.to_string());
+            resolve_in_context(&mut resolver, &spell, &context)
+                .unwrap()
+                .value
+        };
+
+        assert!((with_element("fire") - 120.0).abs() < 1e-9);
+        assert!((with_element("ice") - 80.0).abs() < 1e-9);
+        assert_eq!(with_element("poison"), 50.0);
+    }
+
+    #[test]
+    fn test_resolved_sources() {
+        use std::collections::HashMap;
+
+        let json = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/basic_stats.json"
+        ))
+        .unwrap();
+        let config = StatConfig::from_json(&json).unwrap();
+
+        let sources = config.resolved_sources("HP", &HashMap::new()).unwrap();
+        assert_eq!(
+            sources[0],
+            (Some("Base HP value".to_string()), 100.0),
+            "names are kept in declaration order"
+        );
+        let total: f64 = sources.iter().map(|(_, value)| value).sum();
+        assert_eq!(total, 200.0);
+
+        assert!(config.resolved_sources("Nope", &HashMap::new()).is_err());
+
+        // Templated stats list the template's base and sources, then their own
+        let json = r#"
+{
+  "templates": {
+    "Base": {"base": 10.0, "sources": [{"type": "constant", "value": "{{bonus}}", "name": "Bonus"}]},
+    "Hero": {"extends": "Base", "params": {"bonus": 1.0}}
+  },
+  "stats": {
+    "hero:HP": {"template": "Hero", "params": {"bonus": 5.0}, "sources": [{"type": "constant", "value": 2.0, "name": "Gear"}]},
+    "hero:MP": {"template": "Missing"}
+  }
+}
+"#;
+        let config = StatConfig::from_json(json).unwrap();
+        assert_eq!(
+            config.resolved_sources("hero:HP", &HashMap::new()).unwrap(),
+            [
+                (None, 10.0),
+                (Some("Bonus".to_string()), 5.0),
+                (Some("Gear".to_string()), 2.0)
+            ]
+        );
+        let params = HashMap::from([("bonus".to_string(), 7.0)]);
+        assert_eq!(
+            config.resolved_sources("hero:HP", &params).unwrap()[1].1,
+            7.0
+        );
+        assert!(config.resolved_sources("hero:MP", &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_missing_param_policy() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "hp": {
+      "sources": [
+        {"type": "constant", "value": 100.0},
+        {"type": "scaling", "base": "{{gear_base}}", "scale": 10.0, "level": 2.0}
+      ]
+    }
+  }
+}
+"#;
+        let apply = |policy: MissingParamPolicy| {
+            let mut manager = StatTemplateManager::from_json(json).unwrap();
+            manager.set_missing_param_policy(policy);
+            let mut resolver = StatResolver::new();
+            manager
+                .apply_template(&mut resolver, "hp", "HP", &HashMap::new())
+                .map(|()| {
+                    resolver
+                        .resolve(&StatId::from_str("HP"), &StatContext::new())
+                        .unwrap()
+                        .value
+                })
+        };
+
+        assert!(apply(MissingParamPolicy::Error).is_err());
+        assert_eq!(apply(MissingParamPolicy::Zero).unwrap(), 120.0);
+        assert_eq!(apply(MissingParamPolicy::Skip).unwrap(), 100.0);
+    }
+
+    #[test]
+    fn test_ratio_scale_transform() {
+        let json = r#"
+{
+  "stats": {
+    "ATK": {"base": 100.0},
+    "Defense": {"base": 50.0},
+    "Damage": {
+      "base": 10.0,
+      "transforms": [{
+        "type": "ratio_scale",
+        "numerator_stat": "ATK",
+        "denominator_stat": "Defense",
+        "factor": 0.1
+      }]
+    }
+  }
+}
+"#;
+        let context = StatContext::new();
+        let damage = StatId::from_str("Damage");
+
+        let mut resolver = StatLoader::from_json(json).unwrap();
+        let resolved = resolver.resolve(&damage, &context).unwrap();
+        assert!((resolved.value - 12.0).abs() < 1e-9);
+
+        let zero_defense = json.replace(r#""base": 50.0"#, r#""base": 0.0"#);
+        let mut resolver = StatLoader::from_json(&zero_defense).unwrap();
+        assert!(resolver.resolve(&damage, &context).is_err());
+    }
+
+    #[test]
+    fn test_lint_flags_always_active_clamp() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "stats": {
+    "Speed": {
+      "base": "{{base_speed}}",
+      "transforms": [
+        {"type": "multiplicative", "value": 2.0},
+        {"type": "clamp", "min": 0.0, "max": 100.0}
+      ]
+    },
+    "Armor": {
+      "base": "{{base_armor}}",
+      "transforms": [{"type": "clamp", "min": 0.0, "max": 500.0}]
+    }
+  }
+}
+"#;
+        let config = StatConfig::from_json(json).unwrap();
+        let params = HashMap::from([
+            ("base_speed".to_string(), 80.0),
+            ("base_armor".to_string(), 120.0),
+        ]);
+
+        let warnings = config.lint(&params);
+        assert_eq!(warnings.len(), 1, "{:?}", warnings);
+        assert!(warnings[0].starts_with("stat 'Speed'"));
+        assert!(warnings[0].contains("above the clamp max 100"));
+    }
+
+    #[test]
+    fn test_empty_stat_name_fails_to_parse() {
+        use config::TransformConfig;
+
+        let json = r#"{"stats": {"HP": {"base": 1.0, "transforms": [{"type": "map", "dependencies": [""]}]}}}"#;
+
+        let err = StatConfig::from_json(json).unwrap_err().to_string();
+        assert!(
+            err.contains("stat name must not be empty (check `dependencies`)"),
+            "{}",
+            err
+        );
+
+        // Every stat-referencing field is checked, and the error names it
+        for (transform, field) in [
+            (r#"{"type": "floor_to_stat", "stat": ""}"#, "stat"),
+            (r#"{"type": "ceil_to_stat", "stat": " "}"#, "stat"),
+            (r#"{"type": "min_of_stats", "stats": ["ATK", ""]}"#, "stats"),
+            (r#"{"type": "max_of_stats", "stats": [""]}"#, "stats"),
+            (
+                r#"{"type": "weighted_average", "terms": [["", 1.0]]}"#,
+                "terms",
+            ),
+            (
+                r#"{"type": "excess_of", "stat": "", "threshold": 1.0, "factor": 1.0}"#,
+                "stat",
+            ),
+            (
+                r#"{"type": "ratio_scale", "numerator_stat": "ATK", "denominator_stat": "", "factor": 1.0}"#,
+                "denominator_stat",
+            ),
+            (r#"{"type": "clamp", "max_stat": ""}"#, "max_stat"),
+        ] {
+            let err = serde_json::from_str::<TransformConfig>(transform)
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains(&format!("(check `{}`)", field)), "{}", err);
+        }
+        let percentage = r#"{"type": "percentage", "of": "", "percent": 20.0}"#;
+        let err = serde_json::from_str::<config::SourceConfig>(percentage)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("(check `of`)"), "{}", err);
+
+        let config = StatConfig::from_json(&json.replace(r#"[""]"#, r#"["Vitality"]"#)).unwrap();
+        let round_trip = serde_json::to_string(&config).unwrap();
+        assert!(round_trip.contains(r#""dependencies":["Vitality"]"#));
+
+        // The fields stay plain strings; only deserialization checks them
+        let TransformConfig::Map { dependencies, .. } = &config.stats["HP"].transforms[0] else {
+            panic!("expected a map transform");
+        };
+        let dependencies: &Vec<String> = dependencies;
+        assert_eq!(dependencies, &["Vitality"]);
+        let blank_condition = r#"{"type": "conditional", "condition_stat": " ", "condition_value": 1.0, "operator": ">", "then": {"type": "additive", "value": 1.0}}"#;
+        assert!(serde_json::from_str::<TransformConfig>(blank_condition).is_err());
+    }
+
+    #[test]
+    fn test_collect_resolved_across_managers() {
+        use std::collections::HashMap;
+
+        let combat =
+            StatTemplateManager::from_json(r#"{"templates": {"ATK": {"base": "{{atk}}"}}}"#)
+                .unwrap();
+        let movement = StatTemplateManager::from_json(
+            r#"{"templates": {"Speed": {"base": 5.0, "transforms": [{"type": "multiplicative", "value": 1.5}]}}}"#,
+        )
+        .unwrap();
+
+        let mut resolver = StatResolver::new();
+        let atk_params = HashMap::from([("atk".to_string(), 40.0)]);
+        combat
+            .apply_template(&mut resolver, "ATK", "hero:ATK", &atk_params)
+            .unwrap();
+        movement
+            .apply_template(&mut resolver, "Speed", "hero:Speed", &HashMap::new())
+            .unwrap();
+
+        let ids = [StatId::from_str("hero:ATK"), StatId::from_str("hero:Speed")];
+        let values = collect_resolved(&mut resolver, &ids, &StatContext::new()).unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values["hero:ATK"], 40.0);
+        assert_eq!(values["hero:Speed"], 7.5);
+
+        let missing = [StatId::from_str("hero:Mana")];
+        assert!(collect_resolved(&mut resolver, &missing, &StatContext::new()).is_err());
+    }
+
+    #[test]
+    fn test_to_markdown_warrior() {
+        let json = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/warrior.json"
+        ))
+        .unwrap();
+        let config = StatConfig::from_json(&json).unwrap();
+
+        let markdown = config.to_markdown();
+        for name in config.templates.keys() {
+            assert!(markdown.contains(&format!("\n## {}\n", name)), "{}", name);
+        }
+        assert!(markdown.contains(
+            "Formula: `clamp(({{base_hp}} + {{hp_per_level}} × {{level}} + Vitality × 3 + Strength × 2) × 1.2, 100, ∞)`"
+        ), "{}", markdown);
+        assert!(markdown.contains("Parameters: `base_hp`, `hp_per_level`, `level`"));
+    }
+
+    #[test]
+    fn test_conditional_stat_selects_dependency() {
+        let json = r#"
+{
+  "stats": {
+    "Stance": {"base": 1.0},
+    "AggressiveBonus": {"base": 30.0},
+    "DefensiveBonus": {"base": 5.0},
+    "ATK": {
+      "base": 100.0,
+      "transforms": [{
+        "type": "conditional_stat",
+        "condition_stat": "Stance",
+        "operator": "==",
+        "condition_value": 1.0,
+        "then_stat": "AggressiveBonus",
+        "else_stat": "DefensiveBonus"
+      }]
+    }
+  }
+}
+"#;
+        let atk = StatId::from_str("ATK");
+        let context = StatContext::new();
+
+        let mut aggressive = StatLoader::from_json(json).unwrap();
+        assert_eq!(aggressive.resolve(&atk, &context).unwrap().value, 130.0);
+
+        let defensive_json =
+            json.replace(r#""Stance": {"base": 1.0}"#, r#""Stance": {"base": 0.0}"#);
+        let mut defensive = StatLoader::from_json(&defensive_json).unwrap();
+        assert_eq!(defensive.resolve(&atk, &context).unwrap().value, 105.0);
+    }
+
+    #[test]
+    fn test_mutable_transform_handle() {
+        use std::collections::HashMap;
+
+        let manager =
+            StatTemplateManager::from_json(r#"{"templates": {"ATK": {"base": 50.0}}}"#).unwrap();
+        let mut resolver = StatResolver::new();
+        manager
+            .apply_template(&mut resolver, "ATK", "hero:ATK", &HashMap::new())
+            .unwrap();
+        let rage = manager.add_mutable_transform_to_entity(&mut resolver, "hero", "ATK", 5.0);
+
+        let atk = StatId::from_str("hero:ATK");
+        let context = StatContext::new();
+        assert_eq!(resolver.resolve(&atk, &context).unwrap().value, 55.0);
+
+        rage.set(&mut resolver, 20.0);
+        assert_eq!(rage.get(), 20.0);
+        let resolved = resolver.resolve(&atk, &context).unwrap();
+        assert_eq!(resolved.value, 70.0);
+        assert_eq!(resolved.transforms.len(), 1, "no re-registration");
+    }
+
+    #[test]
+    fn test_from_json_with_vars() {
+        use std::collections::HashMap;
+
+        let json = r#"{"stats": {"Level": {"base": 75.0, "transforms": [{"type": "clamp", "max": ${LEVEL_CAP}}]}}}"#;
+        let vars = HashMap::from([("LEVEL_CAP".to_string(), "60".to_string())]);
+
+        let mut resolver = StatLoader::from_json_with_vars(json, &vars).unwrap();
+        let level = resolver
+            .resolve(&StatId::from_str("Level"), &StatContext::new())
+            .unwrap();
+        assert_eq!(level.value, 60.0);
+
+        let err = StatLoader::from_json_with_vars(json, &HashMap::new())
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("Unresolved variable: ${LEVEL_CAP}"), "{}", err);
+
+        let unterminated = format!("{{\n  \"max\": ${{LEVEL_CAP\n{}", "0".repeat(1000));
+        let err = StatLoader::from_json_with_vars(&unterminated, &vars)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(
+            err.contains("Unterminated variable at line 2 (byte 11): ${LEVEL_CAP"),
+            "{}",
+            err
+        );
+        assert!(err.len() < 120, "{}", err);
+    }
+
+    #[test]
+    fn test_literal_template_sources_resolved_once() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "Armor": {
+      "base": "10",
+      "sources": [{"type": "scaling", "base": 0.0, "scale": "1 / 2", "level": 4.0}]
+    },
+    "HP": {"base": "{{base_hp}}"}
+  }
+}
+"#;
+        let manager = StatTemplateManager::from_json(json).unwrap();
+        assert_eq!(manager.literal_sources["Armor"], vec![10.0, 2.0]);
+        assert!(!manager.literal_sources.contains_key("HP"));
+
+        let parses = || config::STRING_RESOLVES.with(std::cell::Cell::get);
+        let applications = 100;
+        let mut resolver = StatResolver::new();
+        let params = HashMap::from([("base_hp".to_string(), 50.0)]);
+        let mut apply = |template: &str| {
+            let before = parses();
+            for i in 0..applications {
+                manager
+                    .apply_template(
+                        &mut resolver,
+                        template,
+                        &format!("e{}:{}", i, template),
+                        &params,
+                    )
+                    .unwrap();
+            }
+            parses() - before
+        };
+
+        // Literal sources were parsed once by from_json; a param source is parsed every time
+        assert_eq!(apply("Armor"), 0);
+        assert!(apply("HP") >= applications);
+
+        let context = StatContext::new();
+        let armor = resolver.resolve(&StatId::from_str("e99:Armor"), &context);
+        assert_eq!(armor.unwrap().value, 12.0);
+        let hp = resolver.resolve(&StatId::from_str("e99:HP"), &context);
+        assert_eq!(hp.unwrap().value, 50.0);
+    }
+
+    #[test]
+    fn test_remap_transform() {
+        let remap = |value: f64, clamp: bool| {
+            let json = format!(
+                r#"{{"stats": {{"Morale": {{"base": {}, "transforms": [
+                    {{"type": "remap", "in_min": 0.0, "in_max": 1.0, "out_min": 100.0, "out_max": 200.0, "clamp": {}}}
+                ]}}}}}}"#,
+                value, clamp
+            );
+            resolve_stat_from_json(&json, "Morale").unwrap().value
+        };
+
+        assert_eq!(remap(0.5, false), 150.0);
+        assert_eq!(remap(1.5, false), 250.0);
+        assert_eq!(remap(1.5, true), 200.0);
+
+        let empty_range = r#"{"stats": {"Morale": {"base": 0.5, "transforms": [
+            {"type": "remap", "in_min": 1.0, "in_max": 1.0, "out_min": 100.0, "out_max": 200.0}
+        ]}}}"#;
+        assert!(load_from_json(empty_range).is_err());
+    }
+
+    #[test]
+    fn test_check_no_nan() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "stats": {
+    "ATK": {"base": 100.0},
+    "Defense": {"base": "{{defense}}"},
+    "Pierce": {
+      "base": 1.0,
+      "transforms": [{"type": "ratio_scale", "numerator_stat": "ATK", "denominator_stat": "Defense", "factor": 0.1}]
+    },
+    "Burst": {"base": "{{burst}}", "transforms": [{"type": "multiplicative", "value": 1e10}]}
+  }
+}
+"#;
+        let config = StatConfig::from_json(json).unwrap();
+        let params = |defense: f64, burst: f64| {
+            HashMap::from([
+                ("defense".to_string(), defense),
+                ("burst".to_string(), burst),
+            ])
+        };
+        let context = StatContext::new();
+
+        assert!(config.check_no_nan(&[params(50.0, 1.0)], &context).is_ok());
+
+        let failures = config
+            .check_no_nan(&[params(0.0, 1.0), params(50.0, 1e308)], &context)
+            .unwrap_err();
+        // Only the stat that divides by zero fails, not every stat under those params
+        assert_eq!(failures.len(), 2, "{:?}", failures);
+        assert!(
+            failures[0]
+                .to_string()
+                .contains("stat 'Pierce' with params {burst=1, defense=0}"),
+            "{}",
+            failures[0]
+        );
+        assert!(failures[1].to_string().contains("stat 'Burst' is inf"));
+    }
+
+    #[test]
+    fn test_entities_matching_hierarchical_ids() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "Vitality": {"base": "{{vit}}"},
+    "HP": {"base": 100.0, "transforms": [{"type": "map", "dependencies": ["Vitality"], "multiplier": 10.0}]}
+  }
+}
+"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        let mut resolver = StatResolver::new();
+        let stat = |entity_id: &str, stat_type: &str, vit: f64| EntityStatConfig {
+            entity_id: entity_id.to_string(),
+            stat_type: stat_type.to_string(),
+            template_name: stat_type.to_string(),
+            params: HashMap::from([("vit".to_string(), vit)]),
+        };
+        let mut configs = Vec::new();
+        for (entity_id, vit) in [
+            ("guild1:player1", 1.0),
+            ("guild1:player3", 3.0),
+            ("guild10:player2", 2.0),
+            ("solo", 4.0),
+        ] {
+            configs.push(stat(entity_id, "Vitality", vit));
+            configs.push(stat(entity_id, "HP", vit));
+        }
+        manager.load_entity_stats(&mut resolver, configs).unwrap();
+
+        assert_eq!(
+            manager.entities_matching("guild1:"),
+            vec!["guild1:player1", "guild1:player3"]
+        );
+        assert_eq!(manager.entities_matching("guild1").len(), 3);
+        assert_eq!(manager.entities_matching("").len(), 4);
+
+        // Dependencies resolve within the full multi-segment entity ID
+        let hp = manager
+            .resolve_entity_stat(&mut resolver, "guild1:player3", "HP", &StatContext::new())
+            .unwrap();
+        assert_eq!(hp.value, 130.0);
+    }
+
+    #[test]
+    fn test_excess_of_transform() {
+        let json = r#"
+{
+  "stats": {
+    "Strength": {"base": 70.0},
+    "Agility": {"base": 30.0},
+    "Crit": {
+      "base": 5.0,
+      "transforms": [
+        {"type": "excess_of", "stat": "Strength", "threshold": 50.0, "factor": 0.5},
+        {"type": "excess_of", "stat": "Agility", "threshold": 50.0, "factor": 0.5}
+      ]
+    }
+  }
+}
+"#;
+        let crit = resolve_stat_from_json(json, "Crit").unwrap();
+        assert_eq!(crit.value, 15.0);
+    }
+
+    #[test]
+    fn test_as_conditional_view() {
+        let json = r#"
+{
+  "stats": {
+    "HP": {
+      "base": 100.0,
+      "transforms": [
+        {"type": "additive", "value": 5.0},
+        {
+          "type": "conditional",
+          "condition_stat": "Vitality",
+          "condition_value": 20.0,
+          "operator": ">=",
+          "then": {"type": "multiplicative", "value": 1.2}
+        }
+      ]
+    }
+  }
+}
+"#;
+        let config = StatConfig::from_json(json).unwrap();
+        let transforms = &config.stats["HP"].transforms;
+
+        assert!(transforms[0].as_conditional().is_none());
+        let view = transforms[1].as_conditional().unwrap();
+        assert_eq!(view.condition_stat, Some("Vitality"));
+        assert_eq!(view.operator, ">=");
+        assert_eq!(view.condition_value, Some(20.0));
+        assert_eq!(view.then_kind(), "multiplicative");
+        assert_eq!(view.else_kind(), None);
+    }
+
+    #[test]
+    fn test_subtractive_transform() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "constants": {"armor_penalty": 15.0},
+  "stats": {
+    "Speed": {"base": 100.0, "transforms": [{"type": "subtractive", "value": "{{armor_penalty}}"}]}
+  },
+  "templates": {
+    "Evasion": {"base": 40.0, "transforms": [{"type": "subtractive", "value": "{{penalty}}"}]}
+  }
+}
+"#;
+        assert_eq!(resolve_stat_from_json(json, "Speed").unwrap().value, 85.0);
+
+        let params = HashMap::from([("penalty".to_string(), 10.0)]);
+        let mut resolver = create_entity_stats(json, "hero", "Evasion", &params).unwrap();
+        let evasion = resolver
+            .resolve(&StatId::from_str("hero"), &StatContext::new())
+            .unwrap();
+        assert_eq!(evasion.value, 30.0);
+    }
+
+    #[test]
+    fn test_resolve_with_extra_transforms() {
+        use config::{SourceValue, TransformConfig};
+
+        let json = r#"{"stats": {"HP": {"base": 100.0, "transforms": [{"type": "multiplicative", "value": 2.0}]}}}"#;
+        let context = StatContext::new();
+        let event_bonus = TransformConfig::Additive {
+            value: SourceValue::Number(50.0),
+            name: Some("Festival bonus".to_string()),
+        };
+
+        let boosted =
+            StatLoader::resolve_with_extra(json, "HP", vec![event_bonus], &context).unwrap();
+        assert_eq!(boosted.value, 250.0);
+        assert_eq!(boosted.transforms.last().unwrap().0, "Festival bonus");
+
+        let plain = StatLoader::resolve_with_extra(json, "HP", Vec::new(), &context).unwrap();
+        assert_eq!(plain.value, 200.0);
+        assert!(StatLoader::resolve_with_extra(json, "MP", Vec::new(), &context).is_err());
+    }
+
+    #[test]
+    fn test_divide_transform() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "constants": {"divisor": 3.0},
+  "stats": {"Damage": {"base": 90.0, "transforms": [{"type": "divide", "value": "{{divisor}}"}]}},
+  "templates": {"Reduced": {"base": 90.0, "transforms": [{"type": "divide", "value": "{{divisor}}"}]}}
+}
+"#;
+        assert_eq!(resolve_stat_from_json(json, "Damage").unwrap().value, 30.0);
+
+        let zero = HashMap::from([("divisor".to_string(), 0.0)]);
+        let err = create_entity_stats(json, "hero", "Reduced", &zero)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("divisor is zero"), "{}", err);
+        assert!(load_from_json(&json.replace("\"divisor\": 3.0", "\"divisor\": 0.0")).is_err());
+    }
+
+    #[test]
+    fn test_apply_equipment_stacking() {
+        use std::collections::HashMap;
+
+        let manager =
+            StatTemplateManager::from_json(r#"{"templates": {"ATK": {"base": 100.0}}}"#).unwrap();
+        let ring = EquipBonus {
+            kind: BonusKind::Percent,
+            value: 0.1,
+        };
+        let context = StatContext::new();
+        let atk = StatId::from_str("hero:ATK");
+
+        let resolve = |bonuses: &[EquipBonus], rule| {
+            let mut resolver = StatResolver::new();
+            manager
+                .apply_template(&mut resolver, "ATK", "hero:ATK", &HashMap::new())
+                .unwrap();
+            manager.apply_equipment(&mut resolver, "hero", "ATK", bonuses, rule);
+            resolver.resolve(&atk, &context).unwrap().value
+        };
+
+        assert!((resolve(&[ring; 3], StackRule::Additive) - 130.0).abs() < 1e-9);
+        assert!((resolve(&[ring; 3], StackRule::Multiplicative) - 133.1).abs() < 1e-9);
+
+        let sword = EquipBonus {
+            kind: BonusKind::Flat,
+            value: 20.0,
+        };
+        assert!((resolve(&[sword, ring, ring], StackRule::Additive) - 144.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_power_transform() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "stats": {"Resist": {"base": 16.0, "transforms": [{"type": "power", "exponent": 0.5}]}},
+  "templates": {
+    "Curve": {"base": "{{base}}", "transforms": [{"type": "power", "exponent": "{{exp}}"}]}
+  }
+}
+"#;
+        assert_eq!(resolve_stat_from_json(json, "Resist").unwrap().value, 4.0);
+
+        let context = StatContext::new();
+        let resolve = |base: f64, exp: f64| {
+            let params = HashMap::from([("base".to_string(), base), ("exp".to_string(), exp)]);
+            let mut resolver = create_entity_stats(json, "hero:Curve", "Curve", &params).unwrap();
+            resolver.resolve(&StatId::from_str("hero:Curve"), &context)
+        };
+        assert_eq!(resolve(3.0, 2.0).unwrap().value, 9.0);
+        assert_eq!(resolve(-2.0, 1.0).unwrap().value, -2.0);
+        assert_eq!(resolve(-2.0, 3.0).unwrap().value, -8.0);
+        let err = resolve(-4.0, 0.5).unwrap_err();
+        assert!(err.to_string().contains("fractional exponent"), "{}", err);
+        // The error names the stat being resolved
+        assert!(
+            matches!(&err, zzstat::StatError::InvalidTransform(id, _) if id.as_str() == "hero:Curve"),
+            "{:?}",
+            err
+        );
+        let negative = json.replace("\"base\": 16.0", "\"base\": -16.0");
+        let err = resolve_stat_from_json(&negative, "Resist").unwrap_err();
+        assert!(err.to_string().contains("stat Resist"), "{}", err);
+    }
+
+    #[test]
+    fn test_marginal_param_value() {
+        use std::collections::HashMap;
+
+        let json =
+            std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/archer.json"))
+                .unwrap();
+        let manager = StatTemplateManager::from_json(&json).unwrap();
+        let build = |params: &HashMap<String, f64>| {
+            let mut resolver = StatResolver::new();
+            for (template, stat) in [
+                ("ArcherVitality", "Vitality"),
+                ("ArcherDexterity", "Dexterity"),
+                ("ArcherHP", "HP"),
+            ] {
+                let stat_id = StatTemplateManager::entity_stat_id("archer", stat);
+                manager.apply_template(&mut resolver, template, &stat_id, params)?;
+            }
+            Ok(resolver)
+        };
+        let params: HashMap<String, f64> = [
+            ("level", 1.0),
+            ("base_hp", 100.0),
+            ("hp_per_level", 10.0),
+            ("base_vitality", 10.0),
+            ("vitality_per_level", 2.0),
+            ("base_dexterity", 10.0),
+            ("dexterity_per_level", 2.0),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+        let context = StatContext::new();
+
+        let marginal = manager
+            .marginal(
+                build,
+                "archer",
+                "HP",
+                "vitality_per_level",
+                &params,
+                1.0,
+                &context,
+            )
+            .unwrap();
+        assert!(marginal > 0.0);
+        assert!((marginal - 3.0).abs() < 1e-9, "{}", marginal);
+
+        assert!(
+            manager
+                .marginal(build, "archer", "HP", "unknown", &params, 1.0, &context)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_min_max_transforms() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "stats": {
+    "Floored": {"base": -5.0, "transforms": [{"type": "min", "value": 1.0}]},
+    "Capped": {"base": 150.0, "transforms": [{"type": "max", "value": 100.0}]},
+    "Untouched": {"base": 50.0, "transforms": [{"type": "min", "value": 1.0}, {"type": "max", "value": 100.0}]}
+  },
+  "templates": {"Crit": {"base": 0.9, "transforms": [{"type": "max", "value": "{{cap}}"}]}}
+}
+"#;
+        assert_eq!(resolve_stat_from_json(json, "Floored").unwrap().value, 1.0);
+        assert_eq!(resolve_stat_from_json(json, "Capped").unwrap().value, 100.0);
+        assert_eq!(
+            resolve_stat_from_json(json, "Untouched").unwrap().value,
+            50.0
+        );
+
+        let params = HashMap::from([("cap".to_string(), 0.75)]);
+        let mut resolver = create_entity_stats(json, "hero:Crit", "Crit", &params).unwrap();
+        let crit = resolver
+            .resolve(&StatId::from_str("hero:Crit"), &StatContext::new())
+            .unwrap();
+        assert_eq!(crit.value, 0.75);
+    }
+
+    #[test]
+    fn test_multiplicative_as_additive() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "multiplicative_as_additive": false,
+  "constants": {"ring": 1.1},
+  "stats": {"ATK": {"base": 100.0, "transforms": [
+    {"type": "multiplicative", "value": 1.1},
+    {"type": "additive", "value": 10.0},
+    {"type": "multiplicative", "value": "{{ring}}"}
+  ]}},
+  "templates": {"DEF": {"base": 100.0, "transforms": [
+    {"type": "multiplicative", "value": 1.1},
+    {"type": "multiplicative", "value": 1.1}
+  ]}}
+}
+"#;
+        let compounded = resolve_stat_from_json(json, "ATK").unwrap().value;
+        assert!((compounded - 132.0).abs() < 1e-9, "{}", compounded);
+
+        let json = json.replace(
+            "\"multiplicative_as_additive\": false",
+            "\"multiplicative_as_additive\": true",
+        );
+        let additive = resolve_stat_from_json(&json, "ATK").unwrap();
+        assert!((additive.value - 130.0).abs() < 1e-9, "{}", additive.value);
+        assert_eq!(additive.transforms.len(), 2);
+
+        let mut resolver = create_entity_stats(&json, "hero:DEF", "DEF", &HashMap::new()).unwrap();
+        let def = resolver
+            .resolve(&StatId::from_str("hero:DEF"), &StatContext::new())
+            .unwrap();
+        assert!((def.value - 120.0).abs() < 1e-9, "{}", def.value);
+    }
+
+    #[test]
+    fn test_stat_pipeline_order() {
+        let json = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/basic_stats.json"
+        ))
+        .unwrap();
+        let config = StatConfig::from_json(&json).unwrap();
+
+        let pipeline = config.pipeline("ATK");
+        let phases: Vec<&str> = pipeline.iter().map(|(phase, _)| phase.as_str()).collect();
+        assert_eq!(
+            phases,
+            ["source", "source", "additive", "multiplicative", "clamp"]
+        );
+        assert_eq!(pipeline[2].1, "+10 ATK from weapon");
+        assert!(config.pipeline("Missing").is_empty());
+
+        let unnamed = StatConfig::from_json(
+            r#"{"stats": {"X": {"base": 5.0, "transforms": [{"type": "multiplicative", "value": 2.0}]}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            unnamed.pipeline("X"),
+            [
+                ("source".to_string(), "5".to_string()),
+                ("multiplicative".to_string(), "x × 2".to_string()),
+            ]
+        );
+
+        // Templated stats list the template's steps, then their own, then the defaults
+        let templated = StatConfig::from_json(
+            r#"{
+  "default_transforms": [{"type": "clamp", "min": 0.0}],
+  "templates": {"Doubled": {"base": "{{base}}", "transforms": [{"type": "multiplicative", "value": 2.0}]}},
+  "stats": {"ATK": {"template": "Doubled", "params": {"base": 40.0}, "transforms": [{"type": "additive", "value": 30.0}]}}
+}"#,
+        )
+        .unwrap();
+        let phases: Vec<String> = templated
+            .pipeline("ATK")
+            .into_iter()
+            .map(|(phase, _)| phase)
+            .collect();
+        assert_eq!(
+            phases,
+            ["template", "source", "multiplicative", "additive", "clamp"]
+        );
+    }
+
+    #[test]
+    fn test_round_modes() {
+        let json = r#"
+{
+  "stats": {
+    "Nearest": {"base": 12.5, "transforms": [{"type": "round"}]},
+    "Floor": {"base": 12.7, "transforms": [{"type": "round", "mode": "floor"}]},
+    "Ceil": {"base": 12.2, "transforms": [{"type": "round", "mode": "ceil"}]},
+    "CeilTenths": {"base": 1.21, "transforms": [{"type": "round", "decimals": 1, "mode": "ceil"}]},
+    "FloorExact": {"base": 0.29, "transforms": [{"type": "round", "decimals": 2, "mode": "floor"}]},
+    "CeilExact": {"base": 0.07, "transforms": [{"type": "round", "decimals": 2, "mode": "ceil"}]}
+  }
+}
+"#;
+        assert_eq!(resolve_stat_from_json(json, "Nearest").unwrap().value, 13.0);
+        assert_eq!(resolve_stat_from_json(json, "Floor").unwrap().value, 12.0);
+        assert_eq!(resolve_stat_from_json(json, "Ceil").unwrap().value, 13.0);
+        assert_eq!(
+            resolve_stat_from_json(json, "CeilTenths").unwrap().value,
+            1.3
+        );
+        // 0.29 * 100 and 0.07 * 100 carry float error that must not move the result
+        assert_eq!(
+            resolve_stat_from_json(json, "FloorExact").unwrap().value,
+            0.29
+        );
+        assert_eq!(
+            resolve_stat_from_json(json, "CeilExact").unwrap().value,
+            0.07
+        );
+
+        let err = load_from_json(&json.replace("\"ceil\"}]}", "\"up\"}]}"))
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("nearest, floor, ceil"), "{}", err);
+    }
+
+    #[test]
+    fn test_percent_add_transform() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "stats": {"ATK": {"base": 100.0, "transforms": [
+    {"type": "additive", "value": 20.0},
+    {"type": "percent_add", "percent": 15.0},
+    {"type": "clamp", "max": 130.0}
+  ]}},
+  "templates": {"Aura": {"base": 200.0, "transforms": [{"type": "percent_add", "percent": "{{bonus}}"}]}}
+}
+"#;
+        let atk = resolve_stat_from_json(json, "ATK").unwrap();
+        assert!((atk.transforms[1].1 - 138.0).abs() < 1e-9);
+        assert_eq!(atk.value, 130.0);
+
+        let params = HashMap::from([("bonus".to_string(), -25.0)]);
+        let mut resolver = create_entity_stats(json, "hero:Aura", "Aura", &params).unwrap();
+        let aura = resolver
+            .resolve(&StatId::from_str("hero:Aura"), &StatContext::new())
+            .unwrap();
+        assert_eq!(aura.value, 150.0);
+        assert_eq!(
+            aura.transforms[0],
+            ("PercentIncreaseTransform(-25%)".to_string(), 150.0)
+        );
+    }
+
+    #[test]
+    fn test_manager_from_embedded_sources() {
+        use std::collections::HashMap;
+
+        let base = r#"
+{
+  "snippets": {"class_bonus": {"type": "multiplicative", "value": 1.5}},
+  "templates": {"BaseHP": {"base": 100.0, "transforms": [{"type": "additive", "value": "{{bonus}}"}]}}
+}
+"#;
+        let warrior = r#"
+{
+  "templates": {
+    "WarriorHP": {"extends": "BaseHP", "transforms": [{"$ref": "class_bonus"}]}
+  }
+}
+"#;
+        let manager =
+            StatTemplateManager::from_sources(&[("base.json", base), ("warrior.json", warrior)])
+                .unwrap();
+        let mut resolver = StatResolver::new();
+        let params = HashMap::from([("bonus".to_string(), 20.0)]);
+        manager
+            .apply_template(&mut resolver, "WarriorHP", "hero:HP", &params)
+            .unwrap();
+        let hp = resolver
+            .resolve(&StatId::from_str("hero:HP"), &StatContext::new())
+            .unwrap();
+        assert_eq!(hp.value, 180.0);
+
+        let err = StatTemplateManager::from_sources(&[("a.json", base), ("b.json", base)])
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("b.json"), "{}", err);
+
+        let err = StatTemplateManager::from_sources(&[("base.json", base), ("bad.json", "{")])
+            .err()
+            .unwrap();
+        assert!(
+            err.to_string().contains("bad.json: JSON parse error"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_percentage_source() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "stats": {
+    "HP": {"base": 500.0},
+    "Shield": {"sources": [{"type": "percentage", "of": "HP", "percent": 20.0}, {"type": "constant", "value": 5.0}]}
+  },
+  "templates": {
+    "HP": {"base": "{{hp}}"},
+    "Shield": {"sources": [{"type": "percentage", "of": "HP", "percent": "{{shield_pct}}"}]}
+  }
+}
+"#;
+        assert_eq!(resolve_stat_from_json(json, "Shield").unwrap().value, 105.0);
+
+        // Each entity's shield reads its own HP
+        let manager = StatTemplateManager::from_json(json).unwrap();
+        let mut resolver = StatResolver::new();
+        for (entity, hp) in [("hero", 300.0), ("ogre", 900.0)] {
+            let params = HashMap::from([("hp".to_string(), hp), ("shield_pct".to_string(), 10.0)]);
+            for stat_type in ["HP", "Shield"] {
+                let stat_id = StatTemplateManager::entity_stat_id(entity, stat_type);
+                manager
+                    .apply_template(&mut resolver, stat_type, &stat_id, &params)
+                    .unwrap();
+            }
+        }
+        let context = StatContext::new();
+        let mut shield = |entity: &str| {
+            let stat_id = StatTemplateManager::get_entity_stat_id(entity, "Shield");
+            resolver.resolve(&stat_id, &context).unwrap().value
+        };
+        assert_eq!(shield("hero"), 30.0);
+        assert_eq!(shield("ogre"), 90.0);
+    }
+
+    #[test]
+    fn test_resolve_with_base() {
+        use std::collections::HashMap;
+
+        let json = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/warrior.json"
+        ))
+        .unwrap();
+        let manager = StatTemplateManager::from_json(&json).unwrap();
+        let params: HashMap<String, f64> = [
+            ("level", 10.0),
+            ("base_hp", 150.0),
+            ("hp_per_level", 15.0),
+            ("base_vitality", 12.0),
+            ("vitality_per_level", 2.0),
+            ("base_strength", 15.0),
+            ("strength_per_level", 3.0),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+
+        let mut resolver = StatResolver::new();
+        for (template, stat_type) in [
+            ("WarriorVitality", "Vitality"),
+            ("WarriorStrength", "Strength"),
+            ("WarriorHP", "HP"),
+        ] {
+            let stat_id = StatTemplateManager::entity_stat_id("warrior", stat_type);
+            manager
+                .apply_template(&mut resolver, template, &stat_id, &params)
+                .unwrap();
+        }
+
+        let (base, value) = manager
+            .resolve_with_base(&mut resolver, "warrior", "HP", &StatContext::new())
+            .unwrap();
+        // 300 + 32 Vitality × 3 + 45 Strength × 2 from the stat-scaled sources
+        assert_eq!(base, 486.0);
+        assert!(base < value);
+        // 486 × 1.2
+        assert!((value - 583.2).abs() < 1e-9, "{}", value);
+
+        // Percentage and `@stat` sources run as transforms but still count as base
+        let json = r#"
+{
+  "templates": {
+    "Vitality": {"base": 40.0},
+    "Shield": {
+      "base": 10.0,
+      "sources": [
+        {"type": "percentage", "of": "Vitality", "percent": 50.0},
+        {"type": "constant", "value": "@Vitality"}
+      ],
+      "transforms": [{"type": "multiplicative", "value": 2.0}]
+    }
+  }
+}
+"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        let mut resolver = StatResolver::new();
+        let stats = ["Vitality", "Shield"].map(|s| (s.to_string(), s.to_string(), HashMap::new()));
+        let configs = StatTemplateManager::entity_params_to_configs("hero", &stats);
+        manager.load_entity_stats(&mut resolver, configs).unwrap();
+        let (base, value) = manager
+            .resolve_with_base(&mut resolver, "hero", "Shield", &StatContext::new())
+            .unwrap();
+        assert_eq!((base, value), (70.0, 140.0));
+    }
+
+    #[test]
+    fn test_quantize_transform() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "stats": {
+    "Low": {"base": 37.0, "transforms": [{"type": "quantize", "step": 5.0}]},
+    "High": {"base": 38.0, "transforms": [{"type": "quantize", "step": 5.0}]}
+  },
+  "templates": {"Mana": {"base": 112.0, "transforms": [{"type": "quantize", "step": "{{step}}"}]}}
+}
+"#;
+        assert_eq!(resolve_stat_from_json(json, "Low").unwrap().value, 35.0);
+        assert_eq!(resolve_stat_from_json(json, "High").unwrap().value, 40.0);
+
+        let mana = |step: f64| {
+            let params = HashMap::from([("step".to_string(), step)]);
+            create_entity_stats(json, "hero:Mana", "Mana", &params)
+        };
+        let mut resolver = mana(25.0).unwrap();
+        let value = resolver
+            .resolve(&StatId::from_str("hero:Mana"), &StatContext::new())
+            .unwrap()
+            .value;
+        assert_eq!(value, 100.0);
+        let err = mana(0.0).err().unwrap();
+        assert!(err.to_string().contains("step is zero"), "{}", err);
+    }
+
+    #[test]
+    fn test_curve_source() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "Mana": {"sources": [{"type": "curve", "level": "{{level}}", "points": [[1, 10.0], [10, 50.0], [20, 60.0]]}]}
+  }
+}
+"#;
+        let mana = |level: f64| {
+            let params = HashMap::from([("level".to_string(), level)]);
+            let mut resolver = create_entity_stats(json, "hero:Mana", "Mana", &params)?;
+            Ok::<_, YamlStatError>(
+                resolver
+                    .resolve(&StatId::from_str("hero:Mana"), &StatContext::new())?
+                    .value,
+            )
+        };
+        assert_eq!(mana(10.0).unwrap(), 50.0);
+        assert_eq!(mana(15.0).unwrap(), 55.0);
+        assert!((mana(4.0).unwrap() - (10.0 + 40.0 / 3.0)).abs() < 1e-9);
+        assert_eq!(mana(0.0).unwrap(), 10.0);
+        assert_eq!(mana(99.0).unwrap(), 60.0);
+        let err = mana(f64::NAN).unwrap_err();
+        assert!(err.to_string().contains("must be finite"), "{}", err);
+
+        let literal = r#"{"stats": {"Speed": {"sources": [{"type": "curve", "level": 5, "points": [[0, 0.0], [10, 100.0]]}]}}}"#;
+        assert_eq!(
+            resolve_stat_from_json(literal, "Speed").unwrap().value,
+            50.0
+        );
+        let single = literal.replace(", [10, 100.0]", "");
+        let err = load_from_json(&single).err().unwrap();
+        assert!(err.to_string().contains("at least two points"), "{}", err);
+    }
+
+    #[test]
+    fn test_resolve_sheet_lenient() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "Flat": {"base": "{{value}}"},
+    "Scaled": {"base": 10.0, "transforms": [{"type": "ratio_scale", "numerator_stat": "HP", "denominator_stat": "Zero", "factor": 1.0}]},
+    "FromHP": {"base": 0.0, "transforms": [{"type": "map", "dependencies": ["HP"], "multiplier": 0.5}]},
+    "Capped": {"base": 500.0, "transforms": [{"type": "clamp", "max": 100.0}]}
+  }
+}
+"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        let flat = |value: f64| HashMap::from([("value".to_string(), value)]);
+        let configs = StatTemplateManager::entity_params_to_configs(
+            "hero",
+            &[
+                ("HP".to_string(), "Flat".to_string(), flat(100.0)),
+                ("MP".to_string(), "Flat".to_string(), flat(40.0)),
+                ("Zero".to_string(), "Flat".to_string(), flat(0.0)),
+                ("Broken".to_string(), "Scaled".to_string(), HashMap::new()),
+                ("Shield".to_string(), "FromHP".to_string(), HashMap::new()),
+            ],
+        );
+        let mut resolver = StatResolver::new();
+        manager.load_entity_stats(&mut resolver, configs).unwrap();
+        // Stacked on top of the cached Flat template, so HP is capped at 100
+        manager
+            .apply_template(&mut resolver, "Capped", "hero:HP", &HashMap::new())
+            .unwrap();
+
+        let context = StatContext::new();
+        let (values, failures) = manager.resolve_sheet_lenient(&mut resolver, "hero", &context);
+        assert_eq!(values.len(), 4);
+        assert_eq!(values["HP"], 100.0);
+        assert_eq!(values["MP"], 40.0);
+        assert_eq!(values["Shield"], 50.0);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, "Broken");
+
+        // Retrying Broken in a scratch resolver re-applied Flat to hero:HP there, but the
+        // entries recorded for the real resolver still describe the clamp
+        let (_, flags) = manager
+            .resolve_entity_stat_flagged(&mut resolver, "hero", "HP", &context)
+            .unwrap();
+        assert!(flags.clamped_max);
+    }
+
+    #[test]
+    fn test_source_value_expressions() {
+        use config::SourceValue;
+        use std::collections::HashMap;
+
+        let params = HashMap::from([
+            ("base".to_string(), 100.0),
+            ("per_level".to_string(), 10.0),
+            ("level".to_string(), 5.0),
+        ]);
+        let eval = |s: &str| SourceValue::String(s.to_string()).resolve(&params);
+
+        assert_eq!(eval("{{base}} + {{per_level}} * {{level}}"), Ok(150.0));
+        assert_eq!(eval("({{base}} + {{per_level}}) * {{level}}"), Ok(550.0));
+        assert_eq!(eval("{{base}} - {{level}} - 5"), Ok(90.0));
+        assert_eq!(eval("-{{level}} * 2 / 4"), Ok(-2.5));
+        assert_eq!(eval("{{ level }}"), Ok(5.0));
+
+        assert!(
+            eval("{{base}} + {{missing}}")
+                .unwrap_err()
+                .contains("Parameter not found: missing")
+        );
+        assert!(
+            eval("{{base}} +")
+                .unwrap_err()
+                .contains("Invalid expression")
+        );
+        assert!(eval("({{base}}").unwrap_err().contains("Missing ')'"));
+        assert!(
+            eval("{{base}} / ({{level}} - 5)")
+                .unwrap_err()
+                .contains("Division by zero")
+        );
+        assert!(eval("abc").unwrap_err().contains("Invalid number"));
+
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(eval(&nested(expression::MAX_DEPTH)), Ok(1.0));
+        assert!(
+            eval(&nested(100_000))
+                .unwrap_err()
+                .contains("nested too deeply")
+        );
+        assert!(
+            eval(&format!("{}1", "-".repeat(100_000)))
+                .unwrap_err()
+                .contains("nested too deeply")
+        );
+
+        let value = SourceValue::String("{{base}} * ({{level}} + {{bonus}})".to_string());
+        assert_eq!(value.param_names(), ["base", "level", "bonus"]);
+
+        let json = r#"
+{
+  "templates": {"HP": {"base": "{{base}} + {{per_level}} * ({{level}} - 1)"}}
+}
+"#;
+        let mut resolver = create_entity_stats(json, "hero:HP", "HP", &params).unwrap();
+        let hp = resolver
+            .resolve(&StatId::from_str("hero:HP"), &StatContext::new())
+            .unwrap();
+        assert_eq!(hp.value, 140.0);
+    }
+
+    #[test]
+    fn test_extract_template_round_trip() {
+        use std::collections::HashMap;
+        use zzstat::source::ConstantSource;
+        use zzstat::transform::{AdditiveTransform, ClampTransform, MultiplicativeTransform};
+
+        let json = r#"{"templates": {"Ring": {"base": 0.0, "transforms": [
+            {"type": "additive", "value": 7.5, "name": "Ring"},
+            {"type": "clamp", "max": "{{cap}}"}
+        ]}}}"#;
+        let manager = StatTemplateManager::from_json(json).unwrap();
+        let mut resolver = StatResolver::new();
+        let atk = StatId::from_str("custom:ATK");
+        let params = HashMap::from([("cap".to_string(), 1000.125)]);
+        manager
+            .apply_template(&mut resolver, "Ring", "custom:ATK", &params)
+            .unwrap();
+        resolver.register_source(atk.clone(), Box::new(ConstantSource(80.0)));
+        resolver.register_transform(atk.clone(), Box::new(AdditiveTransform::new(12.0)));
+        resolver.register_transform(atk.clone(), Box::new(MultiplicativeTransform::new(1.15)));
+        resolver.register_transform(atk.clone(), Box::new(ClampTransform::new(0.0, 110.0)));
+        let expected = resolver.resolve(&atk, &StatContext::new()).unwrap().value;
+
+        let template = manager.extract_template(&mut resolver, &atk).unwrap();
+        let kinds: Vec<&str> = template.transforms.iter().map(|t| t.kind_label()).collect();
+        assert_eq!(
+            kinds,
+            [
+                "additive",
+                "clamp",
+                "additive",
+                "multiplicative",
+                "additive"
+            ]
+        );
+        assert_eq!(template.transforms[0].name(), Some("Ring"));
+        // The template's clamp keeps its exact bound, not zzstat's rounded description
+        let crate::config::TransformConfig::Clamp { max, .. } = &template.transforms[1] else {
+            unreachable!()
+        };
+        assert_eq!(max.as_ref().unwrap().resolve(&HashMap::new()), Ok(1000.125));
+        // A clamp registered straight on the resolver has no exact bounds and is frozen
+        assert_eq!(template.transforms[4].name(), Some("clamp(0.00, 110.00)"));
+
+        // Saved and loaded again, the template reproduces the value
+        let saved = serde_json::to_string(&StatConfig {
+            templates: HashMap::from([("SavedATK".to_string(), template)]),
+            ..StatConfig::default()
+        })
+        .unwrap();
+        let mut copy =
+            create_entity_stats(&saved, "copy:ATK", "SavedATK", &HashMap::new()).unwrap();
+        let value = copy
+            .resolve(&StatId::from_str("copy:ATK"), &StatContext::new())
+            .unwrap()
+            .value;
+        assert!((value - expected).abs() < 1e-9, "{} != {}", value, expected);
+    }
+
+    #[test]
+    fn test_conditional_context_flag() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "ATK": {
+      "base": 100.0,
+      "transforms": [
+        {
+          "type": "conditional",
+          "condition_context": "in_combat",
+          "operator": "==",
+          "condition_value": 1,
+          "then": {"type": "multiplicative", "value": 1.5}
+        }
+      ]
+    }
+  }
+}
+"#;
+        let mut resolver = create_entity_stats(json, "hero:ATK", "ATK", &HashMap::new()).unwrap();
+        let id = StatId::from_str("hero:ATK");
+
+        let mut context = StatContext::new();
+        let mut value = |context: &StatContext| {
+            resolve_in_context(&mut resolver, &id, context)
+                .unwrap()
+                .value
+        };
+        assert_eq!(value(&context), 100.0);
+
+        context.set("in_combat", 1.0);
+        assert_eq!(value(&context), 150.0);
+
+        context.set("in_combat", false);
+        assert_eq!(value(&context), 100.0);
+
+        let both = r#"
+{
+  "templates": {
+    "ATK": {
+      "base": 100.0,
+      "transforms": [
+        {
+          "type": "conditional",
+          "condition_stat": "Rage",
+          "condition_context": "in_combat",
+          "operator": "is_true",
+          "then": {"type": "additive", "value": 5.0}
+        }
+      ]
+    }
+  }
+}
+"#;
+        assert!(create_entity_stats(both, "hero:ATK", "ATK", &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_source_value_stat_reference() {
+        use config::{SourceConfig, SourceValue};
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "Strength": {"base": 30.0},
+    "Cap": {"base": 35.0},
+    "ATK": {
+      "sources": [
+        {"type": "constant", "value": 10.0},
+        {"type": "constant", "value": "@Strength"}
+      ],
+      "transforms": [{"type": "clamp", "max": "@Cap"}]
+    }
+  }
+}
+"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        let mut resolver = StatResolver::new();
+        let context = StatContext::new();
+        let stats =
+            ["Strength", "Cap", "ATK"].map(|s| (s.to_string(), s.to_string(), HashMap::new()));
+        let configs = StatTemplateManager::entity_params_to_configs("hero", &stats);
+        manager.load_entity_stats(&mut resolver, configs).unwrap();
+
+        // @Strength reads hero:Strength (10 + 30), then @Cap clamps to hero:Cap
+        let atk = manager
+            .resolve_entity_stat(&mut resolver, "hero", "ATK", &context)
+            .unwrap();
+        assert_eq!(atk.value, 35.0);
+
+        let source = SourceConfig::Constant {
+            value: SourceValue::String("@Strength".to_string()),
+            name: None,
+        };
+        assert_eq!(source.stat_refs(), ["Strength"]);
+        assert!(
+            SourceValue::String("@Strength".to_string())
+                .resolve(&HashMap::new())
+                .unwrap_err()
+                .contains("Stat reference @Strength")
+        );
+
+        let bad = r#"{"templates": {"X": {"base": 1.0, "transforms": [{"type": "multiplicative", "value": "@Strength"}]}}}"#;
+        assert!(create_entity_stats(bad, "hero:X", "X", &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_check_params_missing_and_unused() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "constants": {"cap": 100.0},
+  "templates": {
+    "HP": {
+      "base": "{{base}}",
+      "sources": [{"type": "scaling", "base": 0.0, "scale": "{{per_level}}", "level": "{{level}}"}],
+      "transforms": [{"type": "clamp", "max": "{{cap}}"}]
+    }
+  }
+}
+"#;
+        let manager = StatTemplateManager::from_json(json).unwrap();
+        let params = HashMap::from([
+            ("base".to_string(), 50.0),
+            ("per_level".to_string(), 5.0),
+            ("levle".to_string(), 3.0),
+        ]);
+
+        let check = manager.check_params("HP", &params).unwrap();
+        assert_eq!(
+            check,
+            ParamCheck {
+                missing: vec!["level".to_string()],
+                unused: vec!["levle".to_string()],
+            }
+        );
+
+        assert!(manager.check_params("Missing", &params).is_err());
+    }
+
+    #[test]
+    fn test_conditional_not_equal_operator() {
+        use crate::transform_conditional::evaluate_condition;
+        use std::collections::HashMap;
+
+        assert!(evaluate_condition("!=", 2.0, Some(1.0)).unwrap());
+        assert!(!evaluate_condition("!=", 1.0, Some(1.0)).unwrap());
+        // Within f64::EPSILON counts as equal
+        assert!(!evaluate_condition("!=", 0.1 + 0.2, Some(0.3)).unwrap());
+        assert!(evaluate_condition("!=", 0.3 + 1e-9, Some(0.3)).unwrap());
+
+        let json = r#"
+{
+  "templates": {
+    "Stance": {"base": "{{stance}}"},
+    "DEF": {
+      "base": 100.0,
+      "transforms": [
+        {
+          "type": "conditional",
+          "condition_stat": "Stance",
+          "operator": "!=",
+          "condition_value": 2.0,
+          "then": {"type": "multiplicative", "value": 0.5}
+        }
+      ]
+    }
+  }
+}
+"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        let context = StatContext::new();
+        for (stance, expected) in [(2.0, 100.0), (1.0, 50.0)] {
+            let mut resolver = StatResolver::new();
+            let params = HashMap::from([("stance".to_string(), stance)]);
+            let stats = [
+                ("Stance".to_string(), "Stance".to_string(), params.clone()),
+                ("DEF".to_string(), "DEF".to_string(), params),
+            ];
+            let configs = StatTemplateManager::entity_params_to_configs("hero", &stats);
+            manager.load_entity_stats(&mut resolver, configs).unwrap();
+            let def = manager
+                .resolve_entity_stat(&mut resolver, "hero", "DEF", &context)
+                .unwrap();
+            assert_eq!(def.value, expected);
+        }
+    }
+
+    #[test]
+    fn test_range_transform() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "Level": {"base": "{{level}}"},
+    "ATK": {
+      "base": 100.0,
+      "transforms": [
+        {
+          "type": "range",
+          "condition_stat": "Level",
+          "min": 10.0,
+          "max": 20.0,
+          "then": {"type": "additive", "value": 15.0},
+          "else_then": {"type": "additive", "value": 1.0}
+        }
+      ]
+    },
+    "Strict": {
+      "base": 100.0,
+      "transforms": [
+        {
+          "type": "range",
+          "condition_stat": "Level",
+          "min": 10.0,
+          "max": 20.0,
+          "inclusive": false,
+          "then": {"type": "additive", "value": 15.0}
+        }
+      ]
+    }
+  }
+}
+"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        let context = StatContext::new();
+        for (level, atk, strict) in [
+            (5.0, 101.0, 100.0),
+            (10.0, 115.0, 100.0),
+            (15.0, 115.0, 115.0),
+            (20.0, 115.0, 100.0),
+            (21.0, 101.0, 100.0),
+        ] {
+            let mut resolver = StatResolver::new();
+            let params = HashMap::from([("level".to_string(), level)]);
+            let stats =
+                ["Level", "ATK", "Strict"].map(|s| (s.to_string(), s.to_string(), params.clone()));
+            let configs = StatTemplateManager::entity_params_to_configs("hero", &stats);
+            manager.load_entity_stats(&mut resolver, configs).unwrap();
+            let resolve = |resolver: &mut StatResolver, stat: &str| {
+                manager
+                    .resolve_entity_stat(resolver, "hero", stat, &context)
+                    .unwrap()
+                    .value
+            };
+            assert_eq!(resolve(&mut resolver, "ATK"), atk, "level {}", level);
+            assert_eq!(resolve(&mut resolver, "Strict"), strict, "level {}", level);
+        }
+
+        let inverted = r#"
+{
+  "stats": {
+    "Level": {"base": 15.0},
+    "ATK": {
+      "base": 100.0,
+      "transforms": [
+        {"type": "range", "condition_stat": "Level", "min": 20.0, "max": 10.0,
+         "then": {"type": "additive", "value": 15.0}}
+      ]
+    }
+  }
+}
+"#;
+        let err = load_from_json(inverted).err().unwrap();
+        assert!(
+            err.to_string()
+                .contains("range min 20 is greater than max 10")
+        );
+        let problems = StatConfig::from_json(inverted).unwrap().validate_strict();
+        assert!(problems.iter().any(|p| {
+            p.to_string()
+                .contains("range min 20 is greater than max 10")
+        }));
+    }
+
+    #[test]
+    fn test_value_guard() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "HP": {"base": 500.0},
+    "ATK": {"base": 100.0, "transforms": [{"type": "multiplicative", "value": 1000.0}]}
+  }
+}
+"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        manager.with_value_guard(0.0..=9999.0);
+        let mut resolver = StatResolver::new();
+        let context = StatContext::new();
+        let stats = ["HP", "ATK"].map(|s| (s.to_string(), s.to_string(), HashMap::new()));
+        let configs = StatTemplateManager::entity_params_to_configs("hero", &stats);
+        manager.load_entity_stats(&mut resolver, configs).unwrap();
+
+        let hp = manager
+            .resolve_entity_stat(&mut resolver, "hero", "HP", &context)
+            .unwrap();
+        assert_eq!(hp.value, 500.0);
+
+        let err = manager
+            .resolve_entity_stat(&mut resolver, "hero", "ATK", &context)
+            .unwrap_err();
+        assert!(matches!(err, YamlStatError::InvalidConfig(_)));
+        assert!(err.to_string().contains("value out of policy"));
+    }
+
+    #[test]
+    fn test_from_reader_streaming() {
+        use std::io::Cursor;
+
+        let json = r#"
+{
+  "constants": {"unused": 1.0},
+  "stats": {
+    "HP": {"base": 100.0},
+    "MP": {"sources": [{"type": "constant", "value": 50.0}]},
+    "ATK": {"base": 10.0, "transforms": [{"type": "multiplicative", "value": 2.0}]}
+  },
+  "templates": {"Skipped": {"base": 1.0}}
+}
+"#;
+        let mut seen = Vec::new();
+        StatLoader::from_reader_streaming(Cursor::new(json), |name, definition| {
+            seen.push((name, definition.base.is_some(), definition.transforms.len()));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(
+            seen,
+            [
+                ("HP".to_string(), true, 0),
+                ("MP".to_string(), false, 0),
+                ("ATK".to_string(), true, 1),
+            ]
+        );
+
+        let mut calls = 0;
+        let err = StatLoader::from_reader_streaming(Cursor::new(json), |name, _| {
+            calls += 1;
+            Err(YamlStatError::InvalidConfig(format!("rejected {}", name)))
+        })
+        .unwrap_err();
+        assert_eq!(calls, 1);
+        assert!(matches!(err, YamlStatError::InvalidConfig(ref m) if m == "rejected HP"));
+
+        let truncated = &json[..json.len() / 2];
+        assert!(matches!(
+            StatLoader::from_reader_streaming(Cursor::new(truncated), |_, _| Ok(())),
+            Err(YamlStatError::JsonParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_conditional_all_any() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "Strength": {"base": "{{str}}"},
+    "Vitality": {"base": "{{vit}}"},
+    "Both": {
+      "base": 100.0,
+      "transforms": [
+        {
+          "type": "conditional",
+          "all": [
+            {"condition_stat": "Strength", "operator": ">=", "condition_value": 50.0},
+            {"condition_stat": "Vitality", "operator": ">=", "condition_value": 30.0}
+          ],
+          "then": {"type": "additive", "value": 10.0}
+        }
+      ]
+    },
+    "Either": {
+      "base": 100.0,
+      "transforms": [
+        {
+          "type": "conditional",
+          "any": [
+            {"condition_stat": "Strength", "operator": ">=", "condition_value": 50.0},
+            {"condition_stat": "Vitality", "operator": ">=", "condition_value": 30.0}
+          ],
+          "then": {"type": "additive", "value": 10.0},
+          "else_then": {"type": "additive", "value": 1.0}
+        }
+      ]
+    }
+  }
+}
+"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        let context = StatContext::new();
+        for (str_, vit, both, either) in [
+            (60.0, 40.0, 110.0, 110.0),
+            (60.0, 10.0, 100.0, 110.0),
+            (10.0, 40.0, 100.0, 110.0),
+            (10.0, 10.0, 100.0, 101.0),
+        ] {
+            let mut resolver = StatResolver::new();
+            let params = HashMap::from([("str".to_string(), str_), ("vit".to_string(), vit)]);
+            let stats = ["Strength", "Vitality", "Both", "Either"]
+                .map(|s| (s.to_string(), s.to_string(), params.clone()));
+            let configs = StatTemplateManager::entity_params_to_configs("hero", &stats);
+            manager.load_entity_stats(&mut resolver, configs).unwrap();
+            let mut resolve = |stat: &str| {
+                manager
+                    .resolve_entity_stat(&mut resolver, "hero", stat, &context)
+                    .unwrap()
+                    .value
+            };
+            assert_eq!(resolve("Both"), both, "str {} vit {}", str_, vit);
+            assert_eq!(resolve("Either"), either, "str {} vit {}", str_, vit);
+        }
+
+        let config = StatConfig::from_json(json).unwrap();
+        let view = config.templates["Both"].transforms[0]
+            .as_conditional()
+            .unwrap();
+        assert_eq!(view.condition_stat, None);
+        assert_eq!(view.all.len(), 2);
+        assert_eq!(
+            config.templates["Either"].transforms[0].stat_refs(),
+            ["Strength", "Vitality"]
+        );
+
+        let mixed = r#"{"templates": {"X": {"base": 1.0, "transforms": [{"type": "conditional",
+            "condition_stat": "A", "operator": ">", "condition_value": 0.0,
+            "all": [{"condition_stat": "B", "operator": ">", "condition_value": 0.0}],
+            "then": {"type": "additive", "value": 1.0}}]}}}"#;
+        assert!(create_entity_stats(mixed, "hero:X", "X", &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_clamp_to_stat_fraction() {
+        let json = r#"
+{
+  "stats": {
+    "MaxHP": {"base": 200.0},
+    "Shield": {
+      "base": 150.0,
+      "transforms": [{"type": "clamp_to_stat_fraction", "stat": "MaxHP", "max_fraction": 0.5}]
+    },
+    "Ward": {
+      "base": 10.0,
+      "transforms": [
+        {"type": "clamp_to_stat_fraction", "stat": "MaxHP", "min_fraction": 0.1, "max_fraction": 0.5}
+      ]
+    }
+  }
+}
+"#;
+        let mut resolver = load_from_json(json).unwrap();
+        let context = StatContext::new();
+        let shield = resolver
+            .resolve(&StatId::from_str("Shield"), &context)
+            .unwrap();
+        assert_eq!(shield.value, 100.0);
+        let ward = resolver
+            .resolve(&StatId::from_str("Ward"), &context)
+            .unwrap();
+        assert_eq!(ward.value, 20.0);
+
+        let config = StatConfig::from_json(json).unwrap();
+        assert_eq!(
+            config.pipeline("Shield").last().unwrap().1,
+            "clamp(x, -∞, 0.5 × MaxHP)"
+        );
+
+        let inverted = json.replace(
+            r#""min_fraction": 0.1, "max_fraction": 0.5"#,
+            r#""min_fraction": 0.5, "max_fraction": 0.1"#,
+        );
+        assert!(load_from_json(&inverted).is_err());
+        let unbounded = json.replace(r#", "max_fraction": 0.5}]"#, "}]");
+        assert!(load_from_json(&unbounded).is_err());
+    }
+
+    #[test]
+    fn test_string_template_params() {
+        use config::SourceValue;
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "Bonus": {"base": "{{value}}"},
+    "ATK": {
+      "sources": [
+        {"type": "constant", "value": "{{base}}"},
+        {"type": "constant", "value": "@{{element}}Bonus"}
+      ],
+      "transforms": [
+        {"type": "multiplicative", "value": "1 + {{crit}}"}
+      ]
+    }
+  }
+}
+"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        let mut resolver = StatResolver::new();
+        let bonuses = [("FireBonus", 5.0), ("IceBonus", 7.0)].map(|(stat, value)| {
+            let params = HashMap::from([("value".to_string(), value)]);
+            (stat.to_string(), "Bonus".to_string(), params)
+        });
+        let configs = StatTemplateManager::entity_params_to_configs("hero", &bonuses);
+        manager.load_entity_stats(&mut resolver, configs).unwrap();
+
+        let params = HashMap::from([
+            ("base".to_string(), ParamValue::from(10.0)),
+            ("element".to_string(), ParamValue::from("Ice")),
+            ("crit".to_string(), ParamValue::from(true)),
+        ]);
+        manager
+            .apply_template_with_values(&mut resolver, "ATK", "hero:ATK", &params)
+            .unwrap();
+        let atk = resolver
+            .resolve(&StatId::from_str("hero:ATK"), &StatContext::new())
+            .unwrap();
+        assert_eq!(atk.value, (10.0 + 7.0) * 2.0);
+
+        let value = SourceValue::String("{{base}} * 2".to_string());
+        assert_eq!(value.resolve_with_values(&params), Ok(20.0));
+        let param_value: ParamValue = serde_json::from_str(r#""Fire""#).unwrap();
+        assert_eq!(param_value, ParamValue::from("Fire"));
+
+        // Numeric-only typed params behave like the f64 API
+        let numeric = HashMap::from([("value".to_string(), ParamValue::from(3.0))]);
+        let mut resolver =
+            create_entity_stats_with_values(json, "hero:X", "Bonus", &numeric).unwrap();
+        let x = resolver
+            .resolve(&StatId::from_str("hero:X"), &StatContext::new())
+            .unwrap();
+        assert_eq!(x.value, 3.0);
+    }
+
+    #[test]
+    fn test_template_default_params() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "constants": {"bonus": 100.0},
+  "templates": {
+    "Base": {"params": {"bonus": 5.0, "multiplier": 3.0}},
+    "Damage": {
+      "extends": "Base",
+      "params": {"multiplier": 2.0},
+      "base": "{{base}}",
+      "sources": [{"type": "constant", "value": "{{bonus}}"}],
+      "transforms": [{"type": "multiplicative", "value": "{{multiplier}}"}]
+    },
+    "Plain": {
+      "params": {"base": 10.0},
+      "base": "{{base}}"
+    }
+  }
+}
+"#;
+        let manager = StatTemplateManager::from_json(json).unwrap();
+        let context = StatContext::new();
+        let resolve = |template: &str, params: &HashMap<String, f64>| {
+            let mut resolver = StatResolver::new();
+            manager
+                .apply_template(&mut resolver, template, "hero:X", params)
+                .unwrap();
+            resolver
+                .resolve(&StatId::from_str("hero:X"), &context)
+                .unwrap()
+                .value
+        };
+
+        // Defaults alone are enough
+        assert_eq!(resolve("Plain", &HashMap::new()), 10.0);
+        // Caller params win over defaults; the inherited `bonus` default beats the constant
+        let params = HashMap::from([("base".to_string(), 20.0)]);
+        assert_eq!(resolve("Damage", &params), (20.0 + 5.0) * 2.0);
+        let params = HashMap::from([("base".to_string(), 20.0), ("multiplier".to_string(), 1.0)]);
+        assert_eq!(resolve("Damage", &params), 25.0);
+
+        let check = manager.check_params("Damage", &HashMap::new()).unwrap();
+        assert_eq!(check.missing, ["base"]);
+    }
+
+    #[test]
+    fn test_difficulty_scaled_resolution() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "HP": {"base": 200.0},
+    "CritChance": {"base": 0.2, "tags": ["percent"]}
+  }
+}
+"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        manager.set_difficulty_exempt_tags(["percent"]);
+        let mut resolver = StatResolver::new();
+        let context = StatContext::new();
+        let stats = ["HP", "CritChance"].map(|s| (s.to_string(), s.to_string(), HashMap::new()));
+        let configs = StatTemplateManager::entity_params_to_configs("goblin", &stats);
+        manager.load_entity_stats(&mut resolver, configs).unwrap();
+
+        let hp = manager
+            .resolve_entity_stat_scaled(&mut resolver, "goblin", "HP", 1.5, &context)
+            .unwrap();
+        assert_eq!(hp, 300.0);
+
+        let sheet = manager
+            .resolve_entity_stats_scaled(
+                &mut resolver,
+                "goblin",
+                &["HP", "CritChance"],
+                1.5,
+                &context,
+            )
+            .unwrap();
+        assert_eq!(sheet["HP"], 300.0);
+        assert_eq!(sheet["CritChance"], 0.2);
+    }
+
+    #[test]
+    fn test_compare_param_sets() {
+        use std::collections::HashMap;
+
+        let json = r#"
+{
+  "templates": {
+    "HP": {"sources": [{"type": "scaling", "base": 100.0, "scale": "{{hp_per_level}}", "level": "{{level}}"}]},
+    "ATK": {"base": "{{base_atk}}", "transforms": [{"type": "additive", "value": "{{level}}"}]},
+    "Capped": {"base": 500.0, "transforms": [{"type": "clamp", "max": 100.0}]}
+  }
+}
+"#;
+        let manager = StatTemplateManager::from_json(json).unwrap();
+        let level = |level: f64| {
+            HashMap::from([
+                ("level".to_string(), level),
+                ("hp_per_level".to_string(), 10.0),
+                ("base_atk".to_string(), 20.0),
+            ])
+        };
+
+        let diff = manager
+            .compare_param_sets(
+                &[("HP", "HP"), ("ATK", "ATK")],
+                &level(5.0),
+                &level(10.0),
+                &StatContext::new(),
+            )
+            .unwrap();
+        assert_eq!(diff.len(), 2);
+        let (hp_a, hp_b) = diff["HP"];
+        let (atk_a, atk_b) = diff["ATK"];
+        assert!(hp_b > hp_a);
+        assert_eq!((atk_a, atk_b), (25.0, 30.0));
+
+        // The scratch sheets leave the entries recorded for real stats alone
+        let context = StatContext::new();
+        let mut resolver = StatResolver::new();
+        manager
+            .apply_template(&mut resolver, "Capped", "hero:HP", &HashMap::new())
+            .unwrap();
+        manager
+            .compare_param_sets(&[("ATK", "hero:HP")], &level(5.0), &level(10.0), &context)
+            .unwrap();
+        let (value, flags) = manager
+            .resolve_entity_stat_flagged(&mut resolver, "hero", "HP", &context)
+            .unwrap();
+        assert_eq!(value, 100.0);
+        assert!(flags.clamped_max);
+    }
+
+    #[test]
+    fn test_required_params_reported_up_front() {
+        use std::collections::{HashMap, HashSet};
+
+        let json = r#"
+{
+  "templates": {
+    "HP": {
+      "base": "{{base_hp}}",
+      "sources": [{"type": "scaling", "base": 0.0, "scale": 10.0, "level": "{{level}}"}],
+      "transforms": [{
+        "type": "conditional",
+        "condition_stat": "Rage",
+        "condition_value": 1.0,
+        "operator": ">=",
+        "then": {"type": "multiplicative", "value": "{{rage_mult}}"}
+      }]
+    }
+  }
+}
+"#;
+        let manager = StatTemplateManager::from_json(json).unwrap();
+        let required = manager.templates["HP"].required_params();
+        let expected: HashSet<String> = ["base_hp", "level", "rage_mult"].map(String::from).into();
+        assert_eq!(required, expected);
+
+        let mut resolver = StatResolver::new();
+        let params = HashMap::from([("level".to_string(), 3.0)]);
+        let err = manager
+            .apply_template(&mut resolver, "HP", "goblin:HP", &params)
+            .unwrap_err();
+        assert!(matches!(err, YamlStatError::InvalidConfig(_)));
+        assert!(err.to_string().contains("base_hp, rage_mult"));
+        assert!(
+            resolver
+                .resolve(&StatId::from_str("goblin:HP"), &StatContext::new())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_validate_dependency_cycles() {
+        let cyclic = r#"
+{
+  "stats": {
+    "A": {"base": 1.0, "transforms": [{"type": "map", "dependencies": ["B"]}]},
+    "B": {"base": 1.0, "transforms": [{
+      "type": "conditional",
+      "condition_stat": "A",
+      "condition_value": 0.0,
+      "operator": ">",
+      "then": {"type": "additive", "value": 1.0}
+    }]},
+    "C": {"base": 1.0}
+  }
+}
+"#;
+        let err = load_from_json_validated(cyclic).err().unwrap();
+        assert!(matches!(err, YamlStatError::InvalidConfig(_)));
+        assert!(err.to_string().contains("A -> B -> A"), "{}", err);
+
+        let acyclic = cyclic.replace(r#""dependencies": ["B"]"#, r#""dependencies": ["C"]"#);
+        assert!(load_from_json_validated(&acyclic).is_ok());
+
+        // Template references are prefixed with the stat's entity
+        let entity = r#"
+{
+  "templates": {
+    "Attack": {"base": 1.0, "transforms": [{"type": "map", "dependencies": ["STR"]}]},
+    "Strength": {"base": 1.0, "transforms": [{"type": "map", "dependencies": ["ATK"]}]}
+  },
+  "stats": {
+    "hero:ATK": {"template": "Attack"},
+    "hero:STR": {"template": "Strength"},
+    "STR": {"base": 5.0}
+  }
+}
+"#;
+        let err = StatConfig::from_json(entity)
+            .unwrap()
+            .validate()
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("hero:ATK -> hero:STR -> hero:ATK"),
+            "{}",
+            err
+        );
+
+        // `@stat` bases are dependencies too, in stats and templates
+        let bases = r#"
+{
+  "templates": {"Echo": {"base": "@Shout"}},
+  "stats": {
+    "Shout": {"base": "@Yell"},
+    "Yell": {"template": "Echo"},
+    "Whisper": {"base": 1.0}
+  }
+}
+"#;
+        let err = StatConfig::from_json(bases)
+            .unwrap()
+            .validate()
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Shout -> Yell -> Shout"),
+            "{}",
+            err
+        );
+        let config = StatConfig::from_json(&bases.replace("@Yell", "@Whisper")).unwrap();
+        assert!(config.validate().is_ok());
+        let mut stats: Vec<String> = config
+            .subset(&["Yell"])
+            .unwrap()
+            .stats
+            .into_keys()
+            .collect();
+        stats.sort();
+        assert_eq!(stats, ["Shout", "Whisper", "Yell"]);
+    }
+
+    #[test]
+    fn test_normalize_numeric_strings() {
+        let json = r#"
+{
+  "templates": {"Armor": {"base": "10", "transforms": [{"type": "additive", "value": "5"}]}},
+  "stats": {
+    "HP": {
+      "base": "100",
+      "sources": [{"type": "scaling", "base": "0", "scale": "{{per_level}}", "level": "3"}],
+      "transforms": [{
+        "type": "conditional",
+        "condition_stat": "Level",
+        "condition_value": 0.0,
+        "operator": ">",
+        "then": {"type": "multiplicative", "value": "1.5"},
+        "else_then": {"type": "percent_increase", "value": "-50%"}
+      }]
+    },
+    "Level": {"base": "7"}
+  },
+  "constants": {"per_level": 2.0}
+}
+"#;
+        let mut config = StatConfig::from_json(json).unwrap();
+        config.normalize();
+        let normalized = serde_json::to_value(&config).unwrap();
+
+        let hp = &normalized["stats"]["HP"];
+        assert_eq!(hp["base"], serde_json::json!(100.0));
+        assert_eq!(hp["sources"][0]["base"], serde_json::json!(0.0));
+        assert_eq!(hp["sources"][0]["scale"], "{{per_level}}");
+        assert_eq!(hp["sources"][0]["level"], serde_json::json!(3.0));
+        assert_eq!(hp["transforms"][0]["then"]["value"], serde_json::json!(1.5));
+        assert_eq!(hp["transforms"][0]["else_then"]["value"], "-50%");
+        assert_eq!(
+            normalized["templates"]["Armor"]["base"],
+            serde_json::json!(10.0)
+        );
+
+        let context = StatContext::new();
+        let hp_id = StatId::from_str("HP");
+        let before = load_from_json(json)
+            .unwrap()
+            .resolve(&hp_id, &context)
+            .unwrap()
+            .value;
+        let after = load_from_json(&serde_json::to_string(&config).unwrap())
+            .unwrap()
+            .resolve(&hp_id, &context)
+            .unwrap()
+            .value;
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn test_best_of_transform() {
+        let config = |mode: &str| {
+            format!(
+                r#"
+{{
+  "stats": {{
+    "Bonus": {{"base": 5.0}},
+    "ATK": {{
+      "base": 100.0,
+      "transforms": [{{
+        "type": "best_of",
+        "mode": "{}",
+        "options": [
+          {{"type": "additive", "value": 20.0}},
+          {{"type": "multiplicative", "value": 1.1}},
+          {{"type": "map", "dependencies": ["Bonus"]}}
+        ]
+      }}]
+    }}
+  }}
+}}
+"#,
+                mode
+            )
+        };
+        let atk = |mode: &str| {
+            let mut resolver = load_from_json(&config(mode)).unwrap();
+            resolver
+                .resolve(&StatId::from_str("ATK"), &StatContext::new())
+                .unwrap()
+                .value
+        };
+
+        assert_eq!(atk("max"), 120.0);
+        assert_eq!(atk("min"), 105.0);
+        assert!(load_from_json(&config("best")).is_err());
+    }
+
+    #[test]
+    fn test_load_entity_stats_checks_templates_first() {
+        use std::collections::HashMap;
+
+        let json = r#"{"templates": {"HP": {"base": 100.0}}}"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        let mut resolver = StatResolver::new();
+        let stats = [
+            ("HP", "HP"),
+            ("ATK", "Atk"),
+            ("DEF", "Deff"),
+            ("MDEF", "Deff"),
+        ]
+        .map(|(stat, template)| (stat.to_string(), template.to_string(), HashMap::new()));
+        let configs = StatTemplateManager::entity_params_to_configs("hero", &stats);
+
+        let err = manager
+            .load_entity_stats(&mut resolver, configs)
+            .unwrap_err();
+        assert!(matches!(err, YamlStatError::InvalidConfig(_)));
+        assert_eq!(err.to_string().matches("Deff").count(), 1);
+        assert!(err.to_string().contains("Atk, Deff"), "{}", err);
+
+        // Nothing was registered or cached, not even the valid HP stat
+        let hp = StatTemplateManager::get_entity_stat_id("hero", "HP");
+        assert!(resolver.resolve(&hp, &StatContext::new()).is_err());
+        assert!(manager.entity_stats_by_depth("hero").is_empty());
+    }
+
+    #[test]
+    fn test_json_file_helpers() {
+        let dir = std::env::temp_dir().join(format!("zzstat_json_files_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("stats.json");
+        std::fs::write(
+            &path,
+            r#"{"templates": {"HP": {"base": 50.0}}, "stats": {"MP": {"base": 30.0}}}"#,
+        )
+        .unwrap();
+
+        let mut resolver = load_from_json_file(&path).unwrap();
+        let mp = resolver
+            .resolve(&StatId::from_str("MP"), &StatContext::new())
+            .unwrap();
+        assert_eq!(mp.value, 30.0);
+
+        let manager = StatTemplateManager::from_json_file(&path).unwrap();
+        assert!(manager.check_params("HP", &Default::default()).is_ok());
+
+        let missing = dir.join("missing.json");
+        for err in [
+            load_from_json_file(&missing).err().unwrap(),
+            StatTemplateManager::from_json_file(&missing).err().unwrap(),
+        ] {
+            assert!(matches!(err, YamlStatError::IoError(_)));
+            assert!(err.to_string().contains("missing.json"), "{}", err);
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_labels_and_flags_follow_registered_entries() {
+        use std::collections::HashMap;
+
+        let load = |json: &str, policy: MissingParamPolicy| {
+            let mut manager = StatTemplateManager::from_json(json).unwrap();
+            manager.set_missing_param_policy(policy);
+            let mut resolver = StatResolver::new();
+            let stats = ["Vitality", "HP"].map(|s| (s.to_string(), s.to_string(), HashMap::new()));
+            let configs = StatTemplateManager::entity_params_to_configs("hero", &stats);
+            manager.load_entity_stats(&mut resolver, configs).unwrap();
+            let context = StatContext::new();
+            let resolved = manager
+                .resolve_entity_stat(&mut resolver, "hero", "HP", &context)
+                .unwrap();
+            let (_, flags) = manager
+                .resolve_entity_stat_flagged(&mut resolver, "hero", "HP", &context)
+                .unwrap();
+            (resolved, flags)
+        };
+
+        // A percentage source is registered as a leading transform, not a source
+        let stat_dependent = r#"
+{
+  "templates": {
+    "Vitality": {"base": 100.0},
+    "HP": {
+      "sources": [
+        {"type": "percentage", "of": "Vitality", "percent": 50.0, "name": "Vitality bonus"},
+        {"type": "constant", "value": 100.0, "name": "Base"}
+      ],
+      "transforms": [{"type": "clamp", "max": 120.0}]
+    }
+  }
+}
+"#;
+        let (resolved, flags) = load(stat_dependent, MissingParamPolicy::Error);
+        assert_eq!(resolved.sources, vec![("Base".to_string(), 100.0)]);
+        assert_eq!(
+            resolved.transforms[0],
+            ("Vitality bonus".to_string(), 150.0)
+        );
+        assert_eq!(resolved.value, 120.0);
+        assert!(flags.clamped_max && !flags.clamped_min);
+
+        // Skipped entries are not registered at all
+        let skipped = r#"
+{
+  "templates": {
+    "Vitality": {"base": 1.0},
+    "HP": {
+      "sources": [
+        {"type": "constant", "value": "{{gear}}", "name": "Gear"},
+        {"type": "constant", "value": 100.0, "name": "Base"}
+      ],
+      "transforms": [
+        {"type": "multiplicative", "value": "{{gear_mult}}"},
+        {"type": "clamp", "min": 150.0}
+      ]
+    }
+  }
+}
+"#;
+        let (resolved, flags) = load(skipped, MissingParamPolicy::Skip);
+        assert_eq!(resolved.sources, vec![("Base".to_string(), 100.0)]);
+        assert_eq!(resolved.value, 150.0);
+        assert!(flags.clamped_min && !flags.clamped_max);
+
+        // Merged multiplicatives are one registered transform
+        let merged = r#"
+{
+  "multiplicative_as_additive": true,
+  "templates": {
+    "Vitality": {"base": 1.0},
+    "HP": {
+      "base": 100.0,
+      "transforms": [
+        {"type": "multiplicative", "value": 1.5},
+        {"type": "multiplicative", "value": 1.5},
+        {"type": "clamp", "max": 150.0}
+      ]
+    }
+  }
+}
+"#;
+        let (resolved, flags) = load(merged, MissingParamPolicy::Error);
+        assert_eq!(resolved.transforms.len(), 2);
+        assert_eq!(resolved.value, 150.0);
+        assert!(flags.clamped_max);
+
+        // A later apply to the same stat ID, even in another resolver, replaces the entries
+        let json = r#"
+{
+  "templates": {
+    "Floored": {"base": 50.0, "transforms": [{"type": "clamp", "min": 100.0}]},
+    "Doubled": {"base": 50.0, "transforms": [{"type": "multiplicative", "value": 2.0}]}
+  }
+}
+"#;
+        let manager = StatTemplateManager::from_json(json).unwrap();
+        let context = StatContext::new();
+        let mut first = StatResolver::new();
+        manager
+            .apply_template(&mut first, "Floored", "hero:HP", &HashMap::new())
+            .unwrap();
+        let (_, flags) = manager
+            .resolve_entity_stat_flagged(&mut first, "hero", "HP", &context)
+            .unwrap();
+        assert!(flags.clamped_min);
+        let mut second = StatResolver::new();
+        manager
+            .apply_template(&mut second, "Doubled", "hero:HP", &HashMap::new())
+            .unwrap();
+        let (value, flags) = manager
+            .resolve_entity_stat_flagged(&mut second, "hero", "HP", &context)
+            .unwrap();
+        assert_eq!(value, 100.0);
+        assert!(!flags.clamped_min && !flags.clamped_max);
+        let (base, _) = manager
+            .resolve_with_base(&mut second, "hero", "HP", &context)
+            .unwrap();
+        assert_eq!(base, 50.0);
+    }
+
+    #[test]
+    fn test_reset_clears_entity_params() {
+        use std::collections::HashMap;
+
+        let json = r#"{"templates": {"HP": {"base": "{{level}} * 10"}}}"#;
+        let mut manager = StatTemplateManager::from_json(json).unwrap();
+        manager.set_missing_param_policy(MissingParamPolicy::Zero);
+        let mut resolver = StatResolver::new();
+        let hp = |manager: &mut StatTemplateManager, resolver: &mut StatResolver| {
+            let configs = StatTemplateManager::entity_params_to_configs(
+                "hero",
+                &[("HP".to_string(), "HP".to_string(), HashMap::new())],
+            );
+            manager.load_entity_stats(resolver, configs).unwrap();
+            manager
+                .resolve_entity_stat(resolver, "hero", "HP", &StatContext::new())
+                .unwrap()
+                .value
+        };
+
+        manager.set_entity_params(EntityParams {
+            entity_id: "hero".to_string(),
+            params: HashMap::from([("level".to_string(), 5.0)]),
+        });
+        assert_eq!(hp(&mut manager, &mut resolver), 50.0);
+
+        // A new run must not see the previous run's entity params
+        manager.reset(&mut resolver);
+        assert_eq!(hp(&mut manager, &mut resolver), 0.0);
+    }
+
+    #[test]
+    fn test_conditional_requires_value_for_comparisons() {
+        let missing = r#"
+{
+  "stats": {
+    "Rage": {"base": 30.0},
+    "ATK": {
+      "base": 100.0,
+      "transforms": [
+        {
+          "type": "conditional",
+          "condition_stat": "Rage",
+          "operator": ">=",
+          "then": {"type": "multiplicative", "value": 2.0}
+        }
+      ]
+    }
+  }
+}
+"#;
+        let Err(err) = load_from_json(missing) else {
+            panic!("a comparison without condition_value should be rejected");
+        };
+        assert!(
+            err.to_string().contains("needs a condition_value"),
+            "{}",
+            err
+        );
+        let config = StatConfig::from_json(missing).unwrap();
+        assert!(
+            config
+                .validate_strict()
+                .iter()
+                .any(|e| e.to_string().contains("needs a condition_value"))
+        );
+
+        // The flag operators ignore the value, so it may be omitted
+        let flag = missing.replace(">=", "is_true");
+        let mut resolver = load_from_json(&flag).unwrap();
+        let atk = resolver
+            .resolve(&StatId::from_str("ATK"), &StatContext::new())
+            .unwrap();
+        assert_eq!(atk.value, 200.0);
+        assert!(
+            StatConfig::from_json(&flag)
+                .unwrap()
+                .validate_strict()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_sub_condition_requires_value_for_comparisons() {
+        let missing = r#"
+{
+  "stats": {
+    "Rage": {"base": 30.0},
+    "Berserk": {"base": 1.0},
+    "ATK": {
+      "base": 100.0,
+      "transforms": [
+        {
+          "type": "conditional",
+          "all": [
+            {"condition_stat": "Berserk", "operator": "is_true"},
+            {"condition_stat": "Rage", "operator": ">="}
+          ],
+          "then": {"type": "multiplicative", "value": 2.0}
+        }
+      ]
+    }
+  }
+}
+"#;
+        let Err(err) = load_from_json(missing) else {
+            panic!("a sub-condition comparison without condition_value should be rejected");
+        };
+        assert!(
+            err.to_string().contains("needs a condition_value"),
+            "{}",
+            err
+        );
+        let config = StatConfig::from_json(missing).unwrap();
+        assert_eq!(config.validate_strict().len(), 1);
+
+        let valued = missing.replace(
+            r#""operator": ">="}"#,
+            r#""operator": ">=", "condition_value": 20.0}"#,
+        );
+        let mut resolver = load_from_json(&valued).unwrap();
+        let atk = resolver
+            .resolve(&StatId::from_str("ATK"), &StatContext::new())
+            .unwrap();
+        assert_eq!(atk.value, 200.0);
+    }
 }
@@ -1,11 +1,20 @@
-use crate::config::{SourceConfig, StatConfig, TransformConfig};
+use crate::config::{
+    LevelValue, SourceConfig, StatConfig, StatDefinition, TransformConfig, curve_value, sorted,
+};
 use crate::error::YamlStatError;
-use crate::transform::AdditiveTransform;
+use crate::template::StatTemplateManager;
+use crate::transform::{
+    AdditiveTransform, DivideTransform, NamedTransform, ScopedTransform, SubtractiveTransform,
+};
+use crate::transform_map::MapTransform;
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, Visitor};
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::io::{BufReader, Read};
+use std::path::Path;
 use zzstat::{
-    StatId, StatResolver, StatSource, StatTransform,
-    source::ConstantSource,
-    transform::{ClampTransform, MultiplicativeTransform},
+    ResolvedStat, StatContext, StatId, StatResolver, StatSource, StatTransform,
+    source::ConstantSource, transform::MultiplicativeTransform,
 };
 
 /// Loader that creates stat resolvers from JSON.
@@ -26,13 +35,149 @@ impl StatLoader {
     ///
     /// Returns `YamlStatError` if JSON parsing fails or configuration is invalid.
     pub fn from_json(json_content: &str) -> Result<StatResolver, YamlStatError> {
-        let config: StatConfig = serde_json::from_str(json_content)?;
+        let config = StatConfig::from_json(json_content)?;
         Self::build_resolver(config)
     }
 
+    /// Creates a StatResolver from JSON content after substituting `${VAR}` variables.
+    ///
+    /// Substitution is a textual pre-pass over the raw JSON at load time, so a variable
+    /// can stand for a number (`"max": ${LEVEL_CAP}`) or part of a string. Unlike
+    /// `{{param}}`, the values are fixed once loaded. Variables come only from `vars`,
+    /// never from the process environment.
+    ///
+    /// # Arguments
+    ///
+    /// * `json_content` - JSON string containing stat definitions and `${VAR}` references
+    /// * `vars` - Variable names (the text between `${` and `}`) to replacement text
+    ///
+    /// # Returns
+    ///
+    /// A `StatResolver` that can resolve the defined stats.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` for an unresolved or unterminated variable,
+    /// or any error `from_json` can return.
+    pub fn from_json_with_vars(
+        json_content: &str,
+        vars: &HashMap<String, String>,
+    ) -> Result<StatResolver, YamlStatError> {
+        let json_content = substitute_vars(json_content, vars)?;
+        Self::from_json(&json_content)
+    }
+
+    /// Resolves a stat with extra one-off transforms applied after its own.
+    ///
+    /// The transforms only exist in the resolver built for this call, so nothing is
+    /// persisted (useful for previewing event modifiers).
+    ///
+    /// # Arguments
+    ///
+    /// * `json_content` - JSON string containing stat definitions
+    /// * `stat_name` - Stat to resolve (a key of `stats`)
+    /// * `extra_transforms` - Transforms appended to the stat, `{{param}}` values resolving
+    ///   against the config constants
+    /// * `context` - StatContext for resolution
+    ///
+    /// # Returns
+    ///
+    /// The resolved stat, including the extra transforms in its breakdown.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if the stat is not defined, or any error from
+    /// parsing, building, or resolving.
+    pub fn resolve_with_extra(
+        json_content: &str,
+        stat_name: &str,
+        extra_transforms: Vec<TransformConfig>,
+        context: &StatContext,
+    ) -> Result<ResolvedStat, YamlStatError> {
+        let config = StatConfig::from_json(json_content)?;
+        if !config.stats.contains_key(stat_name) {
+            return Err(YamlStatError::InvalidConfig(format!(
+                "Stat not found: {}",
+                stat_name
+            )));
+        }
+        let constants = config.constants.clone();
+        let mut resolver = Self::build_resolver(config)?;
+
+        let stat_id = StatId::from_str(stat_name);
+        for transform_config in &extra_transforms {
+            let transform = Self::build_transform(transform_config, &HashMap::new(), &constants)?;
+            resolver.register_transform(
+                stat_id.clone(),
+                ScopedTransform::wrap(
+                    &stat_id,
+                    NamedTransform::wrap(transform, transform_config.name()),
+                ),
+            );
+        }
+
+        Ok(resolver.resolve(&stat_id, context)?)
+    }
+
+    /// Creates a StatResolver from a JSON file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to a JSON file containing stat definitions
+    ///
+    /// # Returns
+    ///
+    /// A `StatResolver` that can resolve the defined stats.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::IoError` (mentioning the path) if the file cannot be read,
+    /// or any error `from_json` can return.
+    pub fn from_json_file(path: impl AsRef<Path>) -> Result<StatResolver, YamlStatError> {
+        let json_content = read_config_file(path.as_ref())?;
+        Self::from_json(&json_content)
+    }
+
+    /// Streams the top-level `stats` entries of a JSON config, one at a time.
+    ///
+    /// Only the stat being handed to `on_stat` is held in memory, so peak memory does not
+    /// grow with the file size. Every other top-level key is skipped. Whole-document
+    /// features are not applied: `$ref` snippets are not expanded and aliases are not
+    /// rewritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of the JSON document (buffered internally)
+    /// * `on_stat` - Called with each stat name and definition, in document order
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from `on_stat` (streaming stops there), or
+    /// `YamlStatError::JsonParseError` if the JSON is invalid.
+    pub fn from_reader_streaming<R: Read>(
+        reader: R,
+        mut on_stat: impl FnMut(String, StatDefinition) -> Result<(), YamlStatError>,
+    ) -> Result<(), YamlStatError> {
+        let mut callback_error = None;
+        let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(reader));
+        let result = StreamedConfig {
+            on_stat: &mut on_stat,
+            error: &mut callback_error,
+        }
+        .deserialize(&mut deserializer);
+
+        if let Some(e) = callback_error {
+            return Err(e);
+        }
+        result?;
+        deserializer.end()?;
+        Ok(())
+    }
+
     /// Builds a resolver from configuration.
-    fn build_resolver(config: StatConfig) -> Result<StatResolver, YamlStatError> {
+    pub(crate) fn build_resolver(mut config: StatConfig) -> Result<StatResolver, YamlStatError> {
         let mut resolver = StatResolver::new();
+        config.apply_aliases();
 
         // First, create all stat IDs
         let stat_ids: HashMap<String, StatId> = config
@@ -41,43 +186,128 @@ impl StatLoader {
             .map(|name| (name.clone(), StatId::from_str(name)))
             .collect();
 
+        // Register in name order so repeated builds of one config are identical
+        let stats = sorted(&config.stats);
+
+        // Stats that instantiate a template are expanded through the template manager,
+        // together with their own sources and transforms
+        if config.stats.values().any(|d| d.template.is_some()) {
+            let manager = StatTemplateManager::from_config(StatConfig {
+                templates: config.templates.clone(),
+                constants: config.constants.clone(),
+                default_transforms: config.default_transforms.clone(),
+                aliases: config.aliases.clone(),
+                multiplicative_as_additive: config.multiplicative_as_additive,
+                require_explicit_level: config.require_explicit_level,
+                ..StatConfig::default()
+            })?;
+
+            for &(stat_name, definition) in &stats {
+                if let Some(template_name) = &definition.template {
+                    manager.apply_stat_definition(
+                        &mut resolver,
+                        template_name,
+                        stat_name,
+                        definition,
+                    )?;
+                }
+            }
+        }
+        let stats: Vec<_> = stats
+            .into_iter()
+            .filter(|(_, definition)| definition.template.is_none())
+            .collect();
+
         // Register sources
-        for (stat_name, definition) in &config.stats {
+        for &(stat_name, definition) in &stats {
             let stat_id = stat_ids.get(stat_name).ok_or_else(|| {
                 YamlStatError::InvalidConfig(format!("Stat not found: {}", stat_name))
             })?;
 
-            for source_config in &definition.sources {
-                let source = Self::build_source(source_config, &stat_ids)?;
+            let base_source = definition
+                .base
+                .clone()
+                .map(|value| SourceConfig::Constant { value, name: None });
+
+            for source_config in base_source.iter().chain(&definition.sources) {
+                // Stat-scaled sources need dependencies, so they run as the first transforms
+                if let Some(transform) =
+                    StatTemplateManager::resolve_stat_scaling(source_config, &config.constants, "")?
+                {
+                    resolver.register_transform(stat_id.clone(), transform);
+                    continue;
+                }
+                let source = Self::build_source(
+                    source_config,
+                    &stat_ids,
+                    &config.constants,
+                    config.require_explicit_level,
+                )?;
                 resolver.register_source(stat_id.clone(), source);
             }
         }
 
         // Register transformations
-        for (stat_name, definition) in &config.stats {
+        for &(stat_name, definition) in &stats {
             let stat_id = stat_ids.get(stat_name).ok_or_else(|| {
                 YamlStatError::InvalidConfig(format!("Stat not found: {}", stat_name))
             })?;
 
-            for transform_config in &definition.transforms {
-                let transform = Self::build_transform(transform_config, &stat_ids)?;
-                resolver.register_transform(stat_id.clone(), transform);
+            let default_transforms: &[TransformConfig] = if definition.ignore_default_transforms {
+                &[]
+            } else {
+                &config.default_transforms
+            };
+
+            let transforms = definition.transforms.iter().chain(default_transforms);
+            let transforms: Vec<Cow<TransformConfig>> = if config.multiplicative_as_additive {
+                TransformConfig::combine_multiplicatives(transforms, &config.constants)?
+            } else {
+                transforms.map(Cow::Borrowed).collect()
+            };
+            for transform_config in &transforms {
+                let transform =
+                    Self::build_transform(transform_config, &stat_ids, &config.constants)?;
+                resolver.register_transform(
+                    stat_id.clone(),
+                    ScopedTransform::wrap(
+                        stat_id,
+                        NamedTransform::wrap(transform, transform_config.name()),
+                    ),
+                );
             }
         }
 
+        // Aliases resolve to their canonical stat's value
+        for (alias, canonical) in sorted(&config.aliases) {
+            if config.stats.contains_key(alias) {
+                continue;
+            }
+            let passthrough = MapTransform::new(vec![StatId::from_str(canonical)], 1.0);
+            resolver.register_transform(
+                StatId::from_str(alias),
+                NamedTransform::wrap(
+                    Box::new(passthrough),
+                    Some(&format!("alias of {}", canonical)),
+                ),
+            );
+        }
+
         Ok(resolver)
     }
 
     /// Creates a StatSource from source configuration.
+    ///
+    /// `constants` are the config-level constants, used to resolve `{{param}}` values.
     fn build_source(
         config: &SourceConfig,
         _stat_ids: &HashMap<String, StatId>,
+        constants: &HashMap<String, f64>,
+        require_explicit_level: bool,
     ) -> Result<Box<dyn StatSource>, YamlStatError> {
-        let empty_params = HashMap::new();
-
         match config {
             SourceConfig::Constant { value, name: _ } => {
-                let resolved_value = value.resolve(&empty_params).map_err(|e| {
+                let resolved_value = value.resolve(constants).map_err(|e| {
                     YamlStatError::InvalidConfig(format!("Source resolution error: {}", e))
                 })?;
                 Ok(Box::new(ConstantSource(resolved_value)))
@@ -89,87 +319,134 @@ impl StatLoader {
                 level,
                 name: _,
             } => {
-                let base_val = base.resolve(&empty_params).map_err(|e| {
+                let base_val = base.resolve(constants).map_err(|e| {
                     YamlStatError::InvalidConfig(format!("Base resolution error: {}", e))
                 })?;
-                let scale_val = scale.resolve(&empty_params).map_err(|e| {
+                let scale_val = scale.resolve(constants).map_err(|e| {
                     YamlStatError::InvalidConfig(format!("Scale resolution error: {}", e))
                 })?;
-                let level_val = level
-                    .as_ref()
-                    .map(|l| l.resolve(&empty_params))
-                    .transpose()
-                    .map_err(|e| {
+                let level_val = match level {
+                    Some(LevelValue::Value(level)) => level.resolve(constants).map_err(|e| {
                         YamlStatError::InvalidConfig(format!("Level resolution error: {}", e))
-                    })?
-                    .unwrap_or(1.0);
+                    })?,
+                    Some(LevelValue::Stat(_)) => {
+                        return Err(YamlStatError::InvalidConfig(
+                            "Stat levels must be registered via resolve_stat_scaling".to_string(),
+                        ));
+                    }
+                    None if require_explicit_level => *constants
+                        .get("level")
+                        .ok_or_else(|| YamlStatError::MissingDependency("level".to_string()))?,
+                    None => 1.0,
+                };
 
                 let value = base_val + (scale_val * level_val);
                 Ok(Box::new(ConstantSource(value)))
             }
+
+            SourceConfig::Curve {
+                level,
+                points,
+                name: _,
+            } => {
+                let level_val = level.resolve(constants).map_err(|e| {
+                    YamlStatError::InvalidConfig(format!("Level resolution error: {}", e))
+                })?;
+                Ok(Box::new(ConstantSource(curve_value(points, level_val)?)))
+            }
+
+            SourceConfig::Percentage { .. } => Err(YamlStatError::InvalidConfig(
+                "Percentage sources must be registered via resolve_stat_scaling".to_string(),
+            )),
         }
     }
 
     /// Creates a StatTransform from transform configuration.
+    ///
+    /// `constants` are the config-level constants, used to resolve `{{param}}` values.
     fn build_transform(
         config: &TransformConfig,
         _stat_ids: &HashMap<String, StatId>,
+        constants: &HashMap<String, f64>,
     ) -> Result<Box<dyn StatTransform>, YamlStatError> {
         match config {
             TransformConfig::Multiplicative { value, name: _ } => {
-                let empty_params = HashMap::new();
-                let resolved_value = value.resolve(&empty_params).map_err(|e| {
+                let resolved_value = value.resolve(constants).map_err(|e| {
                     YamlStatError::InvalidConfig(format!("Transform resolution error: {}", e))
                 })?;
                 Ok(Box::new(MultiplicativeTransform::new(resolved_value)))
             }
 
             TransformConfig::Additive { value, name: _ } => {
-                let empty_params = HashMap::new();
-                let resolved_value = value.resolve(&empty_params).map_err(|e| {
+                let resolved_value = value.resolve(constants).map_err(|e| {
                     YamlStatError::InvalidConfig(format!("Transform resolution error: {}", e))
                 })?;
                 Ok(Box::new(AdditiveTransform::new(resolved_value)))
             }
 
-            TransformConfig::Clamp { min, max, name: _ } => {
-                let empty_params = HashMap::new();
-                let min_val = min
-                    .as_ref()
-                    .map(|m| m.resolve(&empty_params))
-                    .transpose()
-                    .map_err(|e| {
-                        YamlStatError::InvalidConfig(format!("Clamp min resolution error: {}", e))
-                    })?
-                    .unwrap_or(f64::NEG_INFINITY);
-                let max_val = max
-                    .as_ref()
-                    .map(|m| m.resolve(&empty_params))
-                    .transpose()
-                    .map_err(|e| {
-                        YamlStatError::InvalidConfig(format!("Clamp max resolution error: {}", e))
-                    })?
-                    .unwrap_or(f64::INFINITY);
-                Ok(Box::new(ClampTransform::new(min_val, max_val)))
+            TransformConfig::Subtractive { value, name: _ } => {
+                let resolved_value = value.resolve(constants).map_err(|e| {
+                    YamlStatError::InvalidConfig(format!("Transform resolution error: {}", e))
+                })?;
+                Ok(Box::new(SubtractiveTransform::new(resolved_value)))
+            }
+
+            TransformConfig::Divide { value, name: _ } => {
+                let resolved_value = value.resolve(constants).map_err(|e| {
+                    YamlStatError::InvalidConfig(format!("Transform resolution error: {}", e))
+                })?;
+                Ok(Box::new(DivideTransform::new(resolved_value)?))
+            }
+
+            TransformConfig::Clamp { .. }
+            | TransformConfig::Round { .. }
+            | TransformConfig::Ramp { .. }
+            | TransformConfig::FloorToStat { .. }
+            | TransformConfig::CeilToStat { .. }
+            | TransformConfig::MinOfStats { .. }
+            | TransformConfig::MaxOfStats { .. }
+            | TransformConfig::WeightedAverage { .. }
+            | TransformConfig::TimedAdditive { .. }
+            | TransformConfig::RateLimit { .. }
+            | TransformConfig::PercentIncrease { .. }
+            | TransformConfig::TableLookup { .. }
+            | TransformConfig::RatioScale { .. }
+            | TransformConfig::ConditionalStat { .. }
+            | TransformConfig::Remap { .. }
+            | TransformConfig::ExcessOf { .. }
+            | TransformConfig::ClampToStatFraction { .. }
+            | TransformConfig::Power { .. }
+            | TransformConfig::PercentAdd { .. }
+            | TransformConfig::Quantize { .. }
+            | TransformConfig::Min { .. }
+            | TransformConfig::Max { .. }
+            | TransformConfig::Range { .. }
+            | TransformConfig::BestOf { .. } => {
+                // Newer transforms share the template resolution path (global stats, no entity prefix)
+                StatTemplateManager::resolve_transform(config, constants)
             }
 
             TransformConfig::Conditional {
                 condition_stat,
+                condition_context,
+                all,
+                any,
                 condition_value,
                 operator,
                 then,
                 else_then,
             } => {
                 use crate::transform_conditional::ConditionalTransform;
-                // Empty string for entity_id for global stats
-                let empty_params = HashMap::new();
                 ConditionalTransform::from_config(
-                    condition_stat,
+                    condition_stat.as_deref(),
+                    condition_context.as_deref(),
+                    all,
+                    any,
                     *condition_value,
                     operator,
                     then,
                     else_then,
-                    &empty_params,
+                    constants,
                     "", // Empty string for global stats
                 )
                 .map(|t| Box::new(t) as Box<dyn StatTransform>)
@@ -180,9 +457,6 @@ impl StatLoader {
                 multiplier,
                 name: _,
             } => {
-                use crate::transform_map::MapTransform;
-                let empty_params = HashMap::new();
-
                 let mut dependency_ids = Vec::new();
                 for dep_name in dependencies {
                     dependency_ids.push(StatId::from_str(dep_name));
@@ -190,15 +464,139 @@ impl StatLoader {
 
                 let multiplier_val = multiplier
                     .as_ref()
-                    .map(|m| m.resolve(&empty_params))
+                    .map(|m| m.resolve(constants))
                     .transpose()
                     .map_err(|e| {
                         YamlStatError::InvalidConfig(format!("Multiplier resolution error: {}", e))
                     })?
                     .unwrap_or(1.0);
 
-                Ok(Box::new(MapTransform::new(dependency_ids, multiplier_val)))
+                Ok(Box::new(MapTransform::try_new(
+                    dependency_ids,
+                    multiplier_val,
+                )?))
+            }
+        }
+    }
+}
+
+/// Characters of the text after an unterminated `${` quoted in the error.
+const VAR_EXCERPT_CHARS: usize = 20;
+
+/// Replaces every `${VAR}` in `text` with its value from `vars`.
+fn substitute_vars(text: &str, vars: &HashMap<String, String>) -> Result<String, YamlStatError> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find('}').ok_or_else(|| {
+            let offset = text.len() - rest.len() + start;
+            let line = text[..offset].matches('\n').count() + 1;
+            let excerpt: String = after
+                .chars()
+                .take_while(|&c| c != '\n')
+                .take(VAR_EXCERPT_CHARS)
+                .collect();
+            YamlStatError::InvalidConfig(format!(
+                "Unterminated variable at line {} (byte {}): ${{{}",
+                line, offset, excerpt
+            ))
+        })?;
+        let name = &after[..end];
+        let value = vars.get(name).ok_or_else(|| {
+            YamlStatError::InvalidConfig(format!("Unresolved variable: ${{{}}}", name))
+        })?;
+        out.push_str(value);
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Reads a config file to a string, wrapping I/O errors with the path.
+pub(crate) fn read_config_file(path: &Path) -> Result<String, YamlStatError> {
+    std::fs::read_to_string(path).map_err(|e| YamlStatError::io_with_path(path, e))
+}
+
+/// Streams the top-level object of a config, handing `stats` to `StreamedStats`.
+struct StreamedConfig<'a, F> {
+    on_stat: &'a mut F,
+    /// Callback error, kept so it is returned as-is rather than as a JSON error
+    error: &'a mut Option<YamlStatError>,
+}
+
+impl<'de, F> DeserializeSeed<'de> for StreamedConfig<'_, F>
+where
+    F: FnMut(String, StatDefinition) -> Result<(), YamlStatError>,
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F> Visitor<'de> for StreamedConfig<'_, F>
+where
+    F: FnMut(String, StatDefinition) -> Result<(), YamlStatError>,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a stat config object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "stats" {
+                map.next_value_seed(StreamedStats {
+                    on_stat: &mut *self.on_stat,
+                    error: &mut *self.error,
+                })?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Deserializes the `stats` object one entry at a time, passing each to the callback.
+struct StreamedStats<'a, F> {
+    on_stat: &'a mut F,
+    error: &'a mut Option<YamlStatError>,
+}
+
+impl<'de, F> DeserializeSeed<'de> for StreamedStats<'_, F>
+where
+    F: FnMut(String, StatDefinition) -> Result<(), YamlStatError>,
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F> Visitor<'de> for StreamedStats<'_, F>
+where
+    F: FnMut(String, StatDefinition) -> Result<(), YamlStatError>,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("an object of stat definitions")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(name) = map.next_key::<String>()? {
+            let definition: StatDefinition = map.next_value()?;
+            if let Err(e) = (self.on_stat)(name, definition) {
+                *self.error = Some(e);
+                return Err(de::Error::custom("stat callback failed"));
             }
         }
+        Ok(())
     }
 }
@@ -1,7 +1,19 @@
-use crate::config::{SourceConfig, StatConfig, StatTemplate, TransformConfig};
+use crate::config::{
+    LevelValue, ParamValue, SourceConfig, SourceValue, StatConfig, StatDefinition, StatLevel,
+    StatTemplate, SubCondition, TransformConfig, canonical_name, curve_value,
+};
 use crate::error::YamlStatError;
+use crate::transform::{
+    AdditiveTransform, MutableAdditiveHandle, MutableAdditiveTransform, NamedTransform,
+    ScopedTransform,
+};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::RangeInclusive;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+use zzstat::transform::MultiplicativeTransform;
 use zzstat::{StatId, StatResolver, StatSource, StatTransform};
 
 /// Entity stat configuration (can be stored in database)
@@ -26,11 +38,168 @@ pub struct EntityParams {
     pub params: HashMap<String, f64>,
 }
 
+/// Resolved sheet values paired with per-stat resolve durations (keyed by stat type)
+pub type TimedSheet = (HashMap<String, f64>, HashMap<String, Duration>);
+
+/// Which bounding or branching transforms were active during a resolve
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatFlags {
+    /// A clamp raised the value to its minimum
+    pub clamped_min: bool,
+    /// A clamp lowered the value to its maximum
+    pub clamped_max: bool,
+    /// A conditional's condition was met
+    pub conditional_fired: bool,
+}
+
+/// One source or transform registered for a stat by `apply_template`, in registration order.
+///
+/// A template entry does not always map 1:1 to a breakdown entry: stat-dependent sources
+/// are registered as transforms, skipped entries are dropped, and merged multiplicatives
+/// become one transform. Recording what was actually registered keeps labels and flags
+/// aligned with the resolved breakdown.
+#[derive(Debug, Clone)]
+pub(crate) enum RegisteredEntry {
+    /// A source (one entry of `ResolvedStat::sources`), with its template `name`
+    Source(Option<String>),
+    /// A stat-dependent source registered as a transform, with its template `name`
+    SourceTransform(Option<String>),
+    /// A transform, with the (possibly merged) config it was built from
+    Transform(TransformConfig),
+}
+
+/// Registered entries per stat ID, recorded by `apply_template`.
+///
+/// Each apply replaces the stat's entries, so they describe the template most recently
+/// applied to that stat ID (in whichever resolver), and the map stays one entry per stat
+/// when a fresh resolver is built per request. `apply_template` only borrows the manager,
+/// so the entries sit behind a mutex. Cloning copies the entries, keeping cloned managers
+/// independent.
+#[derive(Debug, Default)]
+struct Registry(Mutex<HashMap<String, Vec<RegisteredEntry>>>);
+
+impl Registry {
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Vec<RegisteredEntry>>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the entries registered for a stat (empty if it was not applied from a template).
+    fn entries(&self, stat_id: &str) -> Vec<RegisteredEntry> {
+        self.lock().get(stat_id).cloned().unwrap_or_default()
+    }
+}
+
+impl Clone for Registry {
+    fn clone(&self) -> Self {
+        Registry(Mutex::new(self.lock().clone()))
+    }
+}
+
+/// Result of `StatTemplateManager::check_params`: params a template needs but did not get,
+/// and params that were passed but are never referenced (often typos)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParamCheck {
+    /// Referenced params that are neither passed, template defaults, nor global constants (sorted)
+    pub missing: Vec<String>,
+    /// Passed params the template never references (sorted)
+    pub unused: Vec<String>,
+}
+
+/// Structured record of how one entity stat was resolved, for audit logs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Entity ID
+    pub entity_id: String,
+    /// Stat type (e.g., "HP", "ATK")
+    pub stat_type: String,
+    /// Template the stat was loaded from (if cached via `load_entity_stats`)
+    pub template_name: Option<String>,
+    /// Fingerprint of that template's definition (FNV-1a of its JSON, hex)
+    pub config_version: Option<String>,
+    /// Effective values of the parameters the template references
+    pub params: BTreeMap<String, f64>,
+    /// Resolved values of the stats the template depends on
+    pub dependencies: BTreeMap<String, f64>,
+    /// Source contributions as `(label, value)`
+    pub sources: Vec<(String, f64)>,
+    /// Transform steps as `(label, value after the step)`
+    pub transforms: Vec<(String, f64)>,
+    /// Final value
+    pub value: f64,
+}
+
+/// What `apply_template` does when a `{{param}}` has no value (see `set_missing_param_policy`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingParamPolicy {
+    /// Fail with the resolution error (the default)
+    #[default]
+    Error,
+    /// Use 0.0 for the missing param
+    Zero,
+    /// Leave out every source and transform that references the missing param
+    Skip,
+}
+
+/// Kind of an equipment bonus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BonusKind {
+    /// Added to the stat
+    Flat,
+    /// Increase as a fraction (0.1 = +10%)
+    Percent,
+}
+
+/// A single bonus granted by an equipment slot
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EquipBonus {
+    /// Whether the bonus is flat or a percentage
+    pub kind: BonusKind,
+    /// Bonus amount (a fraction for `Percent`)
+    pub value: f64,
+}
+
+/// How percent bonuses from several slots combine (see `apply_equipment`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StackRule {
+    /// Percentages are summed first: three +10% give ×1.3
+    #[default]
+    Additive,
+    /// Percentages compound: three +10% give ×1.331
+    Multiplicative,
+}
+
 /// Stat template manager - manages templates and entity-based stat management
+///
+/// Cloning produces an independent deep copy of the templates, constants, and entity caches.
+#[derive(Debug, Clone)]
 pub struct StatTemplateManager {
     pub(crate) templates: HashMap<String, StatTemplate>,
+    /// Source values of templates whose sources are all literals, resolved once up front
+    ///
+    /// Derived from `templates`; rebuild it with `literal_sources_of` whenever they change.
+    pub(crate) literal_sources: HashMap<String, Vec<f64>>,
+    /// Global constants merged under per-call params
+    pub(crate) constants: HashMap<String, f64>,
+    /// Transforms appended to every applied template
+    pub(crate) default_transforms: Vec<TransformConfig>,
     /// Entity stat configurations (for caching)
     entity_configs: HashMap<String, Vec<EntityStatConfig>>,
+    /// Per-entity default parameters (see `set_entity_params`)
+    entity_params: HashMap<String, HashMap<String, f64>>,
+    /// Reject scaling sources without a `level` (see `set_require_explicit_level`)
+    require_explicit_level: bool,
+    /// How missing `{{param}}` values are handled
+    missing_param_policy: MissingParamPolicy,
+    /// Old stat names mapped to their canonical names
+    pub(crate) aliases: HashMap<String, String>,
+    /// Merge each stat's multiplicatives additively (see `StatConfig::multiplicative_as_additive`)
+    pub(crate) multiplicative_as_additive: bool,
+    /// Allowed range for every resolved entity stat (see `with_value_guard`)
+    value_guard: Option<RangeInclusive<f64>>,
+    /// Template tags whose stats `resolve_entity_stat_scaled` leaves unscaled
+    difficulty_exempt_tags: HashSet<String>,
+    /// What `apply_template` registered for each stat (for labels, flags, and the base)
+    registered: Registry,
 }
 
 impl StatTemplateManager {
@@ -48,10 +217,93 @@ impl StatTemplateManager {
     ///
     /// Returns `YamlStatError` if JSON parsing fails.
     pub fn from_json(json_content: &str) -> Result<Self, YamlStatError> {
-        let config: StatConfig = serde_json::from_str(json_content)?;
+        let config = StatConfig::from_json(json_content)?;
         Self::from_config(config)
     }
 
+    /// Creates a template manager from a JSON file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to a JSON file containing template definitions
+    ///
+    /// # Returns
+    ///
+    /// A `StatTemplateManager` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::IoError` (mentioning the path) if the file cannot be read,
+    /// or any error `from_json` can return.
+    pub fn from_json_file(path: impl AsRef<std::path::Path>) -> Result<Self, YamlStatError> {
+        let json_content = crate::loader::read_config_file(path.as_ref())?;
+        Self::from_json(&json_content)
+    }
+
+    /// Creates a template manager from several named in-memory JSON documents.
+    ///
+    /// The documents are merged before parsing, so `extends`, `include`, and `$ref` in one
+    /// document can refer to templates and snippets defined in another, as if the documents
+    /// were one file. Map sections (`templates`, `snippets`, `constants`, ...) are combined
+    /// and `default_transforms` are concatenated in document order. Handy with `include_str!`.
+    ///
+    /// # Arguments
+    ///
+    /// * `named` - `(name, json_content)` pairs; names are used in error messages
+    ///
+    /// # Returns
+    ///
+    /// A `StatTemplateManager` over all documents.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` naming the document if it is not valid JSON, is
+    /// not a JSON object, or defines an entry another document already defines, plus any
+    /// error `from_json` can return.
+    pub fn from_sources(named: &[(&str, &str)]) -> Result<Self, YamlStatError> {
+        use serde_json::Value;
+
+        let mut merged = serde_json::Map::new();
+        for (name, json_content) in named {
+            let document = serde_json::from_str(json_content).map_err(|e| {
+                YamlStatError::InvalidConfig(format!("{}: JSON parse error: {}", name, e))
+            })?;
+            let Value::Object(document) = document else {
+                return Err(YamlStatError::InvalidConfig(format!(
+                    "{}: expected a JSON object",
+                    name
+                )));
+            };
+            for (section, value) in document {
+                match (merged.get_mut(&section), value) {
+                    (None, value) => {
+                        merged.insert(section, value);
+                    }
+                    (Some(Value::Object(existing)), Value::Object(entries)) => {
+                        for (key, entry) in entries {
+                            if existing.contains_key(&key) {
+                                return Err(YamlStatError::InvalidConfig(format!(
+                                    "{}: {} '{}' is already defined by another source",
+                                    name, section, key
+                                )));
+                            }
+                            existing.insert(key, entry);
+                        }
+                    }
+                    (Some(Value::Array(existing)), Value::Array(items)) => existing.extend(items),
+                    (Some(_), _) => {
+                        return Err(YamlStatError::InvalidConfig(format!(
+                            "{}: '{}' has a different shape than in another source",
+                            name, section
+                        )));
+                    }
+                }
+            }
+        }
+
+        Self::from_config(StatConfig::from_value(Value::Object(merged))?)
+    }
+
     /// Creates a template manager from StatConfig.
     ///
     /// # Arguments
@@ -61,13 +313,206 @@ impl StatTemplateManager {
     /// # Returns
     ///
     /// A `StatTemplateManager` instance.
-    pub fn from_config(config: StatConfig) -> Result<Self, YamlStatError> {
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if an `extends` reference is missing or cyclic.
+    pub fn from_config(mut config: StatConfig) -> Result<Self, YamlStatError> {
+        // Dependencies on renamed stats point at the canonical stat from here on
+        config.apply_aliases();
+
+        let templates = Self::resolve_extends(config.templates)?;
+        let literal_sources = Self::literal_sources_of(&templates);
+
         Ok(Self {
-            templates: config.templates,
+            templates,
+            literal_sources,
+            constants: config.constants,
+            default_transforms: config.default_transforms,
             entity_configs: HashMap::new(),
+            entity_params: HashMap::new(),
+            require_explicit_level: config.require_explicit_level,
+            missing_param_policy: MissingParamPolicy::default(),
+            aliases: config.aliases,
+            multiplicative_as_additive: config.multiplicative_as_additive,
+            value_guard: None,
+            difficulty_exempt_tags: HashSet::new(),
+            registered: Registry::default(),
         })
     }
 
+    /// Resolves the sources of every template whose sources are all literals.
+    fn literal_sources_of(templates: &HashMap<String, StatTemplate>) -> HashMap<String, Vec<f64>> {
+        templates
+            .iter()
+            .filter_map(|(name, template)| {
+                Some((name.clone(), Self::literal_source_values(template)?))
+            })
+            .collect()
+    }
+
+    /// Resolves a template's base and sources if none of them depend on params or stats.
+    ///
+    /// Scaling sources without an explicit `level` depend on `require_explicit_level`, so
+    /// they are not treated as literals.
+    fn literal_source_values(template: &StatTemplate) -> Option<Vec<f64>> {
+        let no_params = HashMap::new();
+        let base_source = template
+            .base
+            .clone()
+            .map(|value| SourceConfig::Constant { value, name: None });
+
+        base_source
+            .iter()
+            .chain(&template.sources)
+            .map(|source| {
+                let literal = match source {
+                    SourceConfig::Constant { .. } => true,
+                    SourceConfig::Scaling { level, .. } => {
+                        matches!(level, Some(LevelValue::Value(_)))
+                    }
+                    SourceConfig::Curve { .. } => true,
+                    SourceConfig::Percentage { .. } => false,
+                };
+                if !literal || source.values().iter().any(|v| !v.param_names().is_empty()) {
+                    return None;
+                }
+                Self::resolve_source_value(source, &no_params, false).ok()
+            })
+            .collect()
+    }
+
+    /// Looks up a template by map key, falling back to a template whose `id` matches.
+    pub(crate) fn find_template<'a>(
+        templates: &'a HashMap<String, StatTemplate>,
+        name: &str,
+    ) -> Option<&'a StatTemplate> {
+        templates
+            .get(name)
+            .or_else(|| templates.values().find(|t| t.id.as_deref() == Some(name)))
+    }
+
+    /// Looks up a template by map key or `id`.
+    fn get_template(&self, name: &str) -> Result<&StatTemplate, YamlStatError> {
+        Self::find_template(&self.templates, name)
+            .ok_or_else(|| YamlStatError::InvalidConfig(format!("Template not found: {}", name)))
+    }
+
+    /// Flattens `extends` chains so each template carries its parents' sources and transforms.
+    ///
+    /// Parent entries come first. `base` and `description` are inherited when not set on the
+    /// child. The resolved templates have `extends` cleared so serializing them round-trips.
+    /// Duplicate template ids are rejected here, since lookups by id would otherwise depend on
+    /// map order.
+    pub(crate) fn resolve_extends(
+        templates: HashMap<String, StatTemplate>,
+    ) -> Result<HashMap<String, StatTemplate>, YamlStatError> {
+        Self::check_template_ids(&templates)?;
+        let mut resolved = HashMap::new();
+        for name in templates.keys() {
+            let mut chain = Vec::new();
+            let template = Self::flatten_template(&templates, name, &mut chain)?;
+            resolved.insert(name.clone(), template);
+        }
+        Ok(resolved)
+    }
+
+    /// Rejects a template `id` shared by two templates or equal to another template's key.
+    fn check_template_ids(templates: &HashMap<String, StatTemplate>) -> Result<(), YamlStatError> {
+        let mut owners: HashMap<&str, &str> = HashMap::new();
+        for (key, template) in crate::config::sorted(templates) {
+            let Some(id) = template.id.as_deref() else {
+                continue;
+            };
+            let other_key = (id != key.as_str() && templates.contains_key(id)).then_some(id);
+            if let Some(owner) = owners.insert(id, key).or(other_key) {
+                return Err(YamlStatError::InvalidConfig(format!(
+                    "Template id '{}' of '{}' is already used by template '{}'",
+                    id, key, owner
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Flattens one template's `extends` parent and `include` mixins (recursively).
+    ///
+    /// `chain` holds the templates currently being flattened, for cycle detection.
+    fn flatten_template(
+        templates: &HashMap<String, StatTemplate>,
+        name: &str,
+        chain: &mut Vec<String>,
+    ) -> Result<StatTemplate, YamlStatError> {
+        let mut template = templates[name].clone();
+        chain.push(name.to_string());
+
+        let mut sources = Vec::new();
+        let mut transforms = Vec::new();
+
+        if let Some(parent_name) = template.extends.take() {
+            let parent = Self::flatten_reference(templates, &parent_name, "extends", chain)?;
+            template.base = template.base.or(parent.base);
+            template.description = template.description.or(parent.description);
+            sources.extend(parent.sources);
+            transforms.extend(parent.transforms);
+            inherit_params(&mut template.params, parent.params);
+            inherit_tags(&mut template.tags, parent.tags);
+        }
+
+        for mixin_name in std::mem::take(&mut template.include) {
+            let mixin = Self::flatten_reference(templates, &mixin_name, "includes", chain)?;
+            sources.extend(
+                mixin
+                    .base
+                    .map(|value| SourceConfig::Constant { value, name: None }),
+            );
+            sources.extend(mixin.sources);
+            transforms.extend(mixin.transforms);
+            inherit_params(&mut template.params, mixin.params);
+            inherit_tags(&mut template.tags, mixin.tags);
+        }
+
+        sources.append(&mut template.sources);
+        transforms.append(&mut template.transforms);
+        template.sources = sources;
+        template.transforms = transforms;
+
+        chain.pop();
+        Ok(template)
+    }
+
+    /// Flattens a template referenced via `extends` or `include` from the end of `chain`.
+    fn flatten_reference(
+        templates: &HashMap<String, StatTemplate>,
+        reference: &str,
+        relation: &str,
+        chain: &mut Vec<String>,
+    ) -> Result<StatTemplate, YamlStatError> {
+        let key = templates
+            .iter()
+            .find(|(key, t)| *key == reference || t.id.as_deref() == Some(reference))
+            .map(|(key, _)| key.clone())
+            .ok_or_else(|| {
+                YamlStatError::InvalidConfig(format!(
+                    "Template {} {} unknown template: {}",
+                    chain.last().map_or("", String::as_str),
+                    relation,
+                    reference
+                ))
+            })?;
+
+        if chain.contains(&key) {
+            chain.push(key);
+            return Err(YamlStatError::InvalidConfig(format!(
+                "Template {} cycle: {}",
+                relation,
+                chain.join(" -> ")
+            )));
+        }
+
+        Self::flatten_template(templates, &key, chain)
+    }
+
     /// Serializes templates to JSON format (for saving to database).
     ///
     /// # Returns
@@ -80,7 +525,11 @@ impl StatTemplateManager {
     pub fn templates_to_json(&self) -> Result<String, YamlStatError> {
         let config = StatConfig {
             templates: self.templates.clone(),
-            stats: HashMap::new(),
+            constants: self.constants.clone(),
+            default_transforms: self.default_transforms.clone(),
+            multiplicative_as_additive: self.multiplicative_as_additive,
+            require_explicit_level: self.require_explicit_level,
+            ..StatConfig::default()
         };
         serde_json::to_string(&config)
             .map_err(|e| YamlStatError::InvalidConfig(format!("JSON serialize error: {}", e)))
@@ -100,6 +549,24 @@ impl StatTemplateManager {
         format!("{}:{}", entity_id, stat_type)
     }
 
+    /// Applies `aliases` to the stat type of a (possibly entity-prefixed) stat name.
+    ///
+    /// Every method that takes a stat name or type goes through here, so an old stat name
+    /// registers, resolves, and reports as its canonical stat.
+    fn canonical_stat_name(&self, stat_name: &str) -> String {
+        match stat_name.rsplit_once(':') {
+            Some((entity_id, stat_type)) => {
+                Self::entity_stat_id(entity_id, canonical_name(&self.aliases, stat_type))
+            }
+            None => canonical_name(&self.aliases, stat_name).to_string(),
+        }
+    }
+
+    /// `entity_stat_id` with `aliases` applied (see `canonical_stat_name`).
+    fn canonical_stat_id(&self, entity_id: &str, stat_type: &str) -> String {
+        self.canonical_stat_name(&Self::entity_stat_id(entity_id, stat_type))
+    }
+
     /// Converts entity stat ID to StatId.
     ///
     /// # Arguments
@@ -116,6 +583,9 @@ impl StatTemplateManager {
 
     /// Loads entity parameters from database and applies stats.
     ///
+    /// Every template name is checked before anything is registered, so a typo leaves the
+    /// resolver untouched.
+    ///
     /// # Arguments
     ///
     /// * `resolver` - StatResolver to register stats in
@@ -123,14 +593,29 @@ impl StatTemplateManager {
     ///
     /// # Errors
     ///
-    /// Returns `YamlStatError` if template is not found or parameter resolution fails.
+    /// Returns `YamlStatError::InvalidConfig` naming every missing template, or
+    /// `YamlStatError` if parameter resolution fails.
     pub fn load_entity_stats(
         &mut self,
         resolver: &mut StatResolver,
         entity_configs: Vec<EntityStatConfig>,
     ) -> Result<(), YamlStatError> {
+        let mut missing: Vec<&str> = Vec::new();
+        for config in &entity_configs {
+            let name = config.template_name.as_str();
+            if Self::find_template(&self.templates, name).is_none() && !missing.contains(&name) {
+                missing.push(name);
+            }
+        }
+        if !missing.is_empty() {
+            return Err(YamlStatError::InvalidConfig(format!(
+                "Templates not found: {}",
+                missing.join(", ")
+            )));
+        }
+
         for config in &entity_configs {
-            let stat_id = Self::entity_stat_id(&config.entity_id, &config.stat_type);
+            let stat_id = self.canonical_stat_id(&config.entity_id, &config.stat_type);
             self.apply_template(resolver, &config.template_name, &stat_id, &config.params)?;
         }
 
@@ -145,6 +630,113 @@ impl StatTemplateManager {
         Ok(())
     }
 
+    /// Stores entity-wide parameters used by every template applied to that entity.
+    ///
+    /// `apply_template` merges them (looked up by the `entity_id` prefix of the stat name)
+    /// under the per-call params, so e.g. `level` only needs to be set once per entity.
+    /// Setting params again for the same entity replaces the previous ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity_params` - Entity identifier and its parameters
+    pub fn set_entity_params(&mut self, entity_params: EntityParams) {
+        self.entity_params
+            .insert(entity_params.entity_id, entity_params.params);
+    }
+
+    /// Controls whether scaling sources must specify their level.
+    ///
+    /// By default a `scaling` source without `level` uses 1.0. When required, such a
+    /// source falls back to a `level` param instead and fails if none is available,
+    /// catching templates applied without the level they need. Starts out as the config's
+    /// `require_explicit_level`.
+    ///
+    /// # Arguments
+    ///
+    /// * `require` - Whether a missing level is an error
+    pub fn set_require_explicit_level(&mut self, require: bool) {
+        self.require_explicit_level = require;
+    }
+
+    /// Sets how templates treat a `{{param}}` that is neither passed, stored for the entity,
+    /// nor a constant.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - Error (default), substitute 0.0, or skip the referencing source/transform
+    pub fn set_missing_param_policy(&mut self, policy: MissingParamPolicy) {
+        self.missing_param_policy = policy;
+    }
+
+    /// Rejects any entity stat that resolves outside `range` (e.g. values inflated by a
+    /// tampered config on an authoritative server).
+    ///
+    /// The guard applies to `resolve_entity_stat` and every method built on it. NaN is always
+    /// out of policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - Allowed resolved values, inclusive
+    pub fn with_value_guard(&mut self, range: RangeInclusive<f64>) {
+        self.value_guard = Some(range);
+    }
+
+    /// Sets the template tags whose stats are not scaled by `resolve_entity_stat_scaled`
+    /// (e.g. `["percent"]` for crit chance or resistances).
+    ///
+    /// # Arguments
+    ///
+    /// * `tags` - Exempt tags, replacing any set before
+    pub fn set_difficulty_exempt_tags<I, S>(&mut self, tags: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.difficulty_exempt_tags = tags.into_iter().map(Into::into).collect();
+    }
+
+    /// Returns the cached entity IDs that start with `prefix`, sorted.
+    ///
+    /// Entity IDs can be hierarchical (e.g. `guild1:player3`), so a prefix such as
+    /// `"guild1:"` selects every entity in that guild. Include the trailing colon to avoid
+    /// also matching `guild10:...`.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - Entity ID prefix to match (empty matches every entity)
+    pub fn entities_matching(&self, prefix: &str) -> Vec<String> {
+        let mut entities: Vec<String> = self
+            .entity_configs
+            .keys()
+            .filter(|entity_id| entity_id.starts_with(prefix))
+            .cloned()
+            .collect();
+        entities.sort();
+        entities
+    }
+
+    /// Clears all cached entity stat configurations, the recorded template entries, and the
+    /// entity params stored with `set_entity_params`.
+    pub fn clear_entity_cache(&mut self) {
+        self.entity_configs.clear();
+        self.entity_params.clear();
+        self.registered.lock().clear();
+    }
+
+    /// Clears the entity cache (including stored entity params) and replaces the resolver
+    /// with an empty one.
+    ///
+    /// `StatResolver` has no way to unregister sources or transforms, so the resolver is
+    /// recreated in place. Use this to reuse a manager and resolver across simulation runs.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - StatResolver to reset
+    pub fn reset(&mut self, resolver: &mut StatResolver) {
+        self.clear_entity_cache();
+        *resolver = StatResolver::new();
+    }
+
     /// Loads stats for a single entity.
     ///
     /// # Arguments
@@ -163,7 +755,7 @@ impl StatTemplateManager {
         stat_configs: Vec<EntityStatConfig>,
     ) -> Result<(), YamlStatError> {
         for config in stat_configs {
-            let stat_id = Self::entity_stat_id(entity_id, &config.stat_type);
+            let stat_id = self.canonical_stat_id(entity_id, &config.stat_type);
             self.apply_template(resolver, &config.template_name, &stat_id, &config.params)?;
         }
         Ok(())
@@ -171,6 +763,12 @@ impl StatTemplateManager {
 
     /// Resolves an entity stat.
     ///
+    /// zzstat labels sources by position (`Source #1`, ...); for stats registered through
+    /// `apply_template` (or `load_entity_stats`), sources that have a `name` in the template
+    /// are relabeled with it. The resolver's cache ignores the context, so call
+    /// `resolver.invalidate_all()` first if the context changed since the last resolve (see
+    /// `resolve_in_context`).
+    ///
     /// # Arguments
     ///
     /// * `resolver` - StatResolver containing the stat
@@ -184,7 +782,8 @@ impl StatTemplateManager {
     ///
     /// # Errors
     ///
-    /// Returns `YamlStatError` if stat resolution fails.
+    /// Returns `YamlStatError` if stat resolution fails, or `YamlStatError::InvalidConfig`
+    /// if the value is outside the value guard (see `with_value_guard`).
     pub fn resolve_entity_stat(
         &self,
         resolver: &mut StatResolver,
@@ -192,85 +791,1123 @@ impl StatTemplateManager {
         stat_type: &str,
         context: &zzstat::StatContext,
     ) -> Result<zzstat::ResolvedStat, YamlStatError> {
-        let stat_id = StatId::from_str(&Self::entity_stat_id(entity_id, stat_type));
-        Ok(resolver.resolve(&stat_id, context)?)
+        let stat_id = StatId::from_str(&self.canonical_stat_id(entity_id, stat_type));
+        let mut resolved = resolver.resolve(&stat_id, context)?;
+        if let Some(guard) = &self.value_guard
+            && !guard.contains(&resolved.value)
+        {
+            return Err(YamlStatError::InvalidConfig(format!(
+                "value out of policy: {} = {} (allowed {}..={})",
+                stat_id,
+                resolved.value,
+                guard.start(),
+                guard.end()
+            )));
+        }
+        self.label_sources(stat_id.as_str(), &mut resolved);
+        Ok(resolved)
     }
 
-    /// Adds a source directly to the resolver (can be used for equipment, buffs, etc.).
+    /// Resolves an entity stat and multiplies it by a difficulty factor.
+    ///
+    /// Stats whose cached template has a tag set with `set_difficulty_exempt_tags` are
+    /// returned unscaled. Stats without a cached template are always scaled.
     ///
     /// # Arguments
     ///
-    /// * `resolver` - StatResolver to add source to
+    /// * `resolver` - StatResolver containing the stat
     /// * `entity_id` - Entity identifier
     /// * `stat_type` - Stat type name
-    /// * `source` - Source to add
-    pub fn add_source_to_entity(
+    /// * `difficulty` - Multiplier applied to the resolved value (1.0 = unchanged)
+    /// * `context` - StatContext for resolution
+    ///
+    /// # Returns
+    ///
+    /// The (possibly scaled) value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError` if stat resolution fails.
+    pub fn resolve_entity_stat_scaled(
         &self,
         resolver: &mut StatResolver,
         entity_id: &str,
         stat_type: &str,
-        source: Box<dyn StatSource>,
-    ) {
-        let stat_id = Self::get_entity_stat_id(entity_id, stat_type);
-        resolver.register_source(stat_id, source);
+        difficulty: f64,
+        context: &zzstat::StatContext,
+    ) -> Result<f64, YamlStatError> {
+        let value = self
+            .resolve_entity_stat(resolver, entity_id, stat_type, context)?
+            .value;
+        let exempt = self.cached_template(entity_id, stat_type).is_some_and(|t| {
+            t.tags
+                .iter()
+                .any(|tag| self.difficulty_exempt_tags.contains(tag))
+        });
+        Ok(if exempt { value } else { value * difficulty })
     }
 
-    /// Adds a transform directly to the resolver (can be used for buffs, debuffs, etc.).
+    /// Resolves several entity stats with `resolve_entity_stat_scaled`.
     ///
     /// # Arguments
     ///
-    /// * `resolver` - StatResolver to add transform to
+    /// * `resolver` - StatResolver containing the stats
     /// * `entity_id` - Entity identifier
-    /// * `stat_type` - Stat type name
-    /// * `transform` - Transform to add
-    pub fn add_transform_to_entity(
+    /// * `stat_types` - Stat type names
+    /// * `difficulty` - Multiplier applied to each non-exempt value
+    /// * `context` - StatContext for resolution
+    ///
+    /// # Returns
+    ///
+    /// Stat type to (possibly scaled) value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError` if any stat resolution fails.
+    pub fn resolve_entity_stats_scaled(
         &self,
         resolver: &mut StatResolver,
         entity_id: &str,
-        stat_type: &str,
-        transform: Box<dyn StatTransform>,
-    ) {
-        let stat_id = Self::get_entity_stat_id(entity_id, stat_type);
-        resolver.register_transform(stat_id, transform);
+        stat_types: &[&str],
+        difficulty: f64,
+        context: &zzstat::StatContext,
+    ) -> Result<HashMap<String, f64>, YamlStatError> {
+        stat_types
+            .iter()
+            .map(|&stat_type| {
+                let value = self.resolve_entity_stat_scaled(
+                    resolver, entity_id, stat_type, difficulty, context,
+                )?;
+                Ok((stat_type.to_string(), value))
+            })
+            .collect()
     }
 
-    /// Converts entity parameters to database format.
+    /// Resolves an entity stat and also returns its pre-transform base (for combat logs).
+    ///
+    /// The base is the sum of the breakdown's sources plus the leading stat-derived additions:
+    /// stat-dependent sources (stat-level scaling, percentage, `@stat` constant), which run as
+    /// the first transforms, and the `map` transforms directly after them, which add
+    /// dependency stats the same way. Everything from the first other transform on
+    /// (multipliers, clamps, ...) counts towards the final value only. The entries recorded
+    /// by `apply_template` tell the steps apart.
     ///
     /// # Arguments
     ///
+    /// * `resolver` - StatResolver containing the stat
     /// * `entity_id` - Entity identifier
-    /// * `stat_mappings` - Vector of (stat_type, template_name, params) tuples
+    /// * `stat_type` - Stat type name
+    /// * `context` - StatContext for resolution
     ///
     /// # Returns
     ///
-    /// Vector of EntityStatConfig instances.
-    pub fn entity_params_to_configs(
+    /// `(source sum, final value)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError` if stat resolution fails.
+    pub fn resolve_with_base(
+        &self,
+        resolver: &mut StatResolver,
         entity_id: &str,
-        stat_mappings: &[(String, String, HashMap<String, f64>)],
-    ) -> Vec<EntityStatConfig> {
-        stat_mappings
+        stat_type: &str,
+        context: &zzstat::StatContext,
+    ) -> Result<(f64, f64), YamlStatError> {
+        let resolved = self.resolve_entity_stat(resolver, entity_id, stat_type, context)?;
+        let stat_id = self.canonical_stat_id(entity_id, stat_type);
+        let entries = self.registered.entries(&stat_id);
+        let transforms = entries
             .iter()
-            .map(|(stat_type, template_name, params)| EntityStatConfig {
-                entity_id: entity_id.to_string(),
-                stat_type: stat_type.clone(),
-                template_name: template_name.clone(),
-                params: params.clone(),
-            })
-            .collect()
+            .filter(|entry| !matches!(entry, RegisteredEntry::Source(_)));
+
+        let mut base: f64 = resolved.sources.iter().map(|(_, value)| value).sum();
+        for (entry, (_, value)) in transforms.zip(&resolved.transforms) {
+            match entry {
+                RegisteredEntry::SourceTransform(_)
+                | RegisteredEntry::Transform(TransformConfig::Map { .. }) => base = *value,
+                _ => break,
+            }
+        }
+        Ok((base, resolved.value))
     }
 
-    /// Applies a template with parameters to a StatResolver.
+    /// Looks up the cached config of an entity stat loaded via `load_entity_stats`.
+    fn cached_config(&self, entity_id: &str, stat_type: &str) -> Option<&EntityStatConfig> {
+        self.entity_configs.get(entity_id).and_then(|configs| {
+            let stat_type = canonical_name(&self.aliases, stat_type);
+            configs
+                .iter()
+                .find(|c| canonical_name(&self.aliases, &c.stat_type) == stat_type)
+        })
+    }
+
+    /// Looks up the template an entity stat was loaded from via `load_entity_stats`.
+    fn cached_template(&self, entity_id: &str, stat_type: &str) -> Option<&StatTemplate> {
+        self.cached_config(entity_id, stat_type)
+            .and_then(|config| Self::find_template(&self.templates, &config.template_name))
+    }
+
+    /// Replaces positional labels with the source names recorded when the stat was applied.
     ///
-    /// # Arguments
+    /// Stat-dependent sources run as transforms, so their names label transform steps.
+    fn label_sources(&self, stat_id: &str, resolved: &mut zzstat::ResolvedStat) {
+        let entries = self.registered.entries(stat_id);
+        let (sources, transforms): (Vec<_>, Vec<_>) = entries
+            .iter()
+            .partition(|entry| matches!(entry, RegisteredEntry::Source(_)));
+
+        for ((label, _), entry) in resolved.sources.iter_mut().zip(sources) {
+            if let RegisteredEntry::Source(Some(name)) = entry {
+                *label = name.clone();
+            }
+        }
+        for ((label, _), entry) in resolved.transforms.iter_mut().zip(transforms) {
+            if let RegisteredEntry::SourceTransform(Some(name)) = entry {
+                *label = name.clone();
+            }
+        }
+    }
+
+    /// Resolves an entity stat and reports whether any clamp or conditional was active.
     ///
-    /// * `resolver` - StatResolver to register stats in
-    /// * `template_name` - Name of the template to apply
-    /// * `stat_name` - Name for the stat (can be entity_id:stat_type format)
-    /// * `params` - Parameters to substitute in the template
+    /// Flags are derived by matching the resolved breakdown against the transforms that
+    /// `apply_template` (or `load_entity_stats`) registered for the stat. Transforms added
+    /// directly to the resolver are not inspected, and stats not applied from a template
+    /// report no flags.
     ///
-    /// # Errors
+    /// # Arguments
     ///
-    /// Returns `YamlStatError` if template is not found or parameter resolution fails.
+    /// * `resolver` - StatResolver containing the stat
+    /// * `entity_id` - Entity identifier
+    /// * `stat_type` - Stat type name
+    /// * `context` - StatContext for resolution
+    ///
+    /// # Returns
+    ///
+    /// The resolved value and its `StatFlags`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError` if stat resolution fails.
+    pub fn resolve_entity_stat_flagged(
+        &self,
+        resolver: &mut StatResolver,
+        entity_id: &str,
+        stat_type: &str,
+        context: &zzstat::StatContext,
+    ) -> Result<(f64, StatFlags), YamlStatError> {
+        use crate::transform_conditional::{context_flag, evaluate_condition, in_range};
+
+        let resolved = self.resolve_entity_stat(resolver, entity_id, stat_type, context)?;
+        let mut flags = StatFlags::default();
+
+        let stat_id = self.canonical_stat_id(entity_id, stat_type);
+        let entries = self.registered.entries(&stat_id);
+        let transforms = entries
+            .iter()
+            .filter(|entry| !matches!(entry, RegisteredEntry::Source(_)));
+
+        let mut previous = resolved.sources.iter().map(|(_, value)| value).sum::<f64>();
+        for (entry, (_, value)) in transforms.zip(&resolved.transforms) {
+            let RegisteredEntry::Transform(config) = entry else {
+                previous = *value;
+                continue;
+            };
+            match config {
+                TransformConfig::Clamp { .. } => {
+                    flags.clamped_min |= *value > previous;
+                    flags.clamped_max |= *value < previous;
+                }
+                TransformConfig::Conditional {
+                    condition_stat,
+                    condition_context,
+                    all,
+                    any,
+                    condition_value,
+                    operator,
+                    ..
+                } => {
+                    let stat_value = |stat: &str| {
+                        resolver
+                            .get_breakdown(&Self::dependency_stat_id(entity_id, stat))
+                            .map(|r| r.value)
+                            .unwrap_or(0.0)
+                    };
+                    let holds = |c: &SubCondition| {
+                        evaluate_condition(
+                            &c.operator,
+                            stat_value(&c.condition_stat),
+                            c.condition_value,
+                        )
+                    };
+                    flags.conditional_fired |= if !all.is_empty() {
+                        all.iter()
+                            .map(holds)
+                            .collect::<Result<Vec<_>, _>>()?
+                            .into_iter()
+                            .all(|held| held)
+                    } else if !any.is_empty() {
+                        any.iter()
+                            .map(holds)
+                            .collect::<Result<Vec<_>, _>>()?
+                            .into_iter()
+                            .any(|held| held)
+                    } else {
+                        let value = match (condition_stat, condition_context) {
+                            (Some(condition_stat), _) => stat_value(condition_stat),
+                            (None, Some(key)) => context_flag(context, key),
+                            (None, None) => 0.0,
+                        };
+                        evaluate_condition(operator, value, *condition_value)?
+                    };
+                }
+                TransformConfig::Range {
+                    condition_stat,
+                    min,
+                    max,
+                    inclusive,
+                    ..
+                } => {
+                    let condition_id = Self::dependency_stat_id(entity_id, condition_stat);
+                    let stat_value = resolver
+                        .get_breakdown(&condition_id)
+                        .map(|r| r.value)
+                        .unwrap_or(0.0);
+                    flags.conditional_fired |=
+                        in_range(stat_value, *min, *max, inclusive.unwrap_or(true));
+                }
+                TransformConfig::ConditionalStat {
+                    condition_stat,
+                    condition_value,
+                    operator,
+                    ..
+                } => {
+                    let condition_id = Self::dependency_stat_id(entity_id, condition_stat);
+                    let stat_value = resolver
+                        .get_breakdown(&condition_id)
+                        .map(|r| r.value)
+                        .unwrap_or(0.0);
+                    flags.conditional_fired |=
+                        evaluate_condition(operator, stat_value, *condition_value)?;
+                }
+                _ => {}
+            }
+            previous = *value;
+        }
+
+        Ok((resolved.value, flags))
+    }
+
+    /// Resolves an entity stat once per named context (e.g. PvE vs PvP).
+    ///
+    /// The resolver's cache does not depend on the context, so it is invalidated before each
+    /// resolution and again afterwards so no context-specific values linger.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - StatResolver containing the stat
+    /// * `entity_id` - Entity identifier
+    /// * `stat_type` - Stat type name
+    /// * `contexts` - (name, context) pairs to resolve against
+    ///
+    /// # Returns
+    ///
+    /// HashMap of context name to resolved value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError` if stat resolution fails under any context.
+    pub fn resolve_entity_stat_multi(
+        &self,
+        resolver: &mut StatResolver,
+        entity_id: &str,
+        stat_type: &str,
+        contexts: &[(&str, &zzstat::StatContext)],
+    ) -> Result<HashMap<String, f64>, YamlStatError> {
+        let mut values = HashMap::new();
+        for (name, context) in contexts {
+            resolver.invalidate_all();
+            let resolved = self.resolve_entity_stat(resolver, entity_id, stat_type, context)?;
+            values.insert(name.to_string(), resolved.value);
+        }
+        resolver.invalidate_all();
+        Ok(values)
+    }
+
+    /// Estimates how much an entity stat changes per unit of a param.
+    ///
+    /// `resolver_builder` is called twice to build scratch resolvers, once with `base_params`
+    /// and once with `param` increased by `delta`; the result is `(hi - lo) / delta`.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver_builder` - Builds a resolver containing the entity's stats for given params
+    /// * `entity_id` - Entity identifier
+    /// * `stat_type` - Stat type name
+    /// * `param` - Param to vary
+    /// * `base_params` - Params to start from (must contain `param`)
+    /// * `delta` - Step size
+    /// * `context` - StatContext for resolution
+    ///
+    /// # Returns
+    ///
+    /// The finite-difference estimate of the marginal value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if `delta` is zero or `param` is not in
+    /// `base_params`, or any error from `resolver_builder` or stat resolution.
+    #[allow(clippy::too_many_arguments)]
+    pub fn marginal(
+        &self,
+        resolver_builder: impl Fn(&HashMap<String, f64>) -> Result<StatResolver, YamlStatError>,
+        entity_id: &str,
+        stat_type: &str,
+        param: &str,
+        base_params: &HashMap<String, f64>,
+        delta: f64,
+        context: &zzstat::StatContext,
+    ) -> Result<f64, YamlStatError> {
+        if delta == 0.0 {
+            return Err(YamlStatError::InvalidConfig(
+                "marginal delta must not be zero".to_string(),
+            ));
+        }
+        let Some(&base) = base_params.get(param) else {
+            return Err(YamlStatError::InvalidConfig(format!(
+                "Param '{}' not found in base params",
+                param
+            )));
+        };
+
+        let resolve = |params: &HashMap<String, f64>| {
+            let mut resolver = resolver_builder(params)?;
+            self.resolve_entity_stat(&mut resolver, entity_id, stat_type, context)
+                .map(|resolved| resolved.value)
+        };
+        let lo = resolve(base_params)?;
+        let mut raised = base_params.clone();
+        raised.insert(param.to_string(), base + delta);
+        let hi = resolve(&raised)?;
+
+        Ok((hi - lo) / delta)
+    }
+
+    /// Applies the same templates with two param sets and returns every stat side by side.
+    ///
+    /// Each param set is applied to its own scratch resolver, so the two sheets cannot
+    /// affect each other. Useful for before/after comparisons such as a level up.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_applications` - (template name, stat name) pairs, as for `apply_template`
+    /// * `params_a` - First param set
+    /// * `params_b` - Second param set
+    /// * `context` - StatContext for resolution
+    ///
+    /// # Returns
+    ///
+    /// Stat name to (value with `params_a`, value with `params_b`).
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError` if applying a template or resolving a stat fails.
+    pub fn compare_param_sets(
+        &self,
+        template_applications: &[(&str, &str)],
+        params_a: &HashMap<String, f64>,
+        params_b: &HashMap<String, f64>,
+        context: &zzstat::StatContext,
+    ) -> Result<HashMap<String, (f64, f64)>, YamlStatError> {
+        let sheet = |params: &HashMap<String, f64>| {
+            let mut resolver = StatResolver::new();
+            for (template_name, stat_name) in template_applications {
+                self.apply_named_template(&mut resolver, template_name, stat_name, params, false)?;
+            }
+            template_applications
+                .iter()
+                .map(|(_, stat_name)| {
+                    let resolved = resolver.resolve(&StatId::from_str(stat_name), context)?;
+                    Ok((stat_name.to_string(), resolved.value))
+                })
+                .collect::<Result<HashMap<_, _>, YamlStatError>>()
+        };
+        let a = sheet(params_a)?;
+        let mut b = sheet(params_b)?;
+        Ok(a.into_iter()
+            .map(|(stat, value_a)| {
+                let value_b = b.remove(&stat).unwrap_or_default();
+                (stat, (value_a, value_b))
+            })
+            .collect())
+    }
+
+    /// Resolves every cached stat of an entity and records how long each one took.
+    ///
+    /// Only stats loaded through `load_entity_stats` (and therefore cached on the manager)
+    /// are included.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - StatResolver containing the stats
+    /// * `entity_id` - Entity identifier
+    /// * `context` - StatContext for resolution
+    ///
+    /// # Returns
+    ///
+    /// A tuple of (stat_type -> value, stat_type -> resolve duration).
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError` if any stat resolution fails.
+    pub fn resolve_sheet_timed(
+        &self,
+        resolver: &mut StatResolver,
+        entity_id: &str,
+        context: &zzstat::StatContext,
+    ) -> Result<TimedSheet, YamlStatError> {
+        let mut values = HashMap::new();
+        let mut timings = HashMap::new();
+
+        for stat_type in self.cached_stat_types(entity_id) {
+            let start = Instant::now();
+            let resolved = self.resolve_entity_stat(resolver, entity_id, stat_type, context)?;
+            timings.insert(stat_type.to_string(), start.elapsed());
+            values.insert(stat_type.to_string(), resolved.value);
+        }
+
+        Ok((values, timings))
+    }
+
+    /// Resolves every cached stat of an entity, collecting failures instead of stopping.
+    ///
+    /// zzstat resolves the whole graph in one pass, so one broken stat can make unrelated
+    /// stats fail in `resolver`. Such stats are retried in a scratch resolver that holds only
+    /// the stat and its cached dependencies (transforms added directly to `resolver` are not
+    /// part of the retry). A stat is reported as failed if the retry fails too.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - StatResolver containing the stats
+    /// * `entity_id` - Entity identifier
+    /// * `context` - StatContext for resolution
+    ///
+    /// # Returns
+    ///
+    /// A tuple of (stat_type -> value, (stat_type, error) failures sorted by stat type).
+    pub fn resolve_sheet_lenient(
+        &self,
+        resolver: &mut StatResolver,
+        entity_id: &str,
+        context: &zzstat::StatContext,
+    ) -> (HashMap<String, f64>, Vec<(String, YamlStatError)>) {
+        let mut values = HashMap::new();
+        let mut failures = Vec::new();
+
+        for stat_type in self.cached_stat_types(entity_id) {
+            let resolved = self
+                .resolve_entity_stat(resolver, entity_id, stat_type, context)
+                .or_else(|error| {
+                    self.resolve_isolated(entity_id, stat_type, context)
+                        .map_err(|_| error)
+                });
+            match resolved {
+                Ok(resolved) => {
+                    values.insert(stat_type.to_string(), resolved.value);
+                }
+                Err(error) => failures.push((stat_type.to_string(), error)),
+            }
+        }
+
+        failures.sort_by(|a, b| a.0.cmp(&b.0));
+        (values, failures)
+    }
+
+    /// Resolves a cached stat in a fresh resolver holding only it and its cached dependencies.
+    fn resolve_isolated(
+        &self,
+        entity_id: &str,
+        stat_type: &str,
+        context: &zzstat::StatContext,
+    ) -> Result<zzstat::ResolvedStat, YamlStatError> {
+        let mut scratch = StatResolver::new();
+        let mut pending = vec![stat_type];
+        let mut applied = HashSet::new();
+
+        while let Some(stat_type) = pending.pop() {
+            if !applied.insert(stat_type) {
+                continue;
+            }
+            if let Some(config) = self.cached_config(entity_id, stat_type) {
+                let stat_id = self.canonical_stat_id(entity_id, stat_type);
+                self.apply_named_template(
+                    &mut scratch,
+                    &config.template_name,
+                    &stat_id,
+                    &config.params,
+                    false,
+                )?;
+            }
+            pending.extend(self.cached_stat_dependencies(entity_id, stat_type));
+        }
+
+        self.resolve_entity_stat(&mut scratch, entity_id, stat_type, context)
+    }
+
+    /// Resolves an entity stat and records everything that went into the result.
+    ///
+    /// The template, params, and dependencies come from the stat's cached config (see
+    /// `load_entity_stats`); for uncached stats only the breakdown is recorded.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - StatResolver containing the stat
+    /// * `entity_id` - Entity identifier
+    /// * `stat_type` - Stat type name
+    /// * `context` - StatContext for resolution
+    ///
+    /// # Returns
+    ///
+    /// The resolved value and its serializable `AuditRecord`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError` if stat resolution fails.
+    pub fn resolve_audited(
+        &self,
+        resolver: &mut StatResolver,
+        entity_id: &str,
+        stat_type: &str,
+        context: &zzstat::StatContext,
+    ) -> Result<(f64, AuditRecord), YamlStatError> {
+        let resolved = self.resolve_entity_stat(resolver, entity_id, stat_type, context)?;
+        let config = self.cached_config(entity_id, stat_type);
+        let template = self.cached_template(entity_id, stat_type);
+
+        let params = match (config, template) {
+            (Some(config), Some(template)) => {
+                let effective = self.effective_params(entity_id, &template.params, &config.params);
+                template
+                    .required_params()
+                    .into_iter()
+                    .filter_map(|name| Some((name.clone(), *effective.get(&name)?)))
+                    .collect()
+            }
+            _ => BTreeMap::new(),
+        };
+
+        let dependencies = self
+            .cached_stat_dependencies(entity_id, stat_type)
+            .into_iter()
+            .filter_map(|name| {
+                let id = Self::dependency_stat_id(entity_id, name);
+                Some((name.to_string(), resolver.get_breakdown(&id)?.value))
+            })
+            .collect();
+
+        let config_version = template
+            .map(|t| {
+                serde_json::to_string(t).map(|json| format!("{:016x}", fnv1a(json.as_bytes())))
+            })
+            .transpose()?;
+
+        let record = AuditRecord {
+            entity_id: entity_id.to_string(),
+            stat_type: stat_type.to_string(),
+            template_name: config.map(|c| c.template_name.clone()),
+            config_version,
+            params,
+            dependencies,
+            sources: resolved.sources,
+            transforms: resolved.transforms,
+            value: resolved.value,
+        };
+        Ok((resolved.value, record))
+    }
+
+    /// Sums an entity's weighted stats into a single "power budget" for balancing.
+    ///
+    /// Only stats listed in `weights` are resolved; any other stat has weight 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - StatResolver containing the stats
+    /// * `entity_id` - Entity identifier
+    /// * `weights` - Stat type to weight
+    /// * `context` - StatContext for resolution
+    ///
+    /// # Returns
+    ///
+    /// `Σ value * weight` over the weighted stats.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError` if a weighted stat fails to resolve.
+    pub fn power_budget(
+        &self,
+        resolver: &mut StatResolver,
+        entity_id: &str,
+        weights: &HashMap<String, f64>,
+        context: &zzstat::StatContext,
+    ) -> Result<f64, YamlStatError> {
+        // Sorted so the floating-point sum doesn't depend on map iteration order
+        let mut weighted: Vec<(&String, &f64)> = weights
+            .iter()
+            .filter(|(_, weight)| **weight != 0.0)
+            .collect();
+        weighted.sort_by_key(|(stat_type, _)| *stat_type);
+
+        let mut budget = 0.0;
+        for (stat_type, weight) in weighted {
+            let resolved = self.resolve_entity_stat(resolver, entity_id, stat_type, context)?;
+            budget += resolved.value * weight;
+        }
+        Ok(budget)
+    }
+
+    /// Resolves stats for several entities and renders them as CSV.
+    ///
+    /// The header row is `entity` followed by the stat types; each entity gets one row.
+    /// Stats that were never registered for an entity are rendered as empty cells.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - StatResolver containing the stats
+    /// * `entity_ids` - Entities to export, one row each
+    /// * `stat_types` - Stat types to export, one column each
+    /// * `context` - StatContext for resolution
+    ///
+    /// # Returns
+    ///
+    /// CSV text with a trailing newline after every row.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError` if any stat resolution fails.
+    pub fn entities_to_csv(
+        &self,
+        resolver: &mut StatResolver,
+        entity_ids: &[&str],
+        stat_types: &[&str],
+        context: &zzstat::StatContext,
+    ) -> Result<String, YamlStatError> {
+        let mut csv = String::new();
+        let header: Vec<String> = std::iter::once("entity")
+            .chain(stat_types.iter().copied())
+            .map(csv_field)
+            .collect();
+        csv.push_str(&header.join(","));
+        csv.push('\n');
+
+        for entity_id in entity_ids {
+            let mut row = vec![csv_field(entity_id)];
+            for stat_type in stat_types {
+                let cell = match self.resolve_entity_stat(resolver, entity_id, stat_type, context) {
+                    Ok(resolved) => resolved.value.to_string(),
+                    Err(YamlStatError::ResolutionError(zzstat::StatError::MissingSource(id)))
+                        if id.as_str() == self.canonical_stat_id(entity_id, stat_type) =>
+                    {
+                        String::new()
+                    }
+                    Err(e) => return Err(e),
+                };
+                row.push(cell);
+            }
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+
+        Ok(csv)
+    }
+
+    /// Resolves an entity stat after checking its dependency chain is at most `max_depth` deep.
+    ///
+    /// The depth is computed from the templates of the entity's cached stats (see
+    /// `load_entity_stats`) before anything is resolved, so cyclic or runaway graphs from
+    /// user-generated configs are rejected up front. Stats without a cached config count as
+    /// base stats (depth 0).
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - StatResolver containing the stat
+    /// * `entity_id` - Entity identifier
+    /// * `stat_type` - Stat type name
+    /// * `context` - StatContext for resolution
+    /// * `max_depth` - Maximum allowed dependency depth (0 allows only base stats)
+    ///
+    /// # Returns
+    ///
+    /// Resolved stat value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if the depth limit is exceeded, or any error
+    /// `resolve_entity_stat` can return.
+    pub fn resolve_entity_stat_bounded(
+        &self,
+        resolver: &mut StatResolver,
+        entity_id: &str,
+        stat_type: &str,
+        context: &zzstat::StatContext,
+        max_depth: usize,
+    ) -> Result<zzstat::ResolvedStat, YamlStatError> {
+        self.dependency_depth(entity_id, stat_type, 0, max_depth)?;
+        self.resolve_entity_stat(resolver, entity_id, stat_type, context)
+    }
+
+    /// Computes the dependency depth of an entity stat, failing once `max_depth` is exceeded.
+    fn dependency_depth(
+        &self,
+        entity_id: &str,
+        stat_type: &str,
+        current_depth: usize,
+        max_depth: usize,
+    ) -> Result<usize, YamlStatError> {
+        if current_depth > max_depth {
+            return Err(YamlStatError::InvalidConfig(format!(
+                "max resolution depth exceeded: {} is deeper than {}",
+                self.canonical_stat_id(entity_id, stat_type),
+                max_depth
+            )));
+        }
+
+        let mut depth = 0;
+        for dependency in self.cached_stat_dependencies(entity_id, stat_type) {
+            let dependency_depth =
+                self.dependency_depth(entity_id, dependency, current_depth + 1, max_depth)?;
+            depth = depth.max(dependency_depth + 1);
+        }
+        Ok(depth)
+    }
+
+    /// Lists an entity's cached stats with their dependency depth, shallowest first.
+    ///
+    /// Base stats have depth 0; a stat's depth is one more than its deepest dependency.
+    /// Ties are ordered by stat type. Dependencies that close a cycle are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity_id` - Entity identifier
+    ///
+    /// # Returns
+    ///
+    /// Vector of (stat_type, depth) pairs.
+    pub fn entity_stats_by_depth(&self, entity_id: &str) -> Vec<(String, usize)> {
+        let mut depths = HashMap::new();
+        let mut visiting = HashSet::new();
+        let mut stats: Vec<(String, usize)> = self
+            .cached_stat_types(entity_id)
+            .into_iter()
+            .map(|stat_type| {
+                let depth = self.memoized_depth(entity_id, stat_type, &mut depths, &mut visiting);
+                (stat_type.to_string(), depth)
+            })
+            .collect();
+        stats.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        stats.dedup();
+        stats
+    }
+
+    /// Computes a stat's dependency depth, caching results and skipping cyclic edges.
+    fn memoized_depth<'a>(
+        &'a self,
+        entity_id: &str,
+        stat_type: &'a str,
+        depths: &mut HashMap<&'a str, usize>,
+        visiting: &mut HashSet<&'a str>,
+    ) -> usize {
+        if let Some(&depth) = depths.get(stat_type) {
+            return depth;
+        }
+        if !visiting.insert(stat_type) {
+            return 0;
+        }
+
+        let mut depth = 0;
+        for dependency in self.cached_stat_dependencies(entity_id, stat_type) {
+            if !visiting.contains(dependency) {
+                let dependency_depth = self.memoized_depth(entity_id, dependency, depths, visiting);
+                depth = depth.max(dependency_depth + 1);
+            }
+        }
+
+        visiting.remove(stat_type);
+        depths.insert(stat_type, depth);
+        depth
+    }
+
+    /// Returns the stat types a cached entity stat depends on (via its template).
+    fn cached_stat_dependencies(&self, entity_id: &str, stat_type: &str) -> Vec<&str> {
+        let Some(template) = self.cached_template(entity_id, stat_type) else {
+            return Vec::new();
+        };
+
+        let mut dependencies = template.stat_refs();
+        if !template.ignore_default_transforms {
+            dependencies.extend(
+                self.default_transforms
+                    .iter()
+                    .flat_map(TransformConfig::stat_refs),
+            );
+        }
+        dependencies
+    }
+
+    /// Returns the stat types cached for an entity (in load order).
+    fn cached_stat_types(&self, entity_id: &str) -> Vec<&str> {
+        self.entity_configs
+            .get(entity_id)
+            .map(|configs| configs.iter().map(|c| c.stat_type.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Adds a source directly to the resolver (can be used for equipment, buffs, etc.).
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - StatResolver to add source to
+    /// * `entity_id` - Entity identifier
+    /// * `stat_type` - Stat type name
+    /// * `source` - Source to add
+    pub fn add_source_to_entity(
+        &self,
+        resolver: &mut StatResolver,
+        entity_id: &str,
+        stat_type: &str,
+        source: Box<dyn StatSource>,
+    ) {
+        let stat_id = StatId::from_str(&self.canonical_stat_id(entity_id, stat_type));
+        resolver.register_source(stat_id, source);
+    }
+
+    /// Adds a transform directly to the resolver (can be used for buffs, debuffs, etc.).
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - StatResolver to add transform to
+    /// * `entity_id` - Entity identifier
+    /// * `stat_type` - Stat type name
+    /// * `transform` - Transform to add
+    pub fn add_transform_to_entity(
+        &self,
+        resolver: &mut StatResolver,
+        entity_id: &str,
+        stat_type: &str,
+        transform: Box<dyn StatTransform>,
+    ) {
+        let stat_id = StatId::from_str(&self.canonical_stat_id(entity_id, stat_type));
+        resolver.register_transform(stat_id, transform);
+    }
+
+    /// Adds an additive transform whose value can be updated later without re-registering.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - StatResolver to add transform to
+    /// * `entity_id` - Entity identifier
+    /// * `stat_type` - Stat type name
+    /// * `value` - Initial value to add
+    ///
+    /// # Returns
+    ///
+    /// A handle whose `set` changes the value and invalidates the resolver's cache.
+    pub fn add_mutable_transform_to_entity(
+        &self,
+        resolver: &mut StatResolver,
+        entity_id: &str,
+        stat_type: &str,
+        value: f64,
+    ) -> MutableAdditiveHandle {
+        let transform = MutableAdditiveTransform::new(value);
+        let handle = transform.handle();
+        self.add_transform_to_entity(resolver, entity_id, stat_type, Box::new(transform));
+        handle
+    }
+
+    /// Applies the bonuses from several equipment slots to an entity stat.
+    ///
+    /// Flat bonuses are summed and added first, then percent bonuses are combined
+    /// according to `rule` and applied as a single multiplier.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - StatResolver to add transforms to
+    /// * `entity_id` - Entity identifier
+    /// * `stat_type` - Stat type name
+    /// * `bonuses` - Bonuses from all equipped items
+    /// * `rule` - How percent bonuses stack
+    pub fn apply_equipment(
+        &self,
+        resolver: &mut StatResolver,
+        entity_id: &str,
+        stat_type: &str,
+        bonuses: &[EquipBonus],
+        rule: StackRule,
+    ) {
+        let flat: f64 = bonuses
+            .iter()
+            .filter(|b| b.kind == BonusKind::Flat)
+            .map(|b| b.value)
+            .sum();
+        let mut percents = bonuses
+            .iter()
+            .filter(|b| b.kind == BonusKind::Percent)
+            .map(|b| b.value)
+            .peekable();
+
+        if bonuses.iter().any(|b| b.kind == BonusKind::Flat) {
+            self.add_transform_to_entity(
+                resolver,
+                entity_id,
+                stat_type,
+                Box::new(AdditiveTransform::new(flat)),
+            );
+        }
+        if percents.peek().is_some() {
+            let multiplier = match rule {
+                StackRule::Additive => 1.0 + percents.sum::<f64>(),
+                StackRule::Multiplicative => percents.map(|p| 1.0 + p).product(),
+            };
+            self.add_transform_to_entity(
+                resolver,
+                entity_id,
+                stat_type,
+                Box::new(MultiplicativeTransform::new(multiplier)),
+            );
+        }
+    }
+
+    /// Reconstructs a template from a stat built in a resolver (best-effort).
+    ///
+    /// The stat is resolved (with an empty context, unless cached) and rebuilt from its
+    /// breakdown: every source becomes a constant source, zzstat's multiplicative and additive
+    /// transforms are recreated as such, clamps registered by this manager keep their
+    /// configured bounds, and any other transform (named, dependent, or custom, including a
+    /// clamp registered directly on the resolver) is frozen into an additive step carrying its
+    /// description as the name.
+    /// Re-applying the template therefore reproduces the current value. The template skips
+    /// `default_transforms`, since the breakdown already includes them.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - StatResolver containing the stat
+    /// * `stat_id` - Stat to extract
+    ///
+    /// # Returns
+    ///
+    /// A `StatTemplate` that can be saved or registered for reuse.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError` if the stat fails to resolve.
+    pub fn extract_template(
+        &self,
+        resolver: &mut StatResolver,
+        stat_id: &StatId,
+    ) -> Result<StatTemplate, YamlStatError> {
+        let resolved = resolver.resolve(stat_id, &zzstat::StatContext::new())?;
+
+        let sources = resolved
+            .sources
+            .iter()
+            .enumerate()
+            .map(|(index, (label, value))| SourceConfig::Constant {
+                value: SourceValue::Number(*value),
+                // zzstat's positional labels carry no information
+                name: (*label != format!("Source #{}", index + 1)).then(|| label.clone()),
+            })
+            .collect();
+
+        // zzstat only describes clamps with rounded bounds, so reuse the registered config
+        let registered: Vec<RegisteredEntry> = self
+            .registered
+            .entries(stat_id.as_str())
+            .into_iter()
+            .filter(|entry| !matches!(entry, RegisteredEntry::Source(_)))
+            .collect();
+        let mut previous: f64 = resolved.sources.iter().map(|(_, value)| value).sum();
+        let mut transforms = Vec::new();
+        for (index, (description, value)) in resolved.transforms.iter().enumerate() {
+            let registered_clamp = match registered.get(index) {
+                Some(RegisteredEntry::Transform(
+                    clamp @ TransformConfig::Clamp {
+                        min,
+                        max,
+                        min_stat: None,
+                        max_stat: None,
+                        ..
+                    },
+                )) if [min, max]
+                    .into_iter()
+                    .flatten()
+                    .all(|bound| matches!(bound, SourceValue::Number(_))) =>
+                {
+                    Some(clamp.clone())
+                }
+                _ => None,
+            };
+
+            transforms.push(if let Some(clamp) = registered_clamp {
+                clamp
+            } else if description.starts_with('×') && previous != 0.0 {
+                TransformConfig::Multiplicative {
+                    value: SourceValue::Number(value / previous),
+                    name: None,
+                }
+            } else {
+                TransformConfig::Additive {
+                    value: SourceValue::Number(value - previous),
+                    name: (!description.starts_with('+')).then(|| description.clone()),
+                }
+            });
+            previous = *value;
+        }
+
+        Ok(StatTemplate {
+            sources,
+            transforms,
+            ignore_default_transforms: true,
+            ..StatTemplate::default()
+        })
+    }
+
+    /// Converts entity parameters to database format.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity_id` - Entity identifier
+    /// * `stat_mappings` - Vector of (stat_type, template_name, params) tuples
+    ///
+    /// # Returns
+    ///
+    /// Vector of EntityStatConfig instances.
+    pub fn entity_params_to_configs(
+        entity_id: &str,
+        stat_mappings: &[(String, String, HashMap<String, f64>)],
+    ) -> Vec<EntityStatConfig> {
+        stat_mappings
+            .iter()
+            .map(|(stat_type, template_name, params)| EntityStatConfig {
+                entity_id: entity_id.to_string(),
+                stat_type: stat_type.clone(),
+                template_name: template_name.clone(),
+                params: params.clone(),
+            })
+            .collect()
+    }
+
+    /// Applies a template with parameters to a StatResolver.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - StatResolver to register stats in
+    /// * `template_name` - Name of the template to apply
+    /// * `stat_name` - Name for the stat (can be entity_id:stat_type format)
+    /// * `params` - Parameters to substitute in the template
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError` if template is not found or parameter resolution fails.
     ///
     /// # Example
     ///
@@ -290,54 +1927,441 @@ impl StatTemplateManager {
     /// }
     /// "#;
     ///
-    /// let manager = StatTemplateManager::from_json(json)?;
-    /// let mut resolver = zzstat::StatResolver::new();
-    /// let mut params = HashMap::new();
-    /// params.insert("base_hp".to_string(), 100.0);
+    /// let manager = StatTemplateManager::from_json(json)?;
+    /// let mut resolver = zzstat::StatResolver::new();
+    /// let mut params = HashMap::new();
+    /// params.insert("base_hp".to_string(), 100.0);
+    ///
+    /// manager.apply_template(&mut resolver, "BaseHP", "player1_HP", &params)?;
+    /// # Ok::<(), zzstat_json::YamlStatError>(())
+    /// ```
+    pub fn apply_template(
+        &self,
+        resolver: &mut StatResolver,
+        template_name: &str,
+        stat_name: &str,
+        params: &HashMap<String, f64>,
+    ) -> Result<(), YamlStatError> {
+        self.apply_named_template(resolver, template_name, stat_name, params, true)
+    }
+
+    /// `apply_template`; with `record` unset the manager's recorded entries are left alone,
+    /// for scratch resolvers that must not relabel the real ones.
+    fn apply_named_template(
+        &self,
+        resolver: &mut StatResolver,
+        template_name: &str,
+        stat_name: &str,
+        params: &HashMap<String, f64>,
+        record: bool,
+    ) -> Result<(), YamlStatError> {
+        let template = self.get_template(template_name)?;
+        let literal_sources = self.literal_sources.get(template_name);
+        self.apply_template_config(
+            resolver,
+            template,
+            literal_sources,
+            stat_name,
+            params,
+            record,
+        )
+    }
+
+    /// Registers a templated `stats` entry: its template merged with the entry's own
+    /// sources and transforms (see `StatDefinition::merged_with`), using the entry's params.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError` if the template is not found or parameter resolution fails.
+    pub(crate) fn apply_stat_definition(
+        &self,
+        resolver: &mut StatResolver,
+        template_name: &str,
+        stat_name: &str,
+        definition: &StatDefinition,
+    ) -> Result<(), YamlStatError> {
+        let template = definition.merged_with(self.get_template(template_name)?);
+        self.apply_template_config(
+            resolver,
+            &template,
+            None,
+            stat_name,
+            &definition.params,
+            true,
+        )
+    }
+
+    /// Applies a template with typed params, for selecting stats or branches by string.
+    ///
+    /// String params are substituted as text into every `{{param}}` placeholder of the
+    /// template first (e.g. `"@{{element}}Bonus"` with `element = "Fire"` reads `FireBonus`).
+    /// Numbers and booleans (`1.0`/`0.0`) are then used as in `apply_template`. Default
+    /// transforms only see numeric params.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - StatResolver to register stats in
+    /// * `template_name` - Name of the template to apply
+    /// * `stat_name` - Name for the stat (can be entity_id:stat_type format)
+    /// * `params` - Parameters to substitute in the template
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError` if the template is not found, the substituted template is
+    /// invalid, or parameter resolution fails.
+    pub fn apply_template_with_values(
+        &self,
+        resolver: &mut StatResolver,
+        template_name: &str,
+        stat_name: &str,
+        params: &HashMap<String, ParamValue>,
+    ) -> Result<(), YamlStatError> {
+        let (numbers, strings) = ParamValue::split(params);
+        if strings.is_empty() {
+            return self.apply_template(resolver, template_name, stat_name, &numbers);
+        }
+        let template = self
+            .get_template(template_name)?
+            .with_string_params(&strings)?;
+        self.apply_template_config(resolver, &template, None, stat_name, &numbers, true)
+    }
+
+    /// Registers a template's sources and transforms (see `apply_template`).
+    ///
+    /// `literal_sources` are the template's pre-resolved literal source values, if any. With
+    /// `record`, the registered entries replace the stat's recorded ones (see `Registry`).
+    fn apply_template_config(
+        &self,
+        resolver: &mut StatResolver,
+        template: &StatTemplate,
+        literal_sources: Option<&Vec<f64>>,
+        stat_name: &str,
+        params: &HashMap<String, f64>,
+        record: bool,
+    ) -> Result<(), YamlStatError> {
+        use zzstat::StatContext;
+
+        let stat_name = &self.canonical_stat_name(stat_name);
+        let stat_id = StatId::from_str(stat_name);
+
+        // Extract entity ID from entity_id:stat_type format
+        // If format is entity_id:stat_type, extract entity_id, otherwise empty string.
+        // Entity IDs may contain colons themselves (e.g. guild1:player3:HP).
+        let entity_id = if let Some(colon_pos) = stat_name.rfind(':') {
+            &stat_name[..colon_pos]
+        } else {
+            ""
+        };
+
+        let default_transforms: &[TransformConfig] = if template.ignore_default_transforms {
+            &[]
+        } else {
+            &self.default_transforms
+        };
+
+        let mut params = self.effective_params(entity_id, &template.params, params);
+        // Literal-only templates never resolve their base or sources again
+        let referenced = if literal_sources.is_some() {
+            template
+                .transforms
+                .iter()
+                .flat_map(TransformConfig::values)
+                .flat_map(SourceValue::param_names)
+                .map(str::to_string)
+                .collect()
+        } else {
+            template.required_params()
+        };
+        let referenced = referenced.into_iter().chain(
+            default_transforms
+                .iter()
+                .flat_map(TransformConfig::values)
+                .flat_map(SourceValue::param_names)
+                .map(str::to_string),
+        );
+        match self.missing_param_policy {
+            MissingParamPolicy::Error => {
+                // Report every missing param at once rather than failing at the first one
+                let mut missing: Vec<String> = referenced
+                    .filter(|name| !params.contains_key(name))
+                    .collect();
+                if !missing.is_empty() {
+                    missing.sort();
+                    missing.dedup();
+                    return Err(YamlStatError::InvalidConfig(format!(
+                        "Missing params for '{}': {}",
+                        stat_name,
+                        missing.join(", ")
+                    )));
+                }
+            }
+            MissingParamPolicy::Zero => {
+                for name in referenced {
+                    params.entry(name).or_insert(0.0);
+                }
+            }
+            MissingParamPolicy::Skip => {}
+        }
+        let params = &params;
+        let skip = |values: Vec<&SourceValue>| {
+            self.missing_param_policy == MissingParamPolicy::Skip
+                && values
+                    .into_iter()
+                    .flat_map(SourceValue::param_names)
+                    .any(|name| !params.contains_key(name))
+        };
+
+        let context = StatContext::new();
+        let mut entries = Vec::new();
+
+        // Literal-only templates reuse the values resolved when the manager was built
+        let literal_names = template
+            .base
+            .iter()
+            .map(|_| None)
+            .chain(template.sources.iter().map(SourceConfig::name));
+        for (&value, name) in literal_sources.into_iter().flatten().zip(literal_names) {
+            resolver.register_source(
+                stat_id.clone(),
+                Box::new(zzstat::source::ConstantSource(value)),
+            );
+            entries.push(RegisteredEntry::Source(name.map(str::to_string)));
+        }
+
+        // Add sources (the `base` shorthand comes first)
+        let base_source = template
+            .base
+            .clone()
+            .filter(|_| literal_sources.is_none())
+            .map(|value| SourceConfig::Constant { value, name: None });
+        let sources: &[SourceConfig] = if literal_sources.is_some() {
+            &[]
+        } else {
+            &template.sources
+        };
+
+        for source_config in base_source.iter().chain(sources) {
+            if skip(source_config.values()) {
+                continue;
+            }
+            // Stat-scaled sources need dependencies, so they run as the first transforms
+            let name = source_config.name().map(str::to_string);
+            if let Some(transform) = Self::resolve_stat_scaling(source_config, params, entity_id)? {
+                resolver.register_transform(stat_id.clone(), transform);
+                entries.push(RegisteredEntry::SourceTransform(name));
+                continue;
+            }
+            let resolved_source = Self::resolve_source(
+                source_config,
+                params,
+                self.require_explicit_level,
+                resolver,
+                entity_id,
+                &context,
+            )?;
+            resolver.register_source(stat_id.clone(), resolved_source);
+            entries.push(RegisteredEntry::Source(name));
+        }
+
+        // Add transformations (config-wide defaults run last)
+        let transforms = template
+            .transforms
+            .iter()
+            .chain(default_transforms)
+            .filter(|transform_config| !skip(transform_config.values()));
+        let transforms: Vec<Cow<TransformConfig>> = if self.multiplicative_as_additive {
+            TransformConfig::combine_multiplicatives(transforms, params)?
+        } else {
+            transforms.map(Cow::Borrowed).collect()
+        };
+        for transform_config in &transforms {
+            let resolved_transform =
+                Self::resolve_transform_with_entity(transform_config, params, entity_id)?;
+            resolver.register_transform(
+                stat_id.clone(),
+                ScopedTransform::wrap(
+                    &stat_id,
+                    NamedTransform::wrap(resolved_transform, transform_config.name()),
+                ),
+            );
+            let mut registered = transform_config.clone().into_owned();
+            // Bind clamp bounds so extract_template can rebuild the clamp exactly
+            if let TransformConfig::Clamp { min, max, .. } = &mut registered {
+                for bound in [min, max].into_iter().flatten() {
+                    if let Ok(value) = bound.resolve(params) {
+                        *bound = SourceValue::Number(value);
+                    }
+                }
+            }
+            entries.push(RegisteredEntry::Transform(registered));
+        }
+
+        if record {
+            self.registered
+                .lock()
+                .insert(stat_name.to_string(), entries);
+        }
+        Ok(())
+    }
+
+    /// Applies a template with parameters supplied as a JSON object.
+    ///
+    /// Numbers are used as-is, booleans become `1.0`/`0.0`, and numeric strings are parsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - StatResolver to register stats in
+    /// * `template_name` - Name of the template to apply
+    /// * `stat_name` - Name for the stat (can be entity_id:stat_type format)
+    /// * `params` - JSON object of parameter names to values
+    ///
+    /// # Errors
     ///
-    /// manager.apply_template(&mut resolver, "BaseHP", "player1_HP", &params)?;
-    /// # Ok::<(), zzstat_json::YamlStatError>(())
-    /// ```
-    pub fn apply_template(
+    /// Returns `YamlStatError::InvalidConfig` if `params` is not an object or a value is
+    /// an array, object, null, or non-numeric string, plus any error `apply_template` can return.
+    pub fn apply_template_json_params(
         &self,
         resolver: &mut StatResolver,
         template_name: &str,
         stat_name: &str,
-        params: &HashMap<String, f64>,
+        params: &serde_json::Value,
     ) -> Result<(), YamlStatError> {
-        use zzstat::StatContext;
+        let params = Self::params_from_json(params)?;
+        self.apply_template(resolver, template_name, stat_name, &params)
+    }
 
-        let template = self.templates.get(template_name).ok_or_else(|| {
-            YamlStatError::InvalidConfig(format!("Template not found: {}", template_name))
+    /// Coerces a JSON object into an f64 parameter map.
+    fn params_from_json(params: &serde_json::Value) -> Result<HashMap<String, f64>, YamlStatError> {
+        use serde_json::Value;
+
+        let object = params.as_object().ok_or_else(|| {
+            YamlStatError::InvalidConfig("Params must be a JSON object".to_string())
         })?;
 
-        let stat_id = StatId::from_str(stat_name);
+        object
+            .iter()
+            .map(|(key, value)| {
+                let number = match value {
+                    Value::Number(n) => n.as_f64(),
+                    Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+                    Value::String(s) => s.trim().parse::<f64>().ok(),
+                    Value::Null | Value::Array(_) | Value::Object(_) => None,
+                };
+                number.map(|n| (key.clone(), n)).ok_or_else(|| {
+                    YamlStatError::InvalidConfig(format!(
+                        "Unsupported value for param '{}': {}",
+                        key, value
+                    ))
+                })
+            })
+            .collect()
+    }
 
-        // Extract entity ID from entity_id:stat_type format
-        // If format is entity_id:stat_type, extract entity_id, otherwise empty string
-        let entity_id = if let Some(colon_pos) = stat_name.find(':') {
-            &stat_name[..colon_pos]
-        } else {
-            ""
-        };
+    /// Merges global constants, then the template's default params, then stored entity params,
+    /// under the per-call params (params win).
+    fn effective_params(
+        &self,
+        entity_id: &str,
+        defaults: &HashMap<String, f64>,
+        params: &HashMap<String, f64>,
+    ) -> HashMap<String, f64> {
+        let mut merged = self.constants.clone();
+        merged.extend(defaults.iter().map(|(k, v)| (k.clone(), *v)));
+        if let Some(entity_params) = self.entity_params.get(entity_id) {
+            merged.extend(entity_params.iter().map(|(k, v)| (k.clone(), *v)));
+        }
+        merged.extend(params.iter().map(|(k, v)| (k.clone(), *v)));
+        merged
+    }
 
-        let context = StatContext::new();
+    /// Reports template params that are supplied per call but also defined as global constants.
+    ///
+    /// The per-call value wins, so each reported name is an override of the constant. The
+    /// result is sorted so it can be compared or logged stably.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_name` - Name of the template to check
+    /// * `params` - Per-call parameters that would be passed to `apply_template`
+    ///
+    /// # Returns
+    ///
+    /// Names referenced by the template that exist both as constants and in `params`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError` if the template is not found.
+    pub fn shadowed_params(
+        &self,
+        template_name: &str,
+        params: &HashMap<String, f64>,
+    ) -> Result<Vec<String>, YamlStatError> {
+        let template = self.get_template(template_name)?;
 
-        // Add sources
-        for source_config in &template.sources {
-            let resolved_source =
-                Self::resolve_source(source_config, params, resolver, entity_id, &context)?;
-            resolver.register_source(stat_id.clone(), resolved_source);
-        }
+        let mut shadowed: Vec<String> = template
+            .required_params()
+            .into_iter()
+            .filter(|name| self.constants.contains_key(name) && params.contains_key(name))
+            .collect();
+        shadowed.sort();
+        Ok(shadowed)
+    }
 
-        // Add transformations
-        for transform_config in &template.transforms {
-            let resolved_transform =
-                Self::resolve_transform_with_entity(transform_config, params, entity_id)?;
-            resolver.register_transform(stat_id.clone(), resolved_transform);
+    /// Compares params against what a template references, before applying it.
+    ///
+    /// Params referenced by the config-wide default transforms count as referenced unless the
+    /// template sets `ignore_default_transforms`. Global constants satisfy a referenced param
+    /// but are never reported as unused.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_name` - Name of the template to check
+    /// * `params` - Per-call parameters that would be passed to `apply_template`
+    ///
+    /// # Returns
+    ///
+    /// A `ParamCheck` with the missing and unused param names.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError` if the template is not found.
+    pub fn check_params(
+        &self,
+        template_name: &str,
+        params: &HashMap<String, f64>,
+    ) -> Result<ParamCheck, YamlStatError> {
+        let template = self.get_template(template_name)?;
+
+        let mut referenced = template.required_params();
+        if !template.ignore_default_transforms {
+            referenced.extend(
+                self.default_transforms
+                    .iter()
+                    .flat_map(TransformConfig::values)
+                    .flat_map(SourceValue::param_names)
+                    .map(str::to_string),
+            );
         }
 
-        Ok(())
+        let mut missing: Vec<String> = referenced
+            .iter()
+            .filter(|name| {
+                !params.contains_key(*name)
+                    && !template.params.contains_key(*name)
+                    && !self.constants.contains_key(*name)
+            })
+            .cloned()
+            .collect();
+        missing.sort();
+
+        let mut unused: Vec<String> = params
+            .keys()
+            .filter(|name| !referenced.contains(*name))
+            .cloned()
+            .collect();
+        unused.sort();
+
+        Ok(ParamCheck { missing, unused })
     }
 
     /// Applies multiple stats at once.
@@ -362,22 +2386,33 @@ impl StatTemplateManager {
     }
 
     /// Resolves source configuration with parameters to create a StatSource.
-    fn resolve_source(
+    ///
+    /// With `require_explicit_level`, a scaling source without `level` uses the `level`
+    /// param and errors if it is missing, instead of defaulting to 1.0.
+    pub(crate) fn resolve_source(
         config: &SourceConfig,
         params: &HashMap<String, f64>,
+        require_explicit_level: bool,
         _resolver: &StatResolver,
         _entity_id: &str,
         _context: &zzstat::StatContext,
     ) -> Result<Box<dyn StatSource>, YamlStatError> {
         use zzstat::source::ConstantSource;
 
+        let value = Self::resolve_source_value(config, params, require_explicit_level)?;
+        Ok(Box::new(ConstantSource(value)))
+    }
+
+    /// Computes the constant value a source contributes, with the same rules as `resolve_source`.
+    pub(crate) fn resolve_source_value(
+        config: &SourceConfig,
+        params: &HashMap<String, f64>,
+        require_explicit_level: bool,
+    ) -> Result<f64, YamlStatError> {
         match config {
-            SourceConfig::Constant { value, name: _ } => {
-                let resolved_value = value.resolve(params).map_err(|e| {
-                    YamlStatError::InvalidConfig(format!("Source resolution error: {}", e))
-                })?;
-                Ok(Box::new(ConstantSource(resolved_value)))
-            }
+            SourceConfig::Constant { value, name: _ } => value.resolve(params).map_err(|e| {
+                YamlStatError::InvalidConfig(format!("Source resolution error: {}", e))
+            }),
 
             SourceConfig::Scaling {
                 base,
@@ -391,18 +2426,116 @@ impl StatTemplateManager {
                 let scale_val = scale.resolve(params).map_err(|e| {
                     YamlStatError::InvalidConfig(format!("Scale resolution error: {}", e))
                 })?;
-                let level_val = level
-                    .as_ref()
-                    .map(|l| l.resolve(params))
-                    .transpose()
-                    .map_err(|e| {
+                let level_val = match level {
+                    Some(LevelValue::Value(level)) => level.resolve(params).map_err(|e| {
                         YamlStatError::InvalidConfig(format!("Level resolution error: {}", e))
-                    })?
-                    .unwrap_or(1.0);
+                    })?,
+                    Some(LevelValue::Stat(_)) => {
+                        return Err(YamlStatError::InvalidConfig(
+                            "Stat levels must be registered via resolve_stat_scaling".to_string(),
+                        ));
+                    }
+                    None if require_explicit_level => *params
+                        .get("level")
+                        .ok_or_else(|| YamlStatError::MissingDependency("level".to_string()))?,
+                    None => 1.0,
+                };
+
+                Ok(base_val + (scale_val * level_val))
+            }
 
-                let value = base_val + (scale_val * level_val);
-                Ok(Box::new(ConstantSource(value)))
+            SourceConfig::Curve {
+                level,
+                points,
+                name: _,
+            } => {
+                let level_val = level.resolve(params).map_err(|e| {
+                    YamlStatError::InvalidConfig(format!("Level resolution error: {}", e))
+                })?;
+                curve_value(points, level_val)
             }
+
+            SourceConfig::Percentage { .. } => Err(YamlStatError::InvalidConfig(
+                "Percentage sources must be registered via resolve_stat_scaling".to_string(),
+            )),
+        }
+    }
+
+    /// Builds the transform for a source that depends on another stat: a scaling source
+    /// whose level is a stat, a percentage source, or a constant `@stat` reference.
+    ///
+    /// Returns `None` for every other source, which should go through `resolve_source`.
+    pub(crate) fn resolve_stat_scaling(
+        config: &SourceConfig,
+        params: &HashMap<String, f64>,
+        entity_id: &str,
+    ) -> Result<Option<Box<dyn StatTransform>>, YamlStatError> {
+        use crate::transform::StatScalingTransform;
+
+        if let SourceConfig::Constant { value, .. } = config
+            && let Some(stat) = value.stat_ref()
+        {
+            return Ok(Some(Box::new(StatScalingTransform::new(
+                0.0,
+                1.0,
+                Self::dependency_stat_id(entity_id, stat),
+            ))));
+        }
+
+        if let SourceConfig::Percentage { of, percent, .. } = config {
+            let percent_val = percent.resolve(params).map_err(|e| {
+                YamlStatError::InvalidConfig(format!("Percent resolution error: {}", e))
+            })?;
+            return Ok(Some(Box::new(StatScalingTransform::new(
+                0.0,
+                percent_val / 100.0,
+                Self::dependency_stat_id(entity_id, of),
+            ))));
+        }
+
+        let SourceConfig::Scaling {
+            base,
+            scale,
+            level: Some(LevelValue::Stat(StatLevel::Stat { stat: level_stat })),
+            ..
+        } = config
+        else {
+            return Ok(None);
+        };
+
+        let base_val = base
+            .resolve(params)
+            .map_err(|e| YamlStatError::InvalidConfig(format!("Base resolution error: {}", e)))?;
+        let scale_val = scale
+            .resolve(params)
+            .map_err(|e| YamlStatError::InvalidConfig(format!("Scale resolution error: {}", e)))?;
+
+        Ok(Some(Box::new(StatScalingTransform::new(
+            base_val,
+            scale_val,
+            Self::dependency_stat_id(entity_id, level_stat),
+        ))))
+    }
+
+    /// Splits a clamp bound into its numeric value and bound stat, moving an `@stat` value
+    /// into the stat slot.
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if the value is an `@stat` reference and the
+    /// matching `min_stat`/`max_stat` is also set.
+    fn clamp_bound<'a>(
+        value: &'a Option<SourceValue>,
+        stat: &'a Option<String>,
+        bound: &str,
+    ) -> Result<(Option<&'a SourceValue>, Option<&'a str>), YamlStatError> {
+        match value.as_ref().and_then(SourceValue::stat_ref) {
+            Some(_) if stat.is_some() => Err(YamlStatError::InvalidConfig(format!(
+                "clamp {} is a stat reference but {}_stat is also set",
+                bound, bound
+            ))),
+            Some(reference) => Ok((None, Some(reference))),
+            None => Ok((value.as_ref(), stat.as_deref())),
         }
     }
 
@@ -412,7 +2545,13 @@ impl StatTemplateManager {
         params: &HashMap<String, f64>,
         entity_id: &str,
     ) -> Result<Box<dyn StatTransform>, YamlStatError> {
-        use crate::transform::AdditiveTransform;
+        use crate::transform::{
+            AdditiveTransform, CeilToStatTransform, DivideTransform, FloorToStatTransform,
+            MaxTransform, MinTransform, PercentIncreaseTransform, PowerTransform,
+            QuantizeTransform, RampTransform, RateLimitTransform, RemapTransform, RoundMode,
+            RoundTransform, StatClampTransform, SubtractiveTransform, TableLookupTransform,
+            TimedAdditiveTransform,
+        };
         use zzstat::transform::{ClampTransform, MultiplicativeTransform};
 
         match config {
@@ -430,9 +2569,68 @@ impl StatTemplateManager {
                 Ok(Box::new(AdditiveTransform::new(resolved_value)))
             }
 
-            TransformConfig::Clamp { min, max, name: _ } => {
+            TransformConfig::Subtractive { value, name: _ } => {
+                let resolved_value = value.resolve(params).map_err(|e| {
+                    YamlStatError::InvalidConfig(format!("Transform resolution error: {}", e))
+                })?;
+                Ok(Box::new(SubtractiveTransform::new(resolved_value)))
+            }
+
+            TransformConfig::Divide { value, name: _ } => {
+                let resolved_value = value.resolve(params).map_err(|e| {
+                    YamlStatError::InvalidConfig(format!("Transform resolution error: {}", e))
+                })?;
+                Ok(Box::new(DivideTransform::new(resolved_value)?))
+            }
+
+            TransformConfig::Power { exponent, name: _ } => {
+                let resolved_exponent = exponent.resolve(params).map_err(|e| {
+                    YamlStatError::InvalidConfig(format!("Transform resolution error: {}", e))
+                })?;
+                Ok(Box::new(PowerTransform::new(resolved_exponent)))
+            }
+
+            TransformConfig::PercentAdd { percent, name: _ } => {
+                let resolved_percent = percent.resolve(params).map_err(|e| {
+                    YamlStatError::InvalidConfig(format!("Transform resolution error: {}", e))
+                })?;
+                // percent_add is percent_increase in percentage points
+                Ok(Box::new(PercentIncreaseTransform::new(
+                    resolved_percent / 100.0,
+                )))
+            }
+
+            TransformConfig::Quantize { step, name: _ } => {
+                let resolved_step = step.resolve(params).map_err(|e| {
+                    YamlStatError::InvalidConfig(format!("Transform resolution error: {}", e))
+                })?;
+                Ok(Box::new(QuantizeTransform::new(resolved_step)?))
+            }
+
+            TransformConfig::Min { value, name: _ } => {
+                let resolved_value = value.resolve(params).map_err(|e| {
+                    YamlStatError::InvalidConfig(format!("Transform resolution error: {}", e))
+                })?;
+                Ok(Box::new(MinTransform::new(resolved_value)))
+            }
+
+            TransformConfig::Max { value, name: _ } => {
+                let resolved_value = value.resolve(params).map_err(|e| {
+                    YamlStatError::InvalidConfig(format!("Transform resolution error: {}", e))
+                })?;
+                Ok(Box::new(MaxTransform::new(resolved_value)))
+            }
+
+            TransformConfig::Clamp {
+                min,
+                max,
+                min_stat,
+                max_stat,
+                name: _,
+            } => {
+                let (min, min_stat) = Self::clamp_bound(min, min_stat, "min")?;
+                let (max, max_stat) = Self::clamp_bound(max, max_stat, "max")?;
                 let min_val = min
-                    .as_ref()
                     .map(|m| m.resolve(params))
                     .transpose()
                     .map_err(|e| {
@@ -440,18 +2638,86 @@ impl StatTemplateManager {
                     })?
                     .unwrap_or(f64::NEG_INFINITY);
                 let max_val = max
-                    .as_ref()
                     .map(|m| m.resolve(params))
                     .transpose()
                     .map_err(|e| {
                         YamlStatError::InvalidConfig(format!("Clamp max resolution error: {}", e))
                     })?
                     .unwrap_or(f64::INFINITY);
-                Ok(Box::new(ClampTransform::new(min_val, max_val)))
+
+                if min_stat.is_none() && max_stat.is_none() {
+                    return Ok(Box::new(ClampTransform::new(min_val, max_val)));
+                }
+                let stat_id = |stat: &str| Self::dependency_stat_id(entity_id, stat);
+                Ok(Box::new(StatClampTransform::new(
+                    min_val,
+                    max_val,
+                    min_stat.map(stat_id),
+                    max_stat.map(stat_id),
+                )))
+            }
+
+            TransformConfig::Round {
+                decimals,
+                mode,
+                name: _,
+            } => {
+                let mode = mode.as_deref().map(RoundMode::parse).transpose()?;
+                Ok(Box::new(RoundTransform::with_mode(
+                    decimals.unwrap_or(0),
+                    mode.unwrap_or_default(),
+                )))
+            }
+
+            TransformConfig::Ramp {
+                per_stack,
+                stacks,
+                cap,
+                name: _,
+            } => {
+                let resolve = |value: &crate::config::SourceValue, field: &str| {
+                    value.resolve(params).map_err(|e| {
+                        YamlStatError::InvalidConfig(format!(
+                            "Ramp {} resolution error: {}",
+                            field, e
+                        ))
+                    })
+                };
+                Ok(Box::new(RampTransform::new(
+                    resolve(per_stack, "per_stack")?,
+                    resolve(stacks, "stacks")?,
+                    resolve(cap, "cap")?,
+                )))
+            }
+
+            TransformConfig::Remap {
+                in_min,
+                in_max,
+                out_min,
+                out_max,
+                clamp,
+                name: _,
+            } => {
+                let resolve = |value: &crate::config::SourceValue, field: &str| {
+                    value.resolve(params).map_err(|e| {
+                        YamlStatError::InvalidConfig(format!(
+                            "Remap {} resolution error: {}",
+                            field, e
+                        ))
+                    })
+                };
+                Ok(Box::new(RemapTransform::new(
+                    (resolve(in_min, "in_min")?, resolve(in_max, "in_max")?),
+                    (resolve(out_min, "out_min")?, resolve(out_max, "out_max")?),
+                    *clamp,
+                )?))
             }
 
             TransformConfig::Conditional {
                 condition_stat,
+                condition_context,
+                all,
+                any,
                 condition_value,
                 operator,
                 then,
@@ -459,7 +2725,10 @@ impl StatTemplateManager {
             } => {
                 use crate::transform_conditional::ConditionalTransform;
                 ConditionalTransform::from_config(
-                    condition_stat,
+                    condition_stat.as_deref(),
+                    condition_context.as_deref(),
+                    all,
+                    any,
                     *condition_value,
                     operator,
                     then,
@@ -470,6 +2739,237 @@ impl StatTemplateManager {
                 .map(|t| Box::new(t) as Box<dyn StatTransform>)
             }
 
+            TransformConfig::Range {
+                condition_stat,
+                min,
+                max,
+                inclusive,
+                then,
+                else_then,
+                name: _,
+            } => {
+                use crate::transform_conditional::RangeTransform;
+                RangeTransform::from_config(
+                    condition_stat,
+                    *min,
+                    *max,
+                    inclusive.unwrap_or(true),
+                    then,
+                    else_then,
+                    params,
+                    entity_id,
+                )
+                .map(|t| Box::new(t) as Box<dyn StatTransform>)
+            }
+
+            TransformConfig::BestOf {
+                options,
+                mode,
+                name: _,
+            } => {
+                use crate::transform_conditional::BestOfTransform;
+                BestOfTransform::from_config(options, mode, params)
+                    .map(|t| Box::new(t) as Box<dyn StatTransform>)
+            }
+
+            TransformConfig::ConditionalStat {
+                condition_stat,
+                condition_value,
+                operator,
+                then_stat,
+                else_stat,
+                name: _,
+            } => {
+                use crate::transform_conditional::{ConditionalStatTransform, condition_threshold};
+                Ok(Box::new(ConditionalStatTransform::new(
+                    Self::dependency_stat_id(entity_id, condition_stat),
+                    condition_threshold(operator, *condition_value)?,
+                    operator,
+                    Self::dependency_stat_id(entity_id, then_stat),
+                    Self::dependency_stat_id(entity_id, else_stat),
+                )?))
+            }
+
+            TransformConfig::FloorToStat { stat, name: _ } => Ok(Box::new(
+                FloorToStatTransform::new(Self::dependency_stat_id(entity_id, stat)),
+            )),
+
+            TransformConfig::CeilToStat { stat, name: _ } => Ok(Box::new(
+                CeilToStatTransform::new(Self::dependency_stat_id(entity_id, stat)),
+            )),
+
+            TransformConfig::MinOfStats { stats, name: _ } => {
+                use crate::transform_map::MinOfStatsTransform;
+
+                let stats = stats
+                    .iter()
+                    .map(|stat| Self::dependency_stat_id(entity_id, stat))
+                    .collect();
+                Ok(Box::new(MinOfStatsTransform::new(stats)?))
+            }
+
+            TransformConfig::MaxOfStats { stats, name: _ } => {
+                use crate::transform_map::MaxOfStatsTransform;
+
+                let stats = stats
+                    .iter()
+                    .map(|stat| Self::dependency_stat_id(entity_id, stat))
+                    .collect();
+                Ok(Box::new(MaxOfStatsTransform::new(stats)?))
+            }
+
+            TransformConfig::WeightedAverage { terms, name: _ } => {
+                use crate::transform_map::WeightedAverageTransform;
+
+                let terms = terms
+                    .iter()
+                    .map(|(stat, weight)| {
+                        let weight_val = weight.resolve(params).map_err(|e| {
+                            YamlStatError::InvalidConfig(format!("Weight resolution error: {}", e))
+                        })?;
+                        Ok((Self::dependency_stat_id(entity_id, stat), weight_val))
+                    })
+                    .collect::<Result<Vec<_>, YamlStatError>>()?;
+
+                Ok(Box::new(WeightedAverageTransform::new(terms)?))
+            }
+
+            TransformConfig::ExcessOf {
+                stat,
+                threshold,
+                factor,
+                name: _,
+            } => {
+                use crate::transform_map::ExcessOfTransform;
+
+                let resolve = |value: &crate::config::SourceValue, field: &str| {
+                    value.resolve(params).map_err(|e| {
+                        YamlStatError::InvalidConfig(format!(
+                            "Excess {} resolution error: {}",
+                            field, e
+                        ))
+                    })
+                };
+                Ok(Box::new(ExcessOfTransform::new(
+                    Self::dependency_stat_id(entity_id, stat),
+                    resolve(threshold, "threshold")?,
+                    resolve(factor, "factor")?,
+                )))
+            }
+
+            TransformConfig::ClampToStatFraction {
+                stat,
+                min_fraction,
+                max_fraction,
+                name: _,
+            } => {
+                use crate::transform_map::ClampToStatFractionTransform;
+
+                let resolve = |value: &Option<SourceValue>, field: &str| {
+                    value
+                        .as_ref()
+                        .map(|v| v.resolve(params))
+                        .transpose()
+                        .map_err(|e| {
+                            YamlStatError::InvalidConfig(format!(
+                                "Clamp {} resolution error: {}",
+                                field, e
+                            ))
+                        })
+                };
+                Ok(Box::new(ClampToStatFractionTransform::new(
+                    Self::dependency_stat_id(entity_id, stat),
+                    resolve(min_fraction, "min_fraction")?,
+                    resolve(max_fraction, "max_fraction")?,
+                )?))
+            }
+
+            TransformConfig::RatioScale {
+                numerator_stat,
+                denominator_stat,
+                factor,
+                name: _,
+            } => {
+                use crate::transform_map::RatioScaleTransform;
+
+                let factor_val = factor.resolve(params).map_err(|e| {
+                    YamlStatError::InvalidConfig(format!("Factor resolution error: {}", e))
+                })?;
+                Ok(Box::new(RatioScaleTransform::new(
+                    Self::dependency_stat_id(entity_id, numerator_stat),
+                    Self::dependency_stat_id(entity_id, denominator_stat),
+                    factor_val,
+                )))
+            }
+
+            TransformConfig::TimedAdditive {
+                value,
+                start_tick,
+                end_tick,
+                tick_key,
+                name: _,
+            } => {
+                let resolved_value = value.resolve(params).map_err(|e| {
+                    YamlStatError::InvalidConfig(format!("Transform resolution error: {}", e))
+                })?;
+                Ok(Box::new(TimedAdditiveTransform::new(
+                    resolved_value,
+                    *start_tick,
+                    *end_tick,
+                    tick_key.clone(),
+                )))
+            }
+
+            TransformConfig::PercentIncrease { value, name: _ } => {
+                let resolved_value = value.resolve(params).map_err(|e| {
+                    YamlStatError::InvalidConfig(format!("Transform resolution error: {}", e))
+                })?;
+                Ok(Box::new(PercentIncreaseTransform::new(resolved_value)))
+            }
+
+            TransformConfig::TableLookup {
+                key_param,
+                table,
+                default,
+                name: _,
+            } => {
+                let resolve = |value: &crate::config::SourceValue| {
+                    value.resolve(params).map_err(|e| {
+                        YamlStatError::InvalidConfig(format!("Table resolution error: {}", e))
+                    })
+                };
+                let table = table
+                    .iter()
+                    .map(|(key, value)| Ok((key.clone(), resolve(value)?)))
+                    .collect::<Result<HashMap<_, _>, YamlStatError>>()?;
+                let default = default.as_ref().map(resolve).transpose()?;
+                Ok(Box::new(TableLookupTransform::new(
+                    key_param.clone(),
+                    table,
+                    default,
+                )))
+            }
+
+            TransformConfig::RateLimit {
+                max_delta,
+                prev_key,
+                name: _,
+            } => {
+                let max_delta_val = max_delta.resolve(params).map_err(|e| {
+                    YamlStatError::InvalidConfig(format!("Max delta resolution error: {}", e))
+                })?;
+                if max_delta_val < 0.0 {
+                    return Err(YamlStatError::InvalidConfig(format!(
+                        "Rate limit max_delta must not be negative: {}",
+                        max_delta_val
+                    )));
+                }
+                Ok(Box::new(RateLimitTransform::new(
+                    max_delta_val,
+                    prev_key.clone(),
+                )))
+            }
+
             TransformConfig::Map {
                 dependencies,
                 multiplier,
@@ -477,17 +2977,10 @@ impl StatTemplateManager {
             } => {
                 use crate::transform_map::MapTransform;
 
-                let mut dependency_ids = Vec::new();
-                for dep_name in dependencies {
-                    let dep_stat_id = if !entity_id.is_empty() {
-                        // Entity-based: entity_id:stat_type format
-                        StatId::from_str(&format!("{}:{}", entity_id, dep_name))
-                    } else {
-                        // Global stat
-                        StatId::from_str(dep_name)
-                    };
-                    dependency_ids.push(dep_stat_id);
-                }
+                let dependency_ids = dependencies
+                    .iter()
+                    .map(|dep_name| Self::dependency_stat_id(entity_id, dep_name))
+                    .collect();
 
                 let multiplier_val = multiplier
                     .as_ref()
@@ -498,11 +2991,26 @@ impl StatTemplateManager {
                     })?
                     .unwrap_or(1.0);
 
-                Ok(Box::new(MapTransform::new(dependency_ids, multiplier_val)))
+                Ok(Box::new(MapTransform::try_new(
+                    dependency_ids,
+                    multiplier_val,
+                )?))
             }
         }
     }
 
+    /// Creates the StatId of a dependency stat.
+    ///
+    /// Entity-based stats use the `entity_id:stat_type` format; global stats (empty
+    /// `entity_id`) use the name as-is.
+    pub(crate) fn dependency_stat_id(entity_id: &str, stat_name: &str) -> StatId {
+        if entity_id.is_empty() {
+            StatId::from_str(stat_name)
+        } else {
+            StatId::from_str(&Self::entity_stat_id(entity_id, stat_name))
+        }
+    }
+
     /// Resolves transform configuration with parameters to create a StatTransform (public, without entity_id).
     pub(crate) fn resolve_transform(
         config: &TransformConfig,
@@ -511,3 +3019,35 @@ impl StatTemplateManager {
         Self::resolve_transform_with_entity(config, params, "")
     }
 }
+
+/// Quotes a CSV field if it contains a comma, quote, or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// 64-bit FNV-1a hash, stable across platforms and Rust versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Adds a parent's or mixin's default params without overriding the template's own.
+fn inherit_params(params: &mut HashMap<String, f64>, inherited: HashMap<String, f64>) {
+    for (name, value) in inherited {
+        params.entry(name).or_insert(value);
+    }
+}
+
+/// Adds a parent's or mixin's tags that the template does not already have.
+fn inherit_tags(tags: &mut Vec<String>, inherited: Vec<String>) {
+    for tag in inherited {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+}
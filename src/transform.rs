@@ -1,5 +1,8 @@
+use crate::error::YamlStatError;
 use std::collections::HashMap;
-use zzstat::{StatContext, StatError, StatId, StatTransform};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use zzstat::{StatContext, StatError, StatId, StatResolver, StatTransform};
 
 /// Additive transform - adds a constant value to the stat.
 pub struct AdditiveTransform {
@@ -35,3 +38,1031 @@ impl StatTransform for AdditiveTransform {
         format!("AdditiveTransform(+{})", self.value)
     }
 }
+
+/// Subtractive transform - subtracts a constant value from the stat.
+pub struct SubtractiveTransform {
+    value: f64,
+}
+
+impl SubtractiveTransform {
+    /// Creates a new SubtractiveTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Value to subtract from the stat
+    pub fn new(value: f64) -> Self {
+        Self { value }
+    }
+}
+
+impl StatTransform for SubtractiveTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        Vec::new() // Subtractive transform doesn't depend on other stats
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        _dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        Ok(value - self.value)
+    }
+
+    fn description(&self) -> String {
+        format!("SubtractiveTransform(-{})", self.value)
+    }
+}
+
+/// Divide transform - divides the stat by a constant value.
+pub struct DivideTransform {
+    divisor: f64,
+}
+
+impl DivideTransform {
+    /// Creates a new DivideTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `divisor` - Value to divide the stat by
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if `divisor` is zero.
+    pub fn new(divisor: f64) -> Result<Self, YamlStatError> {
+        if divisor == 0.0 {
+            return Err(YamlStatError::InvalidConfig(
+                "divide transform divisor is zero".to_string(),
+            ));
+        }
+        Ok(Self { divisor })
+    }
+}
+
+impl StatTransform for DivideTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        Vec::new()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        _dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        Ok(value / self.divisor)
+    }
+
+    fn description(&self) -> String {
+        format!("DivideTransform(/{})", self.divisor)
+    }
+}
+
+/// Power transform - raises the stat to a constant exponent.
+///
+/// A negative value with a fractional exponent is an error rather than `NaN`. zzstat
+/// resolves the whole graph on every `resolve`, so the error fails every stat resolved
+/// through the same resolver. The transform does not know its stat, so the error carries an
+/// empty stat ID; transforms registered by this crate attribute it to their stat (see
+/// `ScopedTransform`).
+pub struct PowerTransform {
+    exponent: f64,
+}
+
+impl PowerTransform {
+    /// Creates a new PowerTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `exponent` - Exponent to raise the stat to
+    pub fn new(exponent: f64) -> Self {
+        Self { exponent }
+    }
+}
+
+impl StatTransform for PowerTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        Vec::new()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        _dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        if self.exponent == 1.0 {
+            return Ok(value);
+        }
+        if value < 0.0 && self.exponent.fract() != 0.0 {
+            return Err(StatError::InvalidTransform(
+                StatId::from_str(""),
+                format!(
+                    "cannot raise negative value {} to fractional exponent {}",
+                    value, self.exponent
+                ),
+            ));
+        }
+        Ok(value.powf(self.exponent))
+    }
+
+    fn description(&self) -> String {
+        format!("PowerTransform(^{})", self.exponent)
+    }
+}
+
+/// Min transform - raises the stat to at least a lower bound.
+pub struct MinTransform {
+    bound: f64,
+}
+
+impl MinTransform {
+    /// Creates a new MinTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `bound` - Smallest allowed value
+    pub fn new(bound: f64) -> Self {
+        Self { bound }
+    }
+}
+
+impl StatTransform for MinTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        Vec::new()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        _dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        Ok(value.max(self.bound))
+    }
+
+    fn description(&self) -> String {
+        format!("MinTransform(>= {})", self.bound)
+    }
+}
+
+/// Max transform - lowers the stat to at most an upper bound.
+pub struct MaxTransform {
+    bound: f64,
+}
+
+impl MaxTransform {
+    /// Creates a new MaxTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `bound` - Largest allowed value
+    pub fn new(bound: f64) -> Self {
+        Self { bound }
+    }
+}
+
+impl StatTransform for MaxTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        Vec::new()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        _dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        Ok(value.min(self.bound))
+    }
+
+    fn description(&self) -> String {
+        format!("MaxTransform(<= {})", self.bound)
+    }
+}
+
+/// Additive transform whose value can be updated in place through a `MutableAdditiveHandle`.
+///
+/// Suited to bonuses that change every frame (e.g. a rage meter), which would otherwise
+/// need a new transform registered on each update.
+pub struct MutableAdditiveTransform {
+    value: Arc<AtomicU64>,
+}
+
+impl MutableAdditiveTransform {
+    /// Creates a new MutableAdditiveTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Initial value to add to the stat
+    pub fn new(value: f64) -> Self {
+        Self {
+            value: Arc::new(AtomicU64::new(value.to_bits())),
+        }
+    }
+
+    /// Returns a handle that updates this transform's value.
+    pub fn handle(&self) -> MutableAdditiveHandle {
+        MutableAdditiveHandle {
+            value: Arc::clone(&self.value),
+        }
+    }
+}
+
+impl StatTransform for MutableAdditiveTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        Vec::new()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        _dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        Ok(value + f64::from_bits(self.value.load(Ordering::Relaxed)))
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "MutableAdditiveTransform(+{})",
+            f64::from_bits(self.value.load(Ordering::Relaxed))
+        )
+    }
+}
+
+/// Handle for updating a registered `MutableAdditiveTransform`.
+#[derive(Debug, Clone)]
+pub struct MutableAdditiveHandle {
+    value: Arc<AtomicU64>,
+}
+
+impl MutableAdditiveHandle {
+    /// Returns the current value.
+    pub fn get(&self) -> f64 {
+        f64::from_bits(self.value.load(Ordering::Relaxed))
+    }
+
+    /// Updates the value and invalidates the resolver's cache.
+    ///
+    /// The whole cache is cleared so stats depending on the updated one are recomputed too.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - Resolver the transform is registered in
+    /// * `value` - New value to add to the stat
+    pub fn set(&self, resolver: &mut StatResolver, value: f64) {
+        self.value.store(value.to_bits(), Ordering::Relaxed);
+        resolver.invalidate_all();
+    }
+}
+
+/// Remap transform - maps an input range linearly onto an output range.
+///
+/// Computes `out_min + (value - in_min) / (in_max - in_min) * (out_max - out_min)`.
+pub struct RemapTransform {
+    input: (f64, f64),
+    output: (f64, f64),
+    clamp: bool,
+}
+
+impl RemapTransform {
+    /// Creates a new RemapTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - `(in_min, in_max)` input range
+    /// * `output` - `(out_min, out_max)` output range
+    /// * `clamp` - Whether to keep the result inside the output range
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if the input range is empty (`in_min == in_max`).
+    pub fn new(input: (f64, f64), output: (f64, f64), clamp: bool) -> Result<Self, YamlStatError> {
+        if input.0 == input.1 {
+            return Err(YamlStatError::InvalidConfig(format!(
+                "Remap input range is empty: [{}, {}]",
+                input.0, input.1
+            )));
+        }
+        Ok(Self {
+            input,
+            output,
+            clamp,
+        })
+    }
+}
+
+impl StatTransform for RemapTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        Vec::new()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        _dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        let ((in_min, in_max), (out_min, out_max)) = (self.input, self.output);
+        let remapped = out_min + (value - in_min) / (in_max - in_min) * (out_max - out_min);
+        if self.clamp {
+            // max/min rather than `clamp` so a reversed output range cannot panic
+            Ok(remapped.max(out_min.min(out_max)).min(out_min.max(out_max)))
+        } else {
+            Ok(remapped)
+        }
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "RemapTransform([{}, {}] -> [{}, {}])",
+            self.input.0, self.input.1, self.output.0, self.output.1
+        )
+    }
+}
+
+/// Quantize transform - snaps the stat to the nearest multiple of a step.
+pub struct QuantizeTransform {
+    step: f64,
+}
+
+impl QuantizeTransform {
+    /// Creates a new QuantizeTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - Increment to snap to (e.g. 5.0 for multiples of 5)
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if `step` is zero.
+    pub fn new(step: f64) -> Result<Self, YamlStatError> {
+        if step == 0.0 {
+            return Err(YamlStatError::InvalidConfig(
+                "quantize transform step is zero".to_string(),
+            ));
+        }
+        Ok(Self { step })
+    }
+}
+
+impl StatTransform for QuantizeTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        Vec::new()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        _dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        Ok((value / self.step).round() * self.step)
+    }
+
+    fn description(&self) -> String {
+        format!("QuantizeTransform(step {})", self.step)
+    }
+}
+
+/// Direction a `RoundTransform` rounds in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundMode {
+    /// Round half away from zero (`f64::round`)
+    #[default]
+    Nearest,
+    /// Round down (`f64::floor`)
+    Floor,
+    /// Round up (`f64::ceil`)
+    Ceil,
+}
+
+impl RoundMode {
+    /// Parses a mode name as used in JSON (`"nearest"`, `"floor"`, or `"ceil"`).
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` listing the accepted values for an unknown mode.
+    pub fn parse(mode: &str) -> Result<Self, YamlStatError> {
+        match mode {
+            "nearest" => Ok(RoundMode::Nearest),
+            "floor" => Ok(RoundMode::Floor),
+            "ceil" => Ok(RoundMode::Ceil),
+            other => Err(YamlStatError::InvalidConfig(format!(
+                "Invalid round mode '{}' (expected one of: nearest, floor, ceil)",
+                other
+            ))),
+        }
+    }
+
+    fn apply(self, value: f64) -> f64 {
+        // Snap away float error first, so `0.29 * 100.0 = 28.999999999999996` floors to 29
+        let snapped = (value * 1e9).round() / 1e9;
+        let value = if snapped.is_finite() { snapped } else { value };
+        match self {
+            RoundMode::Nearest => value.round(),
+            RoundMode::Floor => value.floor(),
+            RoundMode::Ceil => value.ceil(),
+        }
+    }
+}
+
+/// Round transform - rounds the stat to a number of decimal places.
+pub struct RoundTransform {
+    decimals: u32,
+    mode: RoundMode,
+}
+
+impl RoundTransform {
+    /// Creates a new RoundTransform that rounds to the nearest value.
+    ///
+    /// # Arguments
+    ///
+    /// * `decimals` - Decimal places to keep (0 rounds to whole numbers)
+    pub fn new(decimals: u32) -> Self {
+        Self::with_mode(decimals, RoundMode::Nearest)
+    }
+
+    /// Creates a new RoundTransform with an explicit rounding direction.
+    ///
+    /// # Arguments
+    ///
+    /// * `decimals` - Decimal places to keep (0 rounds to whole numbers)
+    /// * `mode` - Whether to round to nearest, down, or up
+    pub fn with_mode(decimals: u32, mode: RoundMode) -> Self {
+        Self { decimals, mode }
+    }
+}
+
+impl StatTransform for RoundTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        Vec::new()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        _dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        if self.decimals == 0 {
+            return Ok(self.mode.apply(value));
+        }
+
+        let factor = 10f64.powi(self.decimals.min(i32::MAX as u32) as i32);
+        let scaled = value * factor;
+        if !scaled.is_finite() {
+            // Too many decimals to represent; the value is already as precise as it gets
+            return Ok(value);
+        }
+        Ok(self.mode.apply(scaled) / factor)
+    }
+
+    fn description(&self) -> String {
+        format!("RoundTransform({} decimals)", self.decimals)
+    }
+}
+
+/// Ramp transform - applies a percentage bonus that grows per stack, up to a cap.
+pub struct RampTransform {
+    per_stack: f64,
+    stacks: f64,
+    cap: f64,
+}
+
+impl RampTransform {
+    /// Creates a new RampTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `per_stack` - Percent bonus per stack
+    /// * `stacks` - Number of stacks
+    /// * `cap` - Maximum total percent bonus
+    pub fn new(per_stack: f64, stacks: f64, cap: f64) -> Self {
+        Self {
+            per_stack,
+            stacks,
+            cap,
+        }
+    }
+
+    /// Total percent bonus after applying the cap.
+    fn percent(&self) -> f64 {
+        (self.per_stack * self.stacks).min(self.cap)
+    }
+}
+
+impl StatTransform for RampTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        Vec::new()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        _dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        Ok(value * (1.0 + self.percent() / 100.0))
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "RampTransform({} stacks × {}%, cap {}% → +{}%)",
+            self.stacks,
+            self.per_stack,
+            self.cap,
+            self.percent()
+        )
+    }
+}
+
+/// Floor-to-stat transform - raises the stat to at least a dependency stat's value.
+pub struct FloorToStatTransform {
+    stat_id: StatId,
+}
+
+impl FloorToStatTransform {
+    /// Creates a new FloorToStatTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `stat_id` - Stat whose value forms the floor
+    pub fn new(stat_id: StatId) -> Self {
+        Self { stat_id }
+    }
+}
+
+impl StatTransform for FloorToStatTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        vec![self.stat_id.clone()]
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        let floor = dependencies
+            .get(&self.stat_id)
+            .copied()
+            .ok_or_else(|| StatError::MissingDependency(self.stat_id.clone()))?;
+        Ok(value.max(floor))
+    }
+
+    fn description(&self) -> String {
+        format!("FloorToStatTransform(>= {})", self.stat_id)
+    }
+}
+
+/// Ceil-to-stat transform - lowers the stat to at most a dependency stat's value.
+pub struct CeilToStatTransform {
+    stat_id: StatId,
+}
+
+impl CeilToStatTransform {
+    /// Creates a new CeilToStatTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `stat_id` - Stat whose value forms the ceiling
+    pub fn new(stat_id: StatId) -> Self {
+        Self { stat_id }
+    }
+}
+
+impl StatTransform for CeilToStatTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        vec![self.stat_id.clone()]
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        let ceiling = dependencies
+            .get(&self.stat_id)
+            .copied()
+            .ok_or_else(|| StatError::MissingDependency(self.stat_id.clone()))?;
+        Ok(value.min(ceiling))
+    }
+
+    fn description(&self) -> String {
+        format!("CeilToStatTransform(<= {})", self.stat_id)
+    }
+}
+
+/// Scoped transform - attributes errors that name no stat to the stat it is registered on.
+///
+/// zzstat's `StatError::InvalidTransform` carries a stat ID, but a transform such as
+/// `PowerTransform` does not know which stat it belongs to and reports an empty one.
+pub(crate) struct ScopedTransform {
+    stat_id: StatId,
+    inner: Box<dyn StatTransform>,
+}
+
+impl ScopedTransform {
+    /// Wraps `inner`, registered on `stat_id`.
+    pub(crate) fn wrap(stat_id: &StatId, inner: Box<dyn StatTransform>) -> Box<dyn StatTransform> {
+        Box::new(Self {
+            stat_id: stat_id.clone(),
+            inner,
+        })
+    }
+}
+
+impl StatTransform for ScopedTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        self.inner.depends_on()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        dependencies: &HashMap<StatId, f64>,
+        context: &StatContext,
+    ) -> Result<f64, StatError> {
+        self.inner
+            .apply(value, dependencies, context)
+            .map_err(|error| match error {
+                StatError::InvalidTransform(stat_id, message) if stat_id.as_str().is_empty() => {
+                    StatError::InvalidTransform(self.stat_id.clone(), message)
+                }
+                other => other,
+            })
+    }
+
+    fn description(&self) -> String {
+        self.inner.description()
+    }
+}
+
+/// Named transform - labels another transform in breakdowns with its config `name`.
+pub struct NamedTransform {
+    name: String,
+    inner: Box<dyn StatTransform>,
+}
+
+impl NamedTransform {
+    /// Creates a new NamedTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Label reported as the transform's description
+    /// * `inner` - Transform that does the actual work
+    pub fn new(name: impl Into<String>, inner: Box<dyn StatTransform>) -> Self {
+        Self {
+            name: name.into(),
+            inner,
+        }
+    }
+
+    /// Wraps `inner` when a name is given, otherwise returns it unchanged.
+    pub(crate) fn wrap(
+        inner: Box<dyn StatTransform>,
+        name: Option<&str>,
+    ) -> Box<dyn StatTransform> {
+        match name {
+            Some(name) => Box::new(Self::new(name, inner)),
+            None => inner,
+        }
+    }
+}
+
+impl StatTransform for NamedTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        self.inner.depends_on()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        dependencies: &HashMap<StatId, f64>,
+        context: &StatContext,
+    ) -> Result<f64, StatError> {
+        self.inner.apply(value, dependencies, context)
+    }
+
+    fn description(&self) -> String {
+        self.name.clone()
+    }
+}
+
+/// Stat scaling transform - adds `base + scale * stat`, for scaling sources whose level is a stat
+/// and percentage sources.
+///
+/// zzstat sources cannot have dependencies, so these sources are registered as transforms
+/// ahead of the stat's own transforms.
+pub struct StatScalingTransform {
+    base: f64,
+    scale: f64,
+    level_stat: StatId,
+}
+
+impl StatScalingTransform {
+    /// Creates a new StatScalingTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - Flat value added regardless of the level
+    /// * `scale` - Amount added per point of the level stat
+    /// * `level_stat` - Stat whose resolved value is the level
+    pub fn new(base: f64, scale: f64, level_stat: StatId) -> Self {
+        Self {
+            base,
+            scale,
+            level_stat,
+        }
+    }
+}
+
+impl StatTransform for StatScalingTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        vec![self.level_stat.clone()]
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        let level = dependencies
+            .get(&self.level_stat)
+            .copied()
+            .ok_or_else(|| StatError::MissingDependency(self.level_stat.clone()))?;
+        Ok(value + self.base + self.scale * level)
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "StatScalingTransform({} + {} * {})",
+            self.base, self.scale, self.level_stat
+        )
+    }
+}
+
+/// Timed additive transform - adds a value while the context's tick is inside a window.
+pub struct TimedAdditiveTransform {
+    value: f64,
+    start_tick: f64,
+    end_tick: f64,
+    tick_key: String,
+}
+
+impl TimedAdditiveTransform {
+    /// Creates a new TimedAdditiveTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Value to add while active
+    /// * `start_tick` - First tick the value applies on
+    /// * `end_tick` - Tick the value stops applying on (exclusive)
+    /// * `tick_key` - Context key holding the current tick
+    pub fn new(value: f64, start_tick: f64, end_tick: f64, tick_key: impl Into<String>) -> Self {
+        Self {
+            value,
+            start_tick,
+            end_tick,
+            tick_key: tick_key.into(),
+        }
+    }
+}
+
+impl StatTransform for TimedAdditiveTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        Vec::new()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        _dependencies: &HashMap<StatId, f64>,
+        context: &StatContext,
+    ) -> Result<f64, StatError> {
+        match context.get::<f64>(&self.tick_key) {
+            Some(tick) if tick >= self.start_tick && tick < self.end_tick => Ok(value + self.value),
+            _ => Ok(value),
+        }
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "TimedAdditiveTransform(+{} for {} in [{}, {}))",
+            self.value, self.tick_key, self.start_tick, self.end_tick
+        )
+    }
+}
+
+/// Stat clamp transform - clamps between static bounds combined with stat-derived bounds.
+///
+/// The effective minimum is the larger of `min` and the `min_stat` value; the effective
+/// maximum is the smaller of `max` and the `max_stat` value.
+pub struct StatClampTransform {
+    min: f64,
+    max: f64,
+    min_stat: Option<StatId>,
+    max_stat: Option<StatId>,
+}
+
+impl StatClampTransform {
+    /// Creates a new StatClampTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - Static minimum (`f64::NEG_INFINITY` for none)
+    /// * `max` - Static maximum (`f64::INFINITY` for none)
+    /// * `min_stat` - Stat whose value is also a minimum
+    /// * `max_stat` - Stat whose value is also a maximum
+    pub fn new(min: f64, max: f64, min_stat: Option<StatId>, max_stat: Option<StatId>) -> Self {
+        Self {
+            min,
+            max,
+            min_stat,
+            max_stat,
+        }
+    }
+}
+
+impl StatTransform for StatClampTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        self.min_stat
+            .iter()
+            .chain(&self.max_stat)
+            .cloned()
+            .collect()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        let bound = |stat: &Option<StatId>| {
+            stat.as_ref()
+                .map(|id| {
+                    dependencies
+                        .get(id)
+                        .copied()
+                        .ok_or_else(|| StatError::MissingDependency(id.clone()))
+                })
+                .transpose()
+        };
+        let min = bound(&self.min_stat)?.map_or(self.min, |m| m.max(self.min));
+        let max = bound(&self.max_stat)?.map_or(self.max, |m| m.min(self.max));
+        Ok(value.max(min).min(max))
+    }
+
+    fn description(&self) -> String {
+        let side = |value: f64, stat: &Option<StatId>| match stat {
+            Some(stat) => format!("{}|{}", value, stat),
+            None => value.to_string(),
+        };
+        format!(
+            "StatClampTransform({}, {})",
+            side(self.min, &self.min_stat),
+            side(self.max, &self.max_stat)
+        )
+    }
+}
+
+/// Rate limit transform - keeps the value within `max_delta` of a previous value.
+///
+/// The previous value comes from the context, so smoothing stays stateless: callers store
+/// the last resolved value and pass it back on the next resolve.
+pub struct RateLimitTransform {
+    max_delta: f64,
+    prev_key: String,
+}
+
+impl RateLimitTransform {
+    /// Creates a new RateLimitTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_delta` - Largest allowed change from the previous value
+    /// * `prev_key` - Context key holding the previous value
+    pub fn new(max_delta: f64, prev_key: impl Into<String>) -> Self {
+        Self {
+            max_delta,
+            prev_key: prev_key.into(),
+        }
+    }
+}
+
+impl StatTransform for RateLimitTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        Vec::new()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        _dependencies: &HashMap<StatId, f64>,
+        context: &StatContext,
+    ) -> Result<f64, StatError> {
+        match context.get::<f64>(&self.prev_key) {
+            Some(prev) => Ok(value.max(prev - self.max_delta).min(prev + self.max_delta)),
+            None => Ok(value),
+        }
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "RateLimitTransform(±{} from {})",
+            self.max_delta, self.prev_key
+        )
+    }
+}
+
+/// Percent increase transform - multiplies the stat by `1 + fraction`.
+pub struct PercentIncreaseTransform {
+    fraction: f64,
+}
+
+impl PercentIncreaseTransform {
+    /// Creates a new PercentIncreaseTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `fraction` - Increase as a fraction (0.2 for +20%)
+    pub fn new(fraction: f64) -> Self {
+        Self { fraction }
+    }
+}
+
+impl StatTransform for PercentIncreaseTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        Vec::new()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        _dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        Ok(value * (1.0 + self.fraction))
+    }
+
+    fn description(&self) -> String {
+        format!("PercentIncreaseTransform({:+}%)", self.fraction * 100.0)
+    }
+}
+
+/// Table lookup transform - multiplies the stat by a table entry chosen by a context key.
+pub struct TableLookupTransform {
+    key_param: String,
+    table: HashMap<String, f64>,
+    default: Option<f64>,
+}
+
+impl TableLookupTransform {
+    /// Creates a new TableLookupTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_param` - Context key holding the lookup key as a string
+    /// * `table` - Lookup key to multiplier
+    /// * `default` - Multiplier for missing or unknown keys (`None` leaves the value unchanged)
+    pub fn new(
+        key_param: impl Into<String>,
+        table: HashMap<String, f64>,
+        default: Option<f64>,
+    ) -> Self {
+        Self {
+            key_param: key_param.into(),
+            table,
+            default,
+        }
+    }
+}
+
+impl StatTransform for TableLookupTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        Vec::new()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        _dependencies: &HashMap<StatId, f64>,
+        context: &StatContext,
+    ) -> Result<f64, StatError> {
+        let multiplier = context
+            .get::<String>(&self.key_param)
+            .and_then(|key| self.table.get(&key).copied())
+            .or(self.default)
+            .unwrap_or(1.0);
+        Ok(value * multiplier)
+    }
+
+    fn description(&self) -> String {
+        format!("TableLookupTransform(× table[{}])", self.key_param)
+    }
+}
@@ -1,17 +1,64 @@
-use crate::config::TransformConfig;
+use crate::config::{SubCondition, TransformConfig};
 use crate::error::YamlStatError;
 use std::collections::HashMap;
 use zzstat::{StatContext, StatError, StatId, StatTransform};
 
-/// Conditional transform - applies different transforms based on a stat's value.
+/// Conditional transform - applies different transforms based on a stat's value, a context
+/// flag, or several stat conditions combined with AND/OR.
 pub struct ConditionalTransform {
-    condition_stat_id: StatId,
-    condition_value: f64,
-    operator: ConditionalOperator,
+    condition: Condition,
     then_transform: Box<dyn StatTransform>,
     else_transform: Option<Box<dyn StatTransform>>,
 }
 
+/// Where a conditional reads the value it compares.
+pub(crate) enum ConditionSource {
+    /// A dependency stat
+    Stat(StatId),
+    /// A `StatContext` key (missing or non-numeric keys read as 0, booleans as 1/0)
+    Context(String),
+}
+
+/// One `stat <operator> value` comparison of an `all`/`any` conditional.
+pub(crate) struct StatCondition {
+    stat_id: StatId,
+    operator: ConditionalOperator,
+    value: f64,
+}
+
+impl StatCondition {
+    fn holds(&self, dependencies: &HashMap<StatId, f64>) -> bool {
+        let stat_value = dependencies.get(&self.stat_id).copied().unwrap_or(0.0);
+        self.operator.evaluate(stat_value, self.value)
+    }
+
+    fn describe(&self) -> String {
+        format!("{} {} {}", self.stat_id, self.operator.as_str(), self.value)
+    }
+}
+
+/// What a conditional checks.
+pub(crate) enum Condition {
+    /// A single stat or context flag comparison
+    Single {
+        source: ConditionSource,
+        operator: ConditionalOperator,
+        value: f64,
+    },
+    /// Every sub-condition must hold
+    All(Vec<StatCondition>),
+    /// At least one sub-condition must hold
+    Any(Vec<StatCondition>),
+}
+
+/// Reads a numeric flag from the context, treating `true`/`false` as 1/0 and missing keys as 0.
+pub(crate) fn context_flag(context: &StatContext, key: &str) -> f64 {
+    context
+        .get::<f64>(key)
+        .or_else(|| context.get::<bool>(key).map(|b| if b { 1.0 } else { 0.0 }))
+        .unwrap_or(0.0)
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum ConditionalOperator {
     GreaterThan,
@@ -19,6 +66,11 @@ pub(crate) enum ConditionalOperator {
     GreaterThanOrEqual,
     LessThanOrEqual,
     Equal,
+    NotEqual,
+    /// Flag check: stat > 0.5 (condition value is ignored)
+    IsTrue,
+    /// Flag check: stat <= 0.5 (condition value is ignored)
+    IsFalse,
 }
 
 impl ConditionalOperator {
@@ -29,6 +81,9 @@ impl ConditionalOperator {
             ">=" => Ok(Self::GreaterThanOrEqual),
             "<=" => Ok(Self::LessThanOrEqual),
             "==" => Ok(Self::Equal),
+            "!=" => Ok(Self::NotEqual),
+            "is_true" => Ok(Self::IsTrue),
+            "is_false" => Ok(Self::IsFalse),
             _ => Err(format!("Invalid operator: {}", op)),
         }
     }
@@ -40,31 +95,83 @@ impl ConditionalOperator {
             Self::GreaterThanOrEqual => stat_value >= condition_value,
             Self::LessThanOrEqual => stat_value <= condition_value,
             Self::Equal => (stat_value - condition_value).abs() < f64::EPSILON,
+            Self::NotEqual => (stat_value - condition_value).abs() >= f64::EPSILON,
+            Self::IsTrue => stat_value > 0.5,
+            Self::IsFalse => stat_value <= 0.5,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::GreaterThan => ">",
+            Self::LessThan => "<",
+            Self::GreaterThanOrEqual => ">=",
+            Self::LessThanOrEqual => "<=",
+            Self::Equal => "==",
+            Self::NotEqual => "!=",
+            Self::IsTrue => "is_true",
+            Self::IsFalse => "is_false",
         }
     }
 }
 
+/// Returns the value a configured condition compares against.
+///
+/// The flag operators (`is_true`/`is_false`) ignore the value, so it may be omitted for
+/// them; every comparison operator needs one.
+///
+/// # Errors
+///
+/// Returns `YamlStatError::InvalidConfig` if the operator is invalid, or if a comparison
+/// operator has no `condition_value`.
+pub(crate) fn condition_threshold(
+    operator: &str,
+    condition_value: Option<f64>,
+) -> Result<f64, YamlStatError> {
+    let op = ConditionalOperator::from_str(operator)
+        .map_err(|e| YamlStatError::InvalidConfig(format!("Operator error: {}", e)))?;
+    match (op, condition_value) {
+        (_, Some(value)) => Ok(value),
+        (ConditionalOperator::IsTrue | ConditionalOperator::IsFalse, None) => Ok(0.0),
+        (_, None) => Err(YamlStatError::InvalidConfig(format!(
+            "operator {} needs a condition_value",
+            operator
+        ))),
+    }
+}
+
+/// Evaluates a condition given the condition stat's value.
+///
+/// # Errors
+///
+/// Returns `YamlStatError::InvalidConfig` if the operator is invalid, or if a comparison
+/// operator has no `condition_value`.
+pub(crate) fn evaluate_condition(
+    operator: &str,
+    stat_value: f64,
+    condition_value: Option<f64>,
+) -> Result<bool, YamlStatError> {
+    let threshold = condition_threshold(operator, condition_value)?;
+    let op = ConditionalOperator::from_str(operator)
+        .map_err(|e| YamlStatError::InvalidConfig(format!("Operator error: {}", e)))?;
+    Ok(op.evaluate(stat_value, threshold))
+}
+
 impl ConditionalTransform {
     /// Creates a new ConditionalTransform.
     ///
     /// # Arguments
     ///
-    /// * `condition_stat_id` - Stat ID to check
-    /// * `condition_value` - Value to compare against
-    /// * `operator` - Comparison operator
+    /// * `condition` - What to check
     /// * `then_transform` - Transform to apply when condition is met
     /// * `else_transform` - Transform to apply when condition is not met (optional)
     pub(crate) fn new(
-        condition_stat_id: StatId,
-        condition_value: f64,
-        operator: ConditionalOperator,
+        condition: Condition,
         then_transform: Box<dyn StatTransform>,
         else_transform: Option<Box<dyn StatTransform>>,
     ) -> Self {
         Self {
-            condition_stat_id,
-            condition_value,
-            operator,
+            condition,
             then_transform,
             else_transform,
         }
@@ -75,8 +182,11 @@ impl ConditionalTransform {
     /// # Arguments
     ///
     /// * `condition_stat` - Stat name to check
-    /// * `condition_value` - Value to compare against
-    /// * `operator` - Comparison operator string (">", "<", ">=", "<=", "==")
+    /// * `condition_context` - Context key to check instead of a stat
+    /// * `all` - Sub-conditions that must all hold (instead of a single condition)
+    /// * `any` - Sub-conditions of which at least one must hold (instead of a single condition)
+    /// * `condition_value` - Value to compare against (single condition)
+    /// * `operator` - Comparison operator string (">", "<", ">=", "<=", "==", "!=", "is_true", "is_false")
     /// * `then` - Transform config to apply when condition is met
     /// * `else_then` - Transform config to apply when condition is not met (optional)
     /// * `params` - Parameters for resolving transform configs
@@ -88,28 +198,66 @@ impl ConditionalTransform {
     ///
     /// # Errors
     ///
-    /// Returns `YamlStatError` if operator is invalid or transform resolution fails.
+    /// Returns `YamlStatError` if an operator is invalid, if not exactly one of
+    /// `condition_stat`, `condition_context`, `all`, and `any` is given, or if transform
+    /// resolution fails.
+    #[allow(clippy::too_many_arguments)]
     pub fn from_config(
-        condition_stat: &str,
-        condition_value: f64,
+        condition_stat: Option<&str>,
+        condition_context: Option<&str>,
+        all: &[SubCondition],
+        any: &[SubCondition],
+        condition_value: Option<f64>,
         operator: &str,
         then: &TransformConfig,
         else_then: &Option<Box<TransformConfig>>,
         params: &HashMap<String, f64>,
         entity_id: &str,
     ) -> Result<Self, YamlStatError> {
-        use zzstat::StatId;
-
-        // Create condition stat ID
-        let condition_stat_id = if !entity_id.is_empty() {
-            StatId::from_str(&format!("{}:{}", entity_id, condition_stat))
-        } else {
-            StatId::from_str(condition_stat)
+        let stat_id = |stat: &str| {
+            if !entity_id.is_empty() {
+                StatId::from_str(&format!("{}:{}", entity_id, stat))
+            } else {
+                StatId::from_str(stat)
+            }
+        };
+        let parse = |operator: &str| {
+            ConditionalOperator::from_str(operator)
+                .map_err(|e| YamlStatError::InvalidConfig(format!("Operator error: {}", e)))
+        };
+        let sub_conditions = |conditions: &[SubCondition]| {
+            conditions
+                .iter()
+                .map(|c| {
+                    Ok(StatCondition {
+                        stat_id: stat_id(&c.condition_stat),
+                        operator: parse(&c.operator)?,
+                        value: condition_threshold(&c.operator, c.condition_value)?,
+                    })
+                })
+                .collect::<Result<Vec<_>, YamlStatError>>()
         };
 
-        // Parse operator
-        let op = ConditionalOperator::from_str(operator)
-            .map_err(|e| YamlStatError::InvalidConfig(format!("Operator error: {}", e)))?;
+        let condition = match (condition_stat, condition_context, all, any) {
+            (Some(condition_stat), None, [], []) => Condition::Single {
+                source: ConditionSource::Stat(stat_id(condition_stat)),
+                operator: parse(operator)?,
+                value: condition_threshold(operator, condition_value)?,
+            },
+            (None, Some(key), [], []) => Condition::Single {
+                source: ConditionSource::Context(key.to_string()),
+                operator: parse(operator)?,
+                value: condition_threshold(operator, condition_value)?,
+            },
+            (None, None, [_, ..], []) => Condition::All(sub_conditions(all)?),
+            (None, None, [], [_, ..]) => Condition::Any(sub_conditions(any)?),
+            _ => {
+                return Err(YamlStatError::InvalidConfig(
+                    "conditional needs exactly one of condition_stat, condition_context, all, or any"
+                        .to_string(),
+                ));
+            }
+        };
 
         // Create then transform
         let then_transform = crate::template::StatTemplateManager::resolve_transform(then, params)?;
@@ -120,19 +268,22 @@ impl ConditionalTransform {
             .map(|e| crate::template::StatTemplateManager::resolve_transform(e, params))
             .transpose()?;
 
-        Ok(Self::new(
-            condition_stat_id,
-            condition_value,
-            op,
-            then_transform,
-            else_transform,
-        ))
+        Ok(Self::new(condition, then_transform, else_transform))
     }
 }
 
 impl StatTransform for ConditionalTransform {
     fn depends_on(&self) -> Vec<StatId> {
-        let mut deps = vec![self.condition_stat_id.clone()];
+        let mut deps = match &self.condition {
+            Condition::Single {
+                source: ConditionSource::Stat(id),
+                ..
+            } => vec![id.clone()],
+            Condition::Single { .. } => Vec::new(),
+            Condition::All(conditions) | Condition::Any(conditions) => {
+                conditions.iter().map(|c| c.stat_id.clone()).collect()
+            }
+        };
 
         // Add then transform's dependencies
         deps.extend(self.then_transform.depends_on());
@@ -151,41 +302,403 @@ impl StatTransform for ConditionalTransform {
         dependencies: &HashMap<StatId, f64>,
         context: &StatContext,
     ) -> Result<f64, StatError> {
-        // Get condition stat's value
+        // Evaluate condition against the condition stat (or context flag) values
+        let condition_met = match &self.condition {
+            Condition::Single {
+                source,
+                operator,
+                value: condition_value,
+            } => {
+                let condition_stat_value = match source {
+                    ConditionSource::Stat(id) => dependencies.get(id).copied().unwrap_or(0.0),
+                    ConditionSource::Context(key) => context_flag(context, key),
+                };
+                operator.evaluate(condition_stat_value, *condition_value)
+            }
+            Condition::All(conditions) => conditions.iter().all(|c| c.holds(dependencies)),
+            Condition::Any(conditions) => conditions.iter().any(|c| c.holds(dependencies)),
+        };
+
+        // Apply transform based on condition
+        if condition_met {
+            self.then_transform.apply(value, dependencies, context)
+        } else if let Some(ref else_transform) = self.else_transform {
+            else_transform.apply(value, dependencies, context)
+        } else {
+            // If no else transform, return value as-is
+            Ok(value)
+        }
+    }
+
+    fn description(&self) -> String {
+        let condition = match &self.condition {
+            Condition::Single {
+                source,
+                operator,
+                value,
+            } => {
+                let source = match source {
+                    ConditionSource::Stat(id) => id.to_string(),
+                    ConditionSource::Context(key) => format!("context.{}", key),
+                };
+                format!("{} {} {}", source, operator.as_str(), value)
+            }
+            Condition::All(conditions) => conditions
+                .iter()
+                .map(StatCondition::describe)
+                .collect::<Vec<_>>()
+                .join(" and "),
+            Condition::Any(conditions) => conditions
+                .iter()
+                .map(StatCondition::describe)
+                .collect::<Vec<_>>()
+                .join(" or "),
+        };
+        format!(
+            "ConditionalTransform(if {} then apply else {:?})",
+            condition,
+            self.else_transform.is_some()
+        )
+    }
+}
+
+/// Checks whether a value lies in a band (`min <= value <= max`, or strict when not inclusive).
+pub(crate) fn in_range(value: f64, min: f64, max: f64, inclusive: bool) -> bool {
+    if inclusive {
+        min <= value && value <= max
+    } else {
+        min < value && value < max
+    }
+}
+
+/// Range transform - applies one transform when a stat lies in a band, another otherwise.
+pub struct RangeTransform {
+    condition_stat_id: StatId,
+    min: f64,
+    max: f64,
+    inclusive: bool,
+    then_transform: Box<dyn StatTransform>,
+    else_transform: Option<Box<dyn StatTransform>>,
+}
+
+impl RangeTransform {
+    /// Creates a RangeTransform from TransformConfig.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition_stat` - Stat name to check
+    /// * `min` - Lower bound of the band
+    /// * `max` - Upper bound of the band
+    /// * `inclusive` - Whether the bounds are part of the band
+    /// * `then` - Transform config to apply when the stat is in range
+    /// * `else_then` - Transform config to apply when the stat is out of range (optional)
+    /// * `params` - Parameters for resolving transform configs
+    /// * `entity_id` - Entity ID (empty string for global stats)
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if `min` is greater than `max`, or
+    /// `YamlStatError` if transform resolution fails.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_config(
+        condition_stat: &str,
+        min: f64,
+        max: f64,
+        inclusive: bool,
+        then: &TransformConfig,
+        else_then: &Option<Box<TransformConfig>>,
+        params: &HashMap<String, f64>,
+        entity_id: &str,
+    ) -> Result<Self, YamlStatError> {
+        if min > max {
+            return Err(YamlStatError::InvalidConfig(format!(
+                "range min {} is greater than max {}",
+                min, max
+            )));
+        }
+
+        let condition_stat_id = if !entity_id.is_empty() {
+            StatId::from_str(&format!("{}:{}", entity_id, condition_stat))
+        } else {
+            StatId::from_str(condition_stat)
+        };
+
+        let then_transform = crate::template::StatTemplateManager::resolve_transform(then, params)?;
+        let else_transform = else_then
+            .as_ref()
+            .map(|e| crate::template::StatTemplateManager::resolve_transform(e, params))
+            .transpose()?;
+
+        Ok(Self {
+            condition_stat_id,
+            min,
+            max,
+            inclusive,
+            then_transform,
+            else_transform,
+        })
+    }
+}
+
+impl StatTransform for RangeTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        let mut deps = vec![self.condition_stat_id.clone()];
+        deps.extend(self.then_transform.depends_on());
+        if let Some(ref else_transform) = self.else_transform {
+            deps.extend(else_transform.depends_on());
+        }
+        deps
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        dependencies: &HashMap<StatId, f64>,
+        context: &StatContext,
+    ) -> Result<f64, StatError> {
         let condition_stat_value = dependencies
             .get(&self.condition_stat_id)
             .copied()
             .unwrap_or(0.0);
 
-        // Evaluate condition
-        let condition_met = self
-            .operator
-            .evaluate(condition_stat_value, self.condition_value);
-
-        // Apply transform based on condition
-        if condition_met {
+        if in_range(condition_stat_value, self.min, self.max, self.inclusive) {
             self.then_transform.apply(value, dependencies, context)
         } else if let Some(ref else_transform) = self.else_transform {
             else_transform.apply(value, dependencies, context)
         } else {
-            // If no else transform, return value as-is
             Ok(value)
         }
     }
 
     fn description(&self) -> String {
+        let (open, close) = if self.inclusive {
+            ('[', ']')
+        } else {
+            ('(', ')')
+        };
         format!(
-            "ConditionalTransform(if {} {} {} then apply else {:?})",
+            "RangeTransform(if {} in {}{}, {}{} then apply else {:?})",
             self.condition_stat_id,
-            match self.operator {
-                ConditionalOperator::GreaterThan => ">",
-                ConditionalOperator::LessThan => "<",
-                ConditionalOperator::GreaterThanOrEqual => ">=",
-                ConditionalOperator::LessThanOrEqual => "<=",
-                ConditionalOperator::Equal => "==",
-            },
-            self.condition_value,
+            open,
+            self.min,
+            self.max,
+            close,
             self.else_transform.is_some()
         )
     }
 }
+
+/// Which result a `BestOfTransform` keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BestOfMode {
+    /// Keep the largest result
+    Max,
+    /// Keep the smallest result
+    Min,
+}
+
+impl BestOfMode {
+    /// Parses `"max"` or `"min"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error message for any other string.
+    fn from_str(mode: &str) -> Result<Self, String> {
+        match mode {
+            "max" => Ok(BestOfMode::Max),
+            "min" => Ok(BestOfMode::Min),
+            _ => Err(format!(
+                "Invalid best_of mode: {} (expected \"max\" or \"min\")",
+                mode
+            )),
+        }
+    }
+
+    /// Returns the mode string used in configs.
+    fn as_str(&self) -> &'static str {
+        match self {
+            BestOfMode::Max => "max",
+            BestOfMode::Min => "min",
+        }
+    }
+}
+
+/// Best-of transform - applies each option to the incoming value and keeps the best result.
+pub struct BestOfTransform {
+    options: Vec<Box<dyn StatTransform>>,
+    mode: BestOfMode,
+}
+
+impl BestOfTransform {
+    /// Creates a new BestOfTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Alternative transforms, each applied to the same incoming value
+    /// * `mode` - Whether to keep the largest or smallest result
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if `options` is empty.
+    pub fn new(
+        options: Vec<Box<dyn StatTransform>>,
+        mode: BestOfMode,
+    ) -> Result<Self, YamlStatError> {
+        if options.is_empty() {
+            return Err(YamlStatError::InvalidConfig(
+                "best_of needs at least one option".to_string(),
+            ));
+        }
+        Ok(Self { options, mode })
+    }
+
+    /// Creates a BestOfTransform from TransformConfig.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Transform configs to choose between
+    /// * `mode` - Mode string ("max" or "min")
+    /// * `params` - Parameters for resolving transform configs
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if the mode is invalid or there are no options,
+    /// or `YamlStatError` if transform resolution fails.
+    pub fn from_config(
+        options: &[TransformConfig],
+        mode: &str,
+        params: &HashMap<String, f64>,
+    ) -> Result<Self, YamlStatError> {
+        let mode = BestOfMode::from_str(mode).map_err(YamlStatError::InvalidConfig)?;
+        let options = options
+            .iter()
+            .map(|option| crate::template::StatTemplateManager::resolve_transform(option, params))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(options, mode)
+    }
+}
+
+impl StatTransform for BestOfTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        self.options
+            .iter()
+            .flat_map(|option| option.depends_on())
+            .collect()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        dependencies: &HashMap<StatId, f64>,
+        context: &StatContext,
+    ) -> Result<f64, StatError> {
+        let mut best: Option<f64> = None;
+        for option in &self.options {
+            let result = option.apply(value, dependencies, context)?;
+            best = Some(match (best, self.mode) {
+                (None, _) => result,
+                (Some(best), BestOfMode::Max) => best.max(result),
+                (Some(best), BestOfMode::Min) => best.min(result),
+            });
+        }
+        Ok(best.unwrap_or(value))
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "BestOfTransform({} of {} options)",
+            self.mode.as_str(),
+            self.options.len()
+        )
+    }
+}
+
+/// Conditional stat transform - adds one of two dependent stats depending on a condition.
+pub struct ConditionalStatTransform {
+    condition_stat_id: StatId,
+    condition_value: f64,
+    operator: ConditionalOperator,
+    then_stat_id: StatId,
+    else_stat_id: StatId,
+}
+
+impl ConditionalStatTransform {
+    /// Creates a new ConditionalStatTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition_stat_id` - Stat ID to check
+    /// * `condition_value` - Value to compare against
+    /// * `operator` - Comparison operator string (">", "<", ">=", "<=", "==", "!=", "is_true", "is_false")
+    /// * `then_stat_id` - Stat ID added when the condition is met
+    /// * `else_stat_id` - Stat ID added when the condition is not met
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if the operator is invalid.
+    pub fn new(
+        condition_stat_id: StatId,
+        condition_value: f64,
+        operator: &str,
+        then_stat_id: StatId,
+        else_stat_id: StatId,
+    ) -> Result<Self, YamlStatError> {
+        let operator = ConditionalOperator::from_str(operator)
+            .map_err(|e| YamlStatError::InvalidConfig(format!("Operator error: {}", e)))?;
+        Ok(Self {
+            condition_stat_id,
+            condition_value,
+            operator,
+            then_stat_id,
+            else_stat_id,
+        })
+    }
+}
+
+impl StatTransform for ConditionalStatTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        vec![
+            self.condition_stat_id.clone(),
+            self.then_stat_id.clone(),
+            self.else_stat_id.clone(),
+        ]
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        let condition_stat_value = dependencies
+            .get(&self.condition_stat_id)
+            .copied()
+            .unwrap_or(0.0);
+
+        let selected = if self
+            .operator
+            .evaluate(condition_stat_value, self.condition_value)
+        {
+            &self.then_stat_id
+        } else {
+            &self.else_stat_id
+        };
+        let bonus = dependencies
+            .get(selected)
+            .copied()
+            .ok_or_else(|| StatError::MissingDependency(selected.clone()))?;
+
+        Ok(value + bonus)
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "ConditionalStatTransform(if {} {} {} then +{} else +{})",
+            self.condition_stat_id,
+            self.operator.as_str(),
+            self.condition_value,
+            self.then_stat_id,
+            self.else_stat_id
+        )
+    }
+}
@@ -1,3 +1,4 @@
+use crate::error::YamlStatError;
 use std::collections::HashMap;
 use zzstat::{StatContext, StatError, StatId, StatTransform};
 
@@ -24,6 +25,28 @@ impl MapTransform {
             multiplier,
         }
     }
+
+    /// Creates a new MapTransform, rejecting an empty dependency list.
+    ///
+    /// A map transform without dependencies always adds 0, which is almost certainly an
+    /// authoring mistake. Config-driven paths use this constructor.
+    ///
+    /// # Arguments
+    ///
+    /// * `dependencies` - Vector of stat IDs this transform depends on
+    /// * `multiplier` - Multiplier to apply to the sum of dependent stat values
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if `dependencies` is empty.
+    pub fn try_new(dependencies: Vec<StatId>, multiplier: f64) -> Result<Self, YamlStatError> {
+        if dependencies.is_empty() {
+            return Err(YamlStatError::InvalidConfig(
+                "Map transform requires at least one dependency".to_string(),
+            ));
+        }
+        Ok(Self::new(dependencies, multiplier))
+    }
 }
 
 impl StatTransform for MapTransform {
@@ -59,3 +82,383 @@ impl StatTransform for MapTransform {
         )
     }
 }
+
+/// Weighted average transform - adds the weighted mean of dependent stats.
+///
+/// Computes `Σ(dep_i * w_i) / Σ(w_i)`, so scaling every weight by the same factor leaves the
+/// result unchanged. The mean is added to the current stat value.
+pub struct WeightedAverageTransform {
+    terms: Vec<(StatId, f64)>,
+}
+
+impl WeightedAverageTransform {
+    /// Creates a new WeightedAverageTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `terms` - (stat ID, weight) pairs
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if the weights sum to zero.
+    pub fn new(terms: Vec<(StatId, f64)>) -> Result<Self, YamlStatError> {
+        let total_weight: f64 = terms.iter().map(|(_, weight)| weight).sum();
+        if total_weight == 0.0 {
+            return Err(YamlStatError::InvalidConfig(
+                "Weighted average weights sum to zero".to_string(),
+            ));
+        }
+        Ok(Self { terms })
+    }
+}
+
+impl StatTransform for WeightedAverageTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        self.terms
+            .iter()
+            .map(|(stat_id, _)| stat_id.clone())
+            .collect()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        let mut weighted_sum = 0.0;
+        let mut total_weight = 0.0;
+        for (dep_id, weight) in &self.terms {
+            let dep_value = dependencies
+                .get(dep_id)
+                .copied()
+                .ok_or_else(|| StatError::MissingDependency(dep_id.clone()))?;
+            weighted_sum += dep_value * weight;
+            total_weight += weight;
+        }
+
+        Ok(value + weighted_sum / total_weight)
+    }
+
+    fn description(&self) -> String {
+        format!("WeightedAverageTransform({:?})", self.terms)
+    }
+}
+
+/// Ratio scale transform - scales the value by the ratio of two dependent stats.
+///
+/// Computes `value * (1 + (numerator / denominator) * factor)`. A zero denominator is an
+/// error, and since zzstat resolves the whole graph on every `resolve`, it fails every stat
+/// resolved through the same resolver, not just this one.
+pub struct RatioScaleTransform {
+    numerator: StatId,
+    denominator: StatId,
+    factor: f64,
+}
+
+impl RatioScaleTransform {
+    /// Creates a new RatioScaleTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `numerator` - Stat ID in the numerator
+    /// * `denominator` - Stat ID in the denominator
+    /// * `factor` - Scale applied to the ratio
+    pub fn new(numerator: StatId, denominator: StatId, factor: f64) -> Self {
+        Self {
+            numerator,
+            denominator,
+            factor,
+        }
+    }
+}
+
+impl StatTransform for RatioScaleTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        vec![self.numerator.clone(), self.denominator.clone()]
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        let [numerator, denominator] = [&self.numerator, &self.denominator].map(|stat_id| {
+            dependencies
+                .get(stat_id)
+                .copied()
+                .ok_or_else(|| StatError::MissingDependency(stat_id.clone()))
+        });
+        let (numerator, denominator) = (numerator?, denominator?);
+        if denominator == 0.0 {
+            return Err(StatError::InvalidTransform(
+                self.denominator.clone(),
+                "ratio_scale denominator is zero".to_string(),
+            ));
+        }
+
+        Ok(value * (1.0 + (numerator / denominator) * self.factor))
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "RatioScaleTransform(× (1 + {}/{} * {}))",
+            self.numerator, self.denominator, self.factor
+        )
+    }
+}
+
+/// Excess-of transform - adds the part of a dependent stat above a threshold.
+///
+/// Computes `value + max(0, dep - threshold) * factor`, modelling soft breakpoints where
+/// only the overflow contributes.
+pub struct ExcessOfTransform {
+    stat: StatId,
+    threshold: f64,
+    factor: f64,
+}
+
+impl ExcessOfTransform {
+    /// Creates a new ExcessOfTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `stat` - Stat ID whose excess contributes
+    /// * `threshold` - Breakpoint below which nothing is added
+    /// * `factor` - Amount added per point above the threshold
+    pub fn new(stat: StatId, threshold: f64, factor: f64) -> Self {
+        Self {
+            stat,
+            threshold,
+            factor,
+        }
+    }
+}
+
+impl StatTransform for ExcessOfTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        vec![self.stat.clone()]
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        let dep_value = dependencies
+            .get(&self.stat)
+            .copied()
+            .ok_or_else(|| StatError::MissingDependency(self.stat.clone()))?;
+
+        Ok(value + (dep_value - self.threshold).max(0.0) * self.factor)
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "ExcessOfTransform(+ max(0, {} - {}) * {})",
+            self.stat, self.threshold, self.factor
+        )
+    }
+}
+
+/// Clamp-to-stat-fraction transform - bounds the value by fractions of a dependent stat.
+///
+/// Computes `clamp(value, min_fraction * dep, max_fraction * dep)`, e.g. a shield capped at
+/// 50% of max HP. A missing fraction leaves that side unbounded.
+pub struct ClampToStatFractionTransform {
+    stat: StatId,
+    min_fraction: Option<f64>,
+    max_fraction: Option<f64>,
+}
+
+impl ClampToStatFractionTransform {
+    /// Creates a new ClampToStatFractionTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `stat` - Stat ID the bounds are fractions of
+    /// * `min_fraction` - Lower bound as a fraction of the stat (optional)
+    /// * `max_fraction` - Upper bound as a fraction of the stat (optional)
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if neither fraction is given or if
+    /// `min_fraction` is greater than `max_fraction`.
+    pub fn new(
+        stat: StatId,
+        min_fraction: Option<f64>,
+        max_fraction: Option<f64>,
+    ) -> Result<Self, YamlStatError> {
+        match (min_fraction, max_fraction) {
+            (None, None) => {
+                return Err(YamlStatError::InvalidConfig(
+                    "clamp_to_stat_fraction needs min_fraction or max_fraction".to_string(),
+                ));
+            }
+            (Some(min), Some(max)) if min > max => {
+                return Err(YamlStatError::InvalidConfig(format!(
+                    "clamp_to_stat_fraction min_fraction {} is greater than max_fraction {}",
+                    min, max
+                )));
+            }
+            _ => {}
+        }
+        Ok(Self {
+            stat,
+            min_fraction,
+            max_fraction,
+        })
+    }
+}
+
+impl StatTransform for ClampToStatFractionTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        vec![self.stat.clone()]
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        let dep_value = dependencies
+            .get(&self.stat)
+            .copied()
+            .ok_or_else(|| StatError::MissingDependency(self.stat.clone()))?;
+
+        let min = self
+            .min_fraction
+            .map_or(f64::NEG_INFINITY, |f| f * dep_value);
+        let max = self.max_fraction.map_or(f64::INFINITY, |f| f * dep_value);
+        Ok(value.max(min).min(max))
+    }
+
+    fn description(&self) -> String {
+        let side = |fraction: Option<f64>, unbounded: &str| match fraction {
+            Some(fraction) => format!("{} × {}", fraction, self.stat),
+            None => unbounded.to_string(),
+        };
+        format!(
+            "ClampToStatFractionTransform(clamp({}, {}))",
+            side(self.min_fraction, "-∞"),
+            side(self.max_fraction, "∞")
+        )
+    }
+}
+
+/// Min-of-stats transform - floors the value at the smallest of several dependent stats.
+///
+/// Computes `value.max(min(deps))`, generalizing `FloorToStatTransform` to multiple inputs.
+pub struct MinOfStatsTransform {
+    stats: Vec<StatId>,
+}
+
+impl MinOfStatsTransform {
+    /// Creates a new MinOfStatsTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `stats` - Stat IDs whose minimum forms the floor
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if `stats` is empty.
+    pub fn new(stats: Vec<StatId>) -> Result<Self, YamlStatError> {
+        if stats.is_empty() {
+            return Err(YamlStatError::InvalidConfig(
+                "min_of_stats transform requires at least one stat".to_string(),
+            ));
+        }
+        Ok(Self { stats })
+    }
+}
+
+impl StatTransform for MinOfStatsTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        self.stats.clone()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        let floor = dependency_values(&self.stats, dependencies)?
+            .into_iter()
+            .fold(f64::INFINITY, f64::min);
+        Ok(value.max(floor))
+    }
+
+    fn description(&self) -> String {
+        format!("MinOfStatsTransform(floor at min of {:?})", self.stats)
+    }
+}
+
+/// Max-of-stats transform - caps the value at the largest of several dependent stats.
+///
+/// Computes `value.min(max(deps))`, generalizing `CeilToStatTransform` to multiple inputs.
+pub struct MaxOfStatsTransform {
+    stats: Vec<StatId>,
+}
+
+impl MaxOfStatsTransform {
+    /// Creates a new MaxOfStatsTransform.
+    ///
+    /// # Arguments
+    ///
+    /// * `stats` - Stat IDs whose maximum forms the ceiling
+    ///
+    /// # Errors
+    ///
+    /// Returns `YamlStatError::InvalidConfig` if `stats` is empty.
+    pub fn new(stats: Vec<StatId>) -> Result<Self, YamlStatError> {
+        if stats.is_empty() {
+            return Err(YamlStatError::InvalidConfig(
+                "max_of_stats transform requires at least one stat".to_string(),
+            ));
+        }
+        Ok(Self { stats })
+    }
+}
+
+impl StatTransform for MaxOfStatsTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        self.stats.clone()
+    }
+
+    fn apply(
+        &self,
+        value: f64,
+        dependencies: &HashMap<StatId, f64>,
+        _context: &StatContext,
+    ) -> Result<f64, StatError> {
+        let ceiling = dependency_values(&self.stats, dependencies)?
+            .into_iter()
+            .fold(f64::NEG_INFINITY, f64::max);
+        Ok(value.min(ceiling))
+    }
+
+    fn description(&self) -> String {
+        format!("MaxOfStatsTransform(cap at max of {:?})", self.stats)
+    }
+}
+
+/// Looks up every stat in the resolved dependencies.
+fn dependency_values(
+    stats: &[StatId],
+    dependencies: &HashMap<StatId, f64>,
+) -> Result<Vec<f64>, StatError> {
+    stats
+        .iter()
+        .map(|id| {
+            dependencies
+                .get(id)
+                .copied()
+                .ok_or_else(|| StatError::MissingDependency(id.clone()))
+        })
+        .collect()
+}